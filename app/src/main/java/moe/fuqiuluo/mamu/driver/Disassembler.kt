@@ -9,18 +9,84 @@ package moe.fuqiuluo.mamu.driver
  * @param mnemonic Instruction mnemonic (e.g., "ldr", "mov").
  * @param operands Instruction operands (e.g., "x0, [x1, #8]").
  * @param pseudoCode Simplified pseudo-code representation (optional).
+ * @param groups Instruction groups, e.g. "jump", "call", "return" (pseudo-code mode only).
+ * @param regsRead Registers implicitly read by the instruction (pseudo-code mode only).
+ * @param regsWritten Registers implicitly written by the instruction (pseudo-code mode only).
+ * @param operandsDetail Structured operands (pseudo-code mode only).
+ * @param id Capstone instruction ID, 0 for ".byte" data entries.
+ * @param size Instruction size in bytes.
  */
 data class DisassemblyResult(
     val address: Long,
     val bytes: String,
     val mnemonic: String,
     val operands: String,
-    val pseudoCode: String?
+    val pseudoCode: String?,
+    val groups: Array<String> = emptyArray(),
+    val regsRead: Array<String> = emptyArray(),
+    val regsWritten: Array<String> = emptyArray(),
+    val operandsDetail: Array<DisassemblyOperand> = emptyArray(),
+    val id: Int = 0,
+    val size: Int = 0
 )
 
 /**
- * ARM instruction disassembler using Capstone engine.
- * Supports ARM32, Thumb, and ARM64 architectures.
+ * Structured instruction operand.
+ * @param type Operand kind, one of [REG], [IMM], or [MEM].
+ * @param reg Register name for [REG] operands.
+ * @param imm Immediate value for [IMM] operands.
+ * @param base Base register for [MEM] operands.
+ * @param index Index register for [MEM] operands.
+ * @param disp Displacement for [MEM] operands.
+ */
+data class DisassemblyOperand(
+    val type: Int,
+    val reg: String?,
+    val imm: Long,
+    val base: String?,
+    val index: String?,
+    val disp: Long
+) {
+    companion object {
+        const val REG = 0
+        const val IMM = 1
+        const val MEM = 2
+    }
+}
+
+/**
+ * A single instruction classified for inspection.
+ * @param address Instruction address.
+ * @param mnemonic Instruction mnemonic.
+ * @param operands Instruction operands.
+ * @param category One of "branch", "call", "return", "load", "store", or "other".
+ * @param branchTarget Target of a direct branch or call, null otherwise.
+ * @param size Instruction size in bytes.
+ */
+data class InstructionDescription(
+    val address: Long,
+    val mnemonic: String,
+    val operands: String,
+    val category: String,
+    val branchTarget: Long?,
+    val size: Int
+)
+
+/**
+ * One row of a hex dump.
+ * @param address Address of the first byte in the row.
+ * @param bytes Row bytes (hex string).
+ * @param ascii Printable ASCII rendering, with '.' for non-printable bytes.
+ */
+data class HexDumpRow(
+    val address: Long,
+    val bytes: String,
+    val ascii: String
+)
+
+/**
+ * Instruction disassembler using Capstone engine.
+ * Supports ARM32, Thumb, ARM64, x86, x86_64, MIPS, and MIPS64 architectures.
  */
 object Disassembler {
     init {
@@ -34,6 +100,22 @@ object Disassembler {
         const val ARM32 = 0
         const val THUMB = 1
         const val ARM64 = 2
+        const val X86 = 3
+        const val X86_64 = 4
+        const val MIPS = 5
+        const val MIPS64 = 6
+    }
+
+    /**
+     * Operand syntax options.
+     */
+    object Syntax {
+        /** Capstone's default syntax for the architecture. */
+        const val DEFAULT = 0
+        /** Numbered registers for ARM32/Thumb (r11, r13 instead of fp, sp). */
+        const val NO_REG_NAME = 1
+        /** AT&T syntax for x86/x86_64. */
+        const val ATT = 2
     }
 
     /**
@@ -81,6 +163,117 @@ object Disassembler {
         return nativeDisassemble(Architecture.ARM64, bytes, address, count)
     }
 
+    /**
+     * Disassembles x86 (32-bit) instructions.
+     * @param bytes Instruction bytes to disassemble.
+     * @param address Starting address for the instructions.
+     * @param count Maximum number of instructions to disassemble (0 = all).
+     * @return Array of disassembly results.
+     */
+    fun disassembleX86(
+        bytes: ByteArray,
+        address: Long = 0,
+        count: Int = 0
+    ): Array<DisassemblyResult> {
+        return nativeDisassemble(Architecture.X86, bytes, address, count)
+    }
+
+    /**
+     * Disassembles x86_64 instructions.
+     * @param bytes Instruction bytes to disassemble.
+     * @param address Starting address for the instructions.
+     * @param count Maximum number of instructions to disassemble (0 = all).
+     * @return Array of disassembly results.
+     */
+    fun disassembleX86_64(
+        bytes: ByteArray,
+        address: Long = 0,
+        count: Int = 0
+    ): Array<DisassemblyResult> {
+        return nativeDisassemble(Architecture.X86_64, bytes, address, count)
+    }
+
+    /**
+     * Disassembles instructions with an explicit byte order.
+     * Big-endian is only supported for ARM32, Thumb, ARM64, MIPS, and MIPS64.
+     * @param architecture Architecture mode.
+     * @param bytes Instruction bytes to disassemble.
+     * @param address Starting address for the instructions.
+     * @param count Maximum number of instructions to disassemble (0 = all).
+     * @param bigEndian Decode the bytes as big-endian.
+     * @return Array of disassembly results.
+     */
+    fun disassembleEndian(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long = 0,
+        count: Int = 0,
+        bigEndian: Boolean = false
+    ): Array<DisassemblyResult> {
+        return nativeDisassembleEndian(architecture, bytes, address, count, bigEndian)
+    }
+
+    /**
+     * Disassembles instructions with an explicit operand syntax.
+     * Syntaxes that do not apply to the architecture fall back to the default.
+     * @param architecture Architecture mode.
+     * @param bytes Instruction bytes to disassemble.
+     * @param address Starting address for the instructions.
+     * @param count Maximum number of instructions to disassemble (0 = all).
+     * @param syntax One of [Syntax].
+     * @param pseudoCode Also generate pseudo-code and instruction details.
+     * @return Array of disassembly results.
+     */
+    fun disassembleWithSyntax(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long = 0,
+        count: Int = 0,
+        syntax: Int = Syntax.DEFAULT,
+        pseudoCode: Boolean = false
+    ): Array<DisassemblyResult> {
+        return nativeDisassembleWithSyntax(architecture, bytes, address, count, syntax, pseudoCode)
+    }
+
+    /**
+     * Disassembles instructions without stopping at undecodable bytes.
+     * Data bytes are returned as ".byte" entries so the result spans the whole input.
+     * @param architecture Architecture mode.
+     * @param bytes Instruction bytes to disassemble.
+     * @param address Starting address for the instructions.
+     * @param count Maximum number of instructions to disassemble (0 = all).
+     * @return Array of disassembly results.
+     */
+    fun disassembleSkipData(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long = 0,
+        count: Int = 0
+    ): Array<DisassemblyResult> {
+        return nativeDisassembleSkipData(architecture, bytes, address, count)
+    }
+
+    /**
+     * Gets the instructions just before an address, for scrolling up from it.
+     * ARM and Thumb cannot be decoded backward, so the bytes are decoded forward from
+     * [windowStart], which must be a known instruction boundary such as a function start.
+     * @param architecture Architecture mode.
+     * @param bytes Instruction bytes starting at [windowStart].
+     * @param windowStart Address of the first byte.
+     * @param targetAddress Address whose preceding instructions are wanted.
+     * @param maxInstructions Maximum number of instructions to return.
+     * @return Instructions in address order, the last ending at or just before [targetAddress].
+     */
+    fun disassembleBackward(
+        architecture: Int,
+        bytes: ByteArray,
+        windowStart: Long,
+        targetAddress: Long,
+        maxInstructions: Int
+    ): Array<DisassemblyResult> {
+        return nativeDisassembleBackward(architecture, bytes, windowStart, targetAddress, maxInstructions)
+    }
+
     /**
      * Generates pseudo-code for ARM instructions.
      * This provides a simplified, high-level representation of the instruction's operation.
@@ -88,19 +281,53 @@ object Disassembler {
      * @param bytes Instruction bytes.
      * @param address Starting address for the instructions.
      * @param count Maximum number of instructions to process (0 = all).
+     * @param withSource Keep the original instruction after the pseudo-code as a `// mnemonic operands` comment.
      * @return Array of disassembly results with pseudo-code.
      */
     fun generatePseudoCode(
         architecture: Int,
         bytes: ByteArray,
         address: Long = 0,
-        count: Int = 0
+        count: Int = 0,
+        withSource: Boolean = false
+    ): Array<DisassemblyResult> {
+        return nativeGeneratePseudoCode(architecture, bytes, address, count, withSource)
+    }
+
+    /**
+     * Disassembles with direct branch and call targets shown by name,
+     * e.g. `bl sub_400abc` and `call sub_400abc` instead of the raw address.
+     * @param architecture Architecture mode.
+     * @param bytes Instruction bytes.
+     * @param address Starting address for the instructions.
+     * @param count Maximum number of instructions to process (0 = all).
+     * @param symbols Symbol names by absolute address, e.g. from a .symtab or PLT dump.
+     * @param pseudo Also generate pseudo-code.
+     * @return Array of disassembly results.
+     */
+    fun disassembleWithSymbols(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long = 0,
+        count: Int = 0,
+        symbols: Map<Long, String>,
+        pseudo: Boolean = false
     ): Array<DisassemblyResult> {
-        return nativeGeneratePseudoCode(architecture, bytes, address, count)
+        val entries = symbols.entries.toList()
+        return nativeDisassembleWithSymbols(
+            architecture,
+            bytes,
+            address,
+            count,
+            pseudo,
+            LongArray(entries.size) { entries[it].key },
+            Array(entries.size) { entries[it].value }
+        )
     }
 
     /**
      * Reads memory from current bound process and disassembles.
+     * Undecodable bytes are kept as ".byte" entries since arbitrary memory may mix code and data.
      * @param architecture Architecture mode.
      * @param address Memory address to read from.
      * @param size Number of bytes to read.
@@ -112,7 +339,90 @@ object Disassembler {
         size: Int
     ): Array<DisassemblyResult> {
         val bytes = WuwaDriver.readMemory(address, size) ?: return emptyArray()
-        return nativeDisassemble(architecture, bytes, address, 0)
+        return nativeDisassembleSkipData(architecture, bytes, address, 0)
+    }
+
+    /**
+     * Disassembles instructions directly from the bound process memory.
+     * The read is truncated at the first unreadable page; it fails if the first page is unreadable.
+     * @param architecture Architecture mode.
+     * @param address Memory address to read from.
+     * @param length Maximum number of bytes to read, at most 1 MiB; 0 returns an empty array.
+     * @param count Maximum number of instructions (0 = all).
+     * @return Array of disassembly results.
+     */
+    fun disassembleAddress(
+        architecture: Int,
+        address: Long,
+        length: Int,
+        count: Int = 0
+    ): Array<DisassemblyResult> {
+        return nativeDisassembleAddress(architecture, address, length, count)
+    }
+
+    /**
+     * Disassembles into a single text block, e.g. for copying to the clipboard.
+     * Each line is `address: bytes  mnemonic operands  ; pseudo`, with aligned columns.
+     * @param architecture Architecture mode.
+     * @param bytes Instruction bytes.
+     * @param address Starting address.
+     * @param count Maximum number of instructions (0 = all).
+     * @param withPseudo Whether to append the pseudo-code column.
+     * @return The listing, lines separated by '\n'.
+     */
+    fun disassembleText(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long,
+        count: Int = 0,
+        withPseudo: Boolean = false
+    ): String {
+        return nativeDisassembleText(architecture, bytes, address, count, withPseudo)
+    }
+
+    /**
+     * Renders bytes as a hex + ASCII dump, for regions that do not disassemble sensibly.
+     * @param bytes Raw bytes.
+     * @param address Address of the first byte.
+     * @param bytesPerRow Number of bytes per row (must be positive).
+     * @return Rows in address order; the last row may be shorter.
+     */
+    fun hexDump(bytes: ByteArray, address: Long, bytesPerRow: Int = 16): Array<HexDumpRow> {
+        return nativeHexDump(bytes, address, bytesPerRow)
+    }
+
+    /**
+     * Guesses whether the bytes are ARM32 or Thumb code, for an "auto" architecture option.
+     * This is a heuristic and can be wrong for short buffers; ties resolve to Thumb.
+     * @param bytes Code bytes.
+     * @param address Address of the first byte.
+     * @return [Architecture.ARM32] or [Architecture.THUMB].
+     */
+    fun detectArmMode(bytes: ByteArray, address: Long): Int {
+        return nativeDetectArmMode(bytes, address)
+    }
+
+    /**
+     * Decodes one instruction and classifies it, e.g. for a tooltip.
+     * @param architecture Architecture mode.
+     * @param bytes Instruction bytes; only the first instruction is decoded.
+     * @param address Instruction address.
+     * @return The instruction description, or null if the bytes are not a valid instruction.
+     */
+    fun describeInstruction(architecture: Int, bytes: ByteArray, address: Long): InstructionDescription? {
+        return nativeDescribeInstruction(architecture, bytes, address)
+    }
+
+    /**
+     * Finds likely function starts, for a "jump to function start" action.
+     * Recognizes ARM64 `stp x29, x30, [sp, #-N]!` / `mov x29, sp` and ARM32/Thumb `push {..., lr}` prologues.
+     * @param architecture Architecture mode.
+     * @param bytes Code bytes.
+     * @param address Address of the first byte.
+     * @return Addresses of the detected prologues in ascending order.
+     */
+    fun findFunctionStarts(architecture: Int, bytes: ByteArray, address: Long): LongArray {
+        return nativeFindFunctionStarts(architecture, bytes, address)
     }
 
     private external fun nativeDisassemble(
@@ -122,10 +432,80 @@ object Disassembler {
         count: Int
     ): Array<DisassemblyResult>
 
-    private external fun nativeGeneratePseudoCode(
+    private external fun nativeDetectArmMode(bytes: ByteArray, address: Long): Int
+
+    private external fun nativeDescribeInstruction(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long
+    ): InstructionDescription?
+
+    private external fun nativeDisassembleText(
         architecture: Int,
         bytes: ByteArray,
         address: Long,
+        count: Int,
+        withPseudo: Boolean
+    ): String
+
+    private external fun nativeHexDump(bytes: ByteArray, address: Long, bytesPerRow: Int): Array<HexDumpRow>
+
+    private external fun nativeFindFunctionStarts(architecture: Int, bytes: ByteArray, address: Long): LongArray
+
+    private external fun nativeDisassembleAddress(
+        architecture: Int,
+        address: Long,
+        length: Int,
         count: Int
     ): Array<DisassemblyResult>
+
+    private external fun nativeDisassembleEndian(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long,
+        count: Int,
+        bigEndian: Boolean
+    ): Array<DisassemblyResult>
+
+    private external fun nativeDisassembleWithSyntax(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long,
+        count: Int,
+        syntax: Int,
+        pseudoCode: Boolean
+    ): Array<DisassemblyResult>
+
+    private external fun nativeDisassembleBackward(
+        architecture: Int,
+        bytes: ByteArray,
+        windowStart: Long,
+        targetAddress: Long,
+        maxInstructions: Int
+    ): Array<DisassemblyResult>
+
+    private external fun nativeDisassembleSkipData(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long,
+        count: Int
+    ): Array<DisassemblyResult>
+
+    private external fun nativeGeneratePseudoCode(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long,
+        count: Int,
+        withSource: Boolean
+    ): Array<DisassemblyResult>
+
+    private external fun nativeDisassembleWithSymbols(
+        architecture: Int,
+        bytes: ByteArray,
+        address: Long,
+        count: Int,
+        pseudo: Boolean,
+        symbolAddresses: LongArray,
+        symbolNames: Array<String>
+    ): Array<DisassemblyResult>
 }
@@ -57,14 +57,50 @@ enum class FuzzyCondition(val nativeId: Int, val displayName: String) {
     /**
      * 值减少了指定百分比 (param1 / 100.0)
      */
-    DECREASED_BY_PERCENT(10, "值减少了%");
+    DECREASED_BY_PERCENT(10, "值减少了%"),
+
+    /**
+     * 当前值位于 [param1, param2] 闭区间内，与旧值无关
+     */
+    BETWEEN(11, "值在范围内"),
+
+    /**
+     * 当前值位于浮点闭区间内，param1/param2 为 Double.toRawBits() 的结果
+     */
+    BETWEEN_FLOAT(12, "值在小数范围内"),
+
+    /**
+     * 值在历次细化中（含本次）至少变化了 param1 次
+     */
+    CHANGED_AT_LEAST(13, "值变化次数至少"),
+
+    /**
+     * 值增加了 param1，允许 ±param2 的偏差
+     */
+    INCREASED_BY_TOLERANCE(14, "值约增加了"),
+
+    /**
+     * 值减少了 param1，允许 ±param2 的偏差
+     */
+    DECREASED_BY_TOLERANCE(15, "值约减少了"),
+
+    /**
+     * 当前值等于 param1，与旧值无关
+     */
+    EQUALS_NOW(16, "值等于"),
+
+    /**
+     * 当前值等于浮点常量，param1 为 Double.toRawBits() 的结果
+     */
+    EQUALS_NOW_FLOAT(17, "值等于小数");
 
     /**
      * 是否需要输入参数
      */
     fun needsParam(): Boolean {
         return when (this) {
-            INCREASED_BY, DECREASED_BY, INCREASED_BY_PERCENT, DECREASED_BY_PERCENT -> true
+            INCREASED_BY, DECREASED_BY, INCREASED_BY_PERCENT, DECREASED_BY_PERCENT, CHANGED_AT_LEAST,
+            EQUALS_NOW, EQUALS_NOW_FLOAT -> true
             else -> false
         }
     }
@@ -74,7 +110,8 @@ enum class FuzzyCondition(val nativeId: Int, val displayName: String) {
      */
     fun needsTwoParams(): Boolean {
         return when (this) {
-            INCREASED_BY_RANGE, DECREASED_BY_RANGE -> true
+            INCREASED_BY_RANGE, DECREASED_BY_RANGE, BETWEEN, BETWEEN_FLOAT,
+            INCREASED_BY_TOLERANCE, DECREASED_BY_TOLERANCE -> true
             else -> false
         }
     }
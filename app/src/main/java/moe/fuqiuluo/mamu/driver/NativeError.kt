@@ -0,0 +1,39 @@
+@file:Suppress("KotlinJniMissingFunction")
+
+package moe.fuqiuluo.mamu.driver
+
+/**
+ * Structured error recorded by the native layer when a call throws.
+ * @param code One of [NativeErrors.Code] constants.
+ * @param message Full error message (same text as the thrown exception).
+ */
+data class NativeError(
+    val code: Int,
+    val message: String
+)
+
+/**
+ * Access to the last native error on the calling thread.
+ */
+object NativeErrors {
+    /** Error code constants. */
+    object Code {
+        const val NONE = 0
+        const val UNKNOWN = 1
+        const val INVALID_ARGUMENT = 2
+        const val OUT_OF_BOUNDS = 3
+        const val DISK_FULL = 4
+        const val IO = 5
+        const val NOT_INITIALIZED = 6
+        const val JNI = 7
+    }
+
+    /**
+     * Gets the last error recorded on the calling thread.
+     * Must be called on the same thread that received the exception.
+     * @return Last error, or null if no native call has failed on this thread.
+     */
+    fun lastError(): NativeError? = nativeLastError()
+
+    private external fun nativeLastError(): NativeError?
+}
@@ -44,6 +44,12 @@ object SearchEngine {
         const val ALREADY_SEARCHING = 5
     }
 
+    /** Result export formats. */
+    object ExportFormat {
+        const val CSV = 0
+        const val JSON = 1
+    }
+
     /** Shared buffer offsets. */
     private object Offset {
         const val STATUS = 0
@@ -205,6 +211,16 @@ object SearchEngine {
         return nativeStartSearchAsync(query, type.nativeId, regions, useDeepSearch, keepResult)
     }
 
+    /**
+     * Synchronous signature (AOB) search. Replaces current results with exact results.
+     * @param pattern Space-separated hex bytes, `??` for wildcards, e.g. "48 8B ?? ?? 89".
+     * @param regions Memory region array, format [start1, end1, start2, end2, ...].
+     * @return Number of matches.
+     */
+    fun signatureSearch(pattern: String, regions: LongArray): Long {
+        return nativeSignatureSearch(pattern, regions)
+    }
+
     /**
      * Starts an async refine search. Returns immediately.
      * @param query Search content.
@@ -278,6 +294,58 @@ object SearchEngine {
         }
     }
 
+    /**
+     * Gets fuzzy results sorted by their current value. Storage order is not changed,
+     * and nativePosition still refers to the address-ordered index.
+     * @param descending Whether to sort from largest to smallest.
+     * @param start Starting index in the sorted order.
+     * @param count Number of results to get.
+     * @return Search result array.
+     */
+    fun getResultsSortedByValue(descending: Boolean, start: Int, count: Int): Array<SearchResultItem> {
+        return nativeGetResultsSortedByValue(descending, start, count)
+    }
+
+    /**
+     * Gets one page of search results.
+     * Pages past the end are clamped to the last page instead of returning nothing.
+     * @param page Zero-based page index.
+     * @param pageSize Number of results per page.
+     * @return The page actually returned and the total page count (0 when there are no results).
+     */
+    fun getResultPage(page: Int, pageSize: Int): SearchResultPage {
+        return nativeGetResultPage(page, pageSize)
+    }
+
+    /**
+     * Opens a cursor for reading results in batches, which avoids building one huge array
+     * for large result sets. Close it with [closeCursor] when done.
+     * @return Cursor handle.
+     */
+    fun openResultCursor(): Long {
+        return nativeOpenResultCursor()
+    }
+
+    /**
+     * Reads the next batch from a cursor and advances it.
+     * The current filter is applied, so a batch may hold fewer than [batchSize] items, or none.
+     * @param handle Cursor handle from [openResultCursor].
+     * @param batchSize Maximum number of results to read.
+     * @return The batch, or null once every result has been read.
+     */
+    fun cursorNext(handle: Long, batchSize: Int): Array<SearchResultItem>? {
+        return nativeCursorNext(handle, batchSize)
+    }
+
+    /**
+     * Closes a cursor.
+     * @param handle Cursor handle from [openResultCursor].
+     * @return Whether the handle referred to an open cursor.
+     */
+    fun closeCursor(handle: Long): Boolean {
+        return nativeCloseCursor(handle)
+    }
+
     /**
      * Gets total result count.
      */
@@ -285,6 +353,93 @@ object SearchEngine {
         return nativeGetTotalResultCount()
     }
 
+    /**
+     * Exports current search results to a file.
+     * @param path Destination file path
+     * @param format [ExportFormat.CSV] or [ExportFormat.JSON]
+     */
+    fun exportResults(path: String, format: Int = ExportFormat.CSV): Boolean {
+        return nativeExportResults(path, format)
+    }
+
+    /**
+     * Compares two saved fuzzy session files without loading either into the result set.
+     * @param oldPath The earlier session file.
+     * @param newPath The later session file.
+     * @return Addresses only in the new session, only in the old one, and in both with a changed value.
+     */
+    fun diffSessions(oldPath: String, newPath: String): SessionDiff {
+        return nativeDiffSessions(oldPath, newPath)
+    }
+
+    /**
+     * Gets memory and disk bytes used by live search results.
+     * @return [memoryBytes, diskBytes]
+     */
+    fun getUsageStats(): LongArray {
+        return nativeGetUsageStats()
+    }
+
+    /**
+     * Gets result counts and bytes used for the current mode in one call.
+     * @return Counts and bytes of the live results.
+     */
+    fun getResultStats(): ResultStats {
+        return nativeGetResultStats()
+    }
+
+    /**
+     * Saves a copy of the current results, replacing any earlier snapshot.
+     * Call before a refine so it can be undone with [restoreResultSnapshot].
+     * @return Number of results in the snapshot.
+     */
+    fun saveResultSnapshot(): Long {
+        return nativeSaveResultSnapshot()
+    }
+
+    /**
+     * Replaces the current results with the saved snapshot and discards it.
+     * @return Whether a snapshot was restored.
+     */
+    fun restoreResultSnapshot(): Boolean {
+        return nativeRestoreResultSnapshot()
+    }
+
+    /**
+     * Appends the saved snapshot to the current results and discards it.
+     * Combines two scans: save a snapshot after the first, run the second, then merge.
+     * Both must be in the same mode; nothing is deduplicated, so the count is the sum of both.
+     * @return Number of results after merging, or -1 if no snapshot was saved.
+     */
+    fun mergeResultSnapshot(): Long {
+        return nativeMergeResultSnapshot()
+    }
+
+    /**
+     * Flushes all fuzzy results to their disk file so they can be restored after the process is killed.
+     * Per-result history is not persisted.
+     * @return Path to pass to [restoreResults], or null if there are no results.
+     */
+    fun persistResults(): String? {
+        return nativePersistResults()
+    }
+
+    /**
+     * Replaces the current results with fuzzy results persisted by [persistResults].
+     * @param path Path returned by [persistResults]
+     * @return Number of restored results.
+     */
+    fun restoreResults(path: String): Long {
+        return nativeRestoreResults(path)
+    }
+
+    /**
+     * Whether a result snapshot is available to restore.
+     */
+    fun hasResultSnapshot(): Boolean {
+        return nativeHasResultSnapshot()
+    }
+
     /**
      * Clears search results.
      */
@@ -319,6 +474,128 @@ object SearchEngine {
         return nativeKeepOnlyResults(indices)
     }
 
+    /**
+     * Updates one fuzzy result after its memory was edited, so the list does not go stale.
+     * The stored value becomes the previous value.
+     * @param index Search result index.
+     * @param valueBytes New raw value bytes as written to memory.
+     * @return Whether the update was successful.
+     */
+    fun updateFuzzyResult(index: Int, valueBytes: ByteArray): Boolean {
+        return nativeUpdateFuzzyResult(index, valueBytes)
+    }
+
+    /**
+     * Sets the group tag stamped on fuzzy results added by later scans.
+     * Existing results keep their tags; refining preserves them.
+     * A non-zero tag turns on history tracking, since tags are stored with the result history.
+     * @param tag Tag in 0..65535, 0 means ungrouped.
+     */
+    fun setResultTag(tag: Int) {
+        nativeSetResultTag(tag)
+    }
+
+    /**
+     * Turns per-result history for fuzzy results on or off: previous value, min/max seen,
+     * change count and group tag. History costs extra memory per result, so it is off by default;
+     * while off, [FuzzySearchResultItem.previousValue] is null and every result is ungrouped.
+     * @param enabled Whether to track history.
+     */
+    fun setHistoryTracking(enabled: Boolean) {
+        nativeSetHistoryTracking(enabled)
+    }
+
+    /**
+     * Makes later scans keep only aligned addresses: each result must be aligned to
+     * the smaller of [alignment] and its value type's size (a Dword at a 4-aligned address).
+     * Existing results are not affected.
+     * @param alignment Power of two in bytes, or 0 to accept any address.
+     */
+    fun setRequiredAlignment(alignment: Int) {
+        nativeSetRequiredAlignment(alignment)
+    }
+
+    /**
+     * Keeps only the fuzzy results with the given tag.
+     * @return Number of results kept.
+     */
+    fun keepOnlyTag(tag: Int): Long {
+        return nativeKeepOnlyTag(tag)
+    }
+
+    /**
+     * Removes the fuzzy results with the given tag.
+     * @return Number of results removed.
+     */
+    fun removeTag(tag: Int): Long {
+        return nativeRemoveTag(tag)
+    }
+
+    /**
+     * Builds a distribution of the current fuzzy result values, e.g. to draw a chart that guides refinement.
+     * NaN and infinite values are not counted. All-equal values produce a single bucket.
+     * @param buckets Number of equally wide buckets between the smallest and largest value.
+     * @return Buckets in ascending value order, empty when there are no results.
+     */
+    fun getValueHistogram(buckets: Int): Array<HistogramBucket> {
+        return nativeGetValueHistogram(buckets)
+    }
+
+    /**
+     * Groups fuzzy results spaced exactly [stride] bytes apart into runs, so a block can be frozen as one region.
+     * Isolated results become runs of count 1.
+     * @param stride Distance between neighbouring results, 0 to use each result's value size.
+     * @return Runs in address order.
+     */
+    fun getContiguousRuns(stride: Int = 0): Array<AddressRun> {
+        return nativeGetContiguousRuns(stride)
+    }
+
+    /**
+     * Gets the addresses of all current results, without their values, e.g. to feed a pointer scan.
+     * @return Addresses in result index order.
+     */
+    fun getResultAddresses(): LongArray {
+        return nativeGetResultAddresses()
+    }
+
+    /**
+     * Finds results whose address is within [window] bytes of [address].
+     * @return Indices of the matching results, usable to jump to those rows.
+     */
+    fun findNear(address: Long, window: Long): IntArray {
+        return nativeFindNear(address, window)
+    }
+
+    /**
+     * Keeps only search results whose address is within [min, max] (inclusive).
+     * @param min Lower address bound.
+     * @param max Upper address bound.
+     * @return Whether operation was successful.
+     */
+    fun filterAddressRange(min: Long, max: Long): Boolean {
+        return nativeFilterAddressRange(min, max)
+    }
+
+    /**
+     * Keeps only search results whose address also appears in [addresses],
+     * e.g. the addresses exported from another search. Order and duplicates do not matter.
+     * @param addresses Addresses to intersect with.
+     * @return Number of results remaining.
+     */
+    fun intersectResults(addresses: LongArray): Long {
+        return nativeIntersectResults(addresses)
+    }
+
+    /**
+     * Releases spare memory and disk space held by the result set.
+     * Call after a refine or keepOnlyResults narrows a large scan down to a few results.
+     * @return Whether operation was successful.
+     */
+    fun shrinkResults(): Boolean {
+        return nativeShrinkResults()
+    }
+
     /**
      * Sets filter conditions (address range, value range, data type, permissions).
      * Only affects search result filtering, does not affect actual search process.
@@ -360,6 +637,25 @@ object SearchEngine {
         )
     }
 
+    /**
+     * Gets how many bytes a value type occupies in memory, as used by the native search.
+     * Variable-length types (string, bytes) report 1.
+     * @param typeId Native value type id (DisplayValueType.nativeId).
+     * @return Size in bytes.
+     */
+    fun valueTypeSize(typeId: Int): Int {
+        return nativeValueTypeSize(typeId)
+    }
+
+    /**
+     * Gets the natural alignment of a value type, as used by aligned scans.
+     * @param typeId Native value type id (DisplayValueType.nativeId).
+     * @return Alignment in bytes.
+     */
+    fun valueTypeAlignment(typeId: Int): Int {
+        return nativeValueTypeAlignment(typeId)
+    }
+
     /**
      * Gets current search mode.
      * @return Current search mode (EXACT or FUZZY).
@@ -387,6 +683,60 @@ object SearchEngine {
         return nativeGetCompatibilityMode()
     }
 
+    /**
+     * Sets unsigned comparison for fuzzy refine.
+     * When enabled, integer values are compared as unsigned, so counters such as timers
+     * or currency behave correctly near the sign boundary.
+     * @param enabled Whether to compare integers as unsigned.
+     */
+    fun setUnsignedCompare(enabled: Boolean) {
+        nativeSetUnsignedCompare(enabled)
+    }
+
+    /**
+     * Gets unsigned comparison for fuzzy refine.
+     * @return Whether integers are compared as unsigned.
+     */
+    fun getUnsignedCompare(): Boolean {
+        return nativeGetUnsignedCompare()
+    }
+
+    /**
+     * Sets wrap-aware difference for fuzzy refine.
+     * When enabled, "increased by" / "decreased by" conditions compute the difference at the
+     * value type's native width, so an 8-bit counter going from 255 to 0 counts as +1.
+     * @param enabled Whether to compute differences with wrap-around.
+     */
+    fun setWrappingCompare(enabled: Boolean) {
+        nativeSetWrappingCompare(enabled)
+    }
+
+    /**
+     * Gets wrap-aware difference for fuzzy refine.
+     * @return Whether differences are computed with wrap-around.
+     */
+    fun getWrappingCompare(): Boolean {
+        return nativeGetWrappingCompare()
+    }
+
+    /**
+     * Sets the target byte order for fuzzy search.
+     * When enabled, values read by the fuzzy scan, refine and result updates are decoded as
+     * big-endian, for emulated or embedded targets.
+     * @param enabled Whether to decode values as big-endian.
+     */
+    fun setBigEndian(enabled: Boolean) {
+        nativeSetBigEndian(enabled)
+    }
+
+    /**
+     * Gets the target byte order for fuzzy search.
+     * @return Whether values are decoded as big-endian.
+     */
+    fun getBigEndian(): Boolean {
+        return nativeGetBigEndian()
+    }
+
     /**
      * Starts an async fuzzy initial search. Records all values in memory regions.
      * @param type Data type to search for.
@@ -451,6 +801,24 @@ object SearchEngine {
         return nativeStartFuzzyRefineAsync(condition.nativeId, param1, param2)
     }
 
+    /**
+     * Refines fuzzy results synchronously against values the caller has already read.
+     * Matching results are updated to their fresh value, the others are removed.
+     * @param condition Fuzzy condition to apply.
+     * @param param1 First parameter for conditions that need it.
+     * @param param2 Second parameter for range conditions.
+     * @param freshValues 8 bytes per result in current result order, value in the low bytes.
+     * @return Number of results remaining.
+     */
+    fun fuzzyRefine(
+        condition: FuzzyCondition,
+        param1: Long,
+        param2: Long,
+        freshValues: ByteArray,
+    ): Long {
+        return nativeFuzzyRefine(condition.nativeId, param1, param2, freshValues)
+    }
+
     /**
      * Executes refine search synchronously (legacy).
      */
@@ -525,6 +893,8 @@ object SearchEngine {
         keepResult: Boolean
     ): Boolean
 
+    private external fun nativeSignatureSearch(pattern: String, regions: LongArray): Long
+
     private external fun nativeStartRefineAsync(query: String, defaultType: Int): Boolean
     private external fun nativeIsSearching(): Boolean
     private external fun nativeRequestCancel()
@@ -539,11 +909,39 @@ object SearchEngine {
     ): Long
 
     private external fun nativeGetResults(start: Int, count: Int): Array<SearchResultItem>
+    private external fun nativeGetResultsSortedByValue(descending: Boolean, start: Int, count: Int): Array<SearchResultItem>
+    private external fun nativeGetResultPage(page: Int, pageSize: Int): SearchResultPage
+    private external fun nativeOpenResultCursor(): Long
+    private external fun nativeCursorNext(handle: Long, batchSize: Int): Array<SearchResultItem>?
+    private external fun nativeCloseCursor(handle: Long): Boolean
     private external fun nativeGetTotalResultCount(): Long
+    private external fun nativeGetUsageStats(): LongArray
+    private external fun nativeGetResultStats(): ResultStats
+    private external fun nativeSaveResultSnapshot(): Long
+    private external fun nativeRestoreResultSnapshot(): Boolean
+    private external fun nativeMergeResultSnapshot(): Long
+    private external fun nativePersistResults(): String?
+    private external fun nativeRestoreResults(path: String): Long
+    private external fun nativeHasResultSnapshot(): Boolean
+    private external fun nativeExportResults(path: String, format: Int): Boolean
+    private external fun nativeDiffSessions(oldPath: String, newPath: String): SessionDiff
     private external fun nativeClearSearchResults()
     private external fun nativeRemoveResult(index: Int): Boolean
     private external fun nativeRemoveResults(indices: IntArray): Boolean
     private external fun nativeKeepOnlyResults(indices: IntArray): Boolean
+    private external fun nativeUpdateFuzzyResult(index: Int, valueBytes: ByteArray): Boolean
+    private external fun nativeSetResultTag(tag: Int)
+    private external fun nativeSetHistoryTracking(enabled: Boolean)
+    private external fun nativeSetRequiredAlignment(alignment: Int)
+    private external fun nativeKeepOnlyTag(tag: Int): Long
+    private external fun nativeRemoveTag(tag: Int): Long
+    private external fun nativeGetValueHistogram(buckets: Int): Array<HistogramBucket>
+    private external fun nativeFindNear(address: Long, window: Long): IntArray
+    private external fun nativeGetResultAddresses(): LongArray
+    private external fun nativeGetContiguousRuns(stride: Int): Array<AddressRun>
+    private external fun nativeFilterAddressRange(min: Long, max: Long): Boolean
+    private external fun nativeIntersectResults(addresses: LongArray): Long
+    private external fun nativeShrinkResults(): Boolean
     private external fun nativeSetFilter(
         enableAddressFilter: Boolean,
         addressStart: Long,
@@ -554,8 +952,16 @@ object SearchEngine {
 
     private external fun nativeClearFilter()
     private external fun nativeGetCurrentSearchMode(): Int
+    private external fun nativeValueTypeSize(typeId: Int): Int
+    private external fun nativeValueTypeAlignment(typeId: Int): Int
     private external fun nativeSetCompatibilityMode(enabled: Boolean)
     private external fun nativeGetCompatibilityMode(): Boolean
+    private external fun nativeSetUnsignedCompare(enabled: Boolean)
+    private external fun nativeGetUnsignedCompare(): Boolean
+    private external fun nativeSetWrappingCompare(enabled: Boolean)
+    private external fun nativeGetWrappingCompare(): Boolean
+    private external fun nativeSetBigEndian(enabled: Boolean)
+    private external fun nativeGetBigEndian(): Boolean
     @Deprecated("同步搜索版本已废弃")
     private external fun nativeRefineSearch(
         query: String,
@@ -580,6 +986,13 @@ object SearchEngine {
         param2: Long
     ): Boolean
 
+    private external fun nativeFuzzyRefine(
+        conditionId: Int,
+        param1: Long,
+        param2: Long,
+        freshValues: ByteArray
+    ): Long
+
     // Legacy native methods kept for backward compatibility.
     @Deprecated("Low performance")
     private external fun nativeSetProgressBuffer(buffer: ByteBuffer): Boolean
@@ -22,8 +22,72 @@ data class FuzzySearchResultItem(
     override val nativePosition: Long,
     val address: Long,
     val value: String,
-    val valueType: Int
+    val valueType: Int,
+    val previousValue: String? = null, // 上一次细化前的值，用于显示 "100 → 95"
+    val tag: Int = 0 // 分组标签，0 表示未分组
 ): SearchResultItem {
     override val displayValueType: DisplayValueType?
         get() = DisplayValueType.fromNativeId(valueType)
-}
\ No newline at end of file
+}
+
+/**
+ * 一页搜索结果
+ * @param items 该页结果，nativePosition 为全局索引
+ * @param page 实际返回的页码（超出范围时为最后一页）
+ * @param totalPages 总页数，没有结果时为 0
+ */
+class SearchResultPage(
+    val items: Array<SearchResultItem>,
+    val page: Int,
+    val totalPages: Int
+)
+
+/**
+ * 地址连续的一段结果，可以作为一个区域整体冻结
+ * @param start 起始地址
+ * @param count 区段内的结果数量
+ */
+data class AddressRun(
+    val start: Long,
+    val count: Int
+)
+
+/**
+ * 两个会话之间的差异，地址均按升序排列
+ * @param appeared 只出现在新会话中的地址
+ * @param disappeared 只出现在旧会话中的地址
+ * @param changed 两个会话中都有、但值发生变化的地址
+ */
+data class SessionDiff(
+    val appeared: LongArray,
+    val disappeared: LongArray,
+    val changed: LongArray
+)
+
+/**
+ * 当前模式下结果的数量与占用
+ * @param totalCount 结果总数
+ * @param memoryCount 内存缓冲区中的结果数量
+ * @param diskCount 磁盘上的结果数量
+ * @param memoryBytes 内存中存活结果占用的字节数
+ * @param diskBytes 磁盘上存活结果占用的字节数
+ */
+data class ResultStats(
+    val totalCount: Long,
+    val memoryCount: Long,
+    val diskCount: Long,
+    val memoryBytes: Long,
+    val diskBytes: Long
+)
+
+/**
+ * 值分布直方图中的一个桶
+ * @param min 桶下界（含）
+ * @param max 桶上界，最后一个桶包含上界
+ * @param count 落入该桶的结果数量
+ */
+data class HistogramBucket(
+    val min: Double,
+    val max: Double,
+    val count: Long
+)
@@ -1,6 +1,7 @@
 //! Driver manager implementation
 
 use crate::core::memory_mode::MemoryAccessMode;
+use crate::search::PAGE_SIZE;
 use crate::wuwa::{BindProc, PageStatusBitmap, WuWaDriver, WuwaMemoryType};
 use log::error;
 
@@ -169,6 +170,48 @@ impl DriverManager {
         }
     }
 
+    /// 读取连续可读的前缀，遇到第一个不可读的页时截断
+    ///
+    /// # Returns
+    /// * `Ok(len)` 从 `addr` 开始成功读取的字节数（`buf[..len]` 有效）
+    /// * `Err` 如果第一个字节所在页就无法读取
+    pub fn read_memory_prefix(&self, addr: u64, buf: &mut [u8]) -> anyhow::Result<usize> {
+        let page_size = *PAGE_SIZE;
+        let page_offset = addr as usize & (page_size - 1);
+        let mut page_status = PageStatusBitmap::new(buf.len(), addr as usize);
+
+        let first_error = match self.read_memory_unified(addr, buf, Some(&mut page_status)) {
+            Ok(()) => {
+                let readable = (0..)
+                    .take_while(|&page| page_status.is_page_success(page))
+                    .map(|page| (page + 1) * page_size - page_offset)
+                    .last()
+                    .unwrap_or(0);
+                if readable == 0 && !buf.is_empty() {
+                    return Err(anyhow::anyhow!("Memory at 0x{:x} is not readable", addr));
+                }
+                return Ok(readable.min(buf.len()));
+            },
+            Err(e) => e,
+        };
+
+        // 整块读取失败时按页读取，直到第一个失败的页
+        let mut readable = 0;
+        while readable < buf.len() {
+            let chunk_len = (page_size - (addr as usize + readable) % page_size).min(buf.len() - readable);
+            let chunk = &mut buf[readable..readable + chunk_len];
+            if self.read_memory_unified(addr + readable as u64, chunk, None).is_err() {
+                break;
+            }
+            readable += chunk_len;
+        }
+
+        if readable == 0 {
+            return Err(first_error);
+        }
+        Ok(readable)
+    }
+
     /// 统一的内存写入方法，使用当前配置的 access_mode
     ///
     /// # Arguments
@@ -0,0 +1,142 @@
+//! Small LRU cache of disassembly results.
+//!
+//! The UI re-disassembles the same window when toggling pseudo-code or re-opening a view, so
+//! [`disassemble_with`](super::disassemble_with) keeps the most recent results per thread and
+//! skips Capstone when the same bytes are requested again with the same options.
+
+use super::{Architecture, DisasmOptions, DisassemblyResult};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+
+/// Number of windows kept per thread.
+pub(super) const CACHE_CAPACITY: usize = 32;
+/// Larger inputs are not cached, so a full-region disassembly does not pin its results in memory.
+pub(super) const MAX_CACHED_BYTES: usize = 64 * 1024;
+/// Input bytes kept per thread across all entries.
+pub(super) const MAX_TOTAL_BYTES: usize = 256 * 1024;
+/// Decoded instructions kept per thread across all entries; each result owns several strings.
+pub(super) const MAX_TOTAL_INSTRUCTIONS: usize = 16 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct CacheKey {
+    arch: Architecture,
+    address: u64,
+    bytes_hash: u64,
+    count: usize,
+    options: DisasmOptions,
+}
+
+impl CacheKey {
+    /// Returns None for inputs larger than [`MAX_CACHED_BYTES`], before hashing them.
+    pub(super) fn new(arch: Architecture, bytes: &[u8], address: u64, count: usize, options: &DisasmOptions) -> Option<Self> {
+        if bytes.len() > MAX_CACHED_BYTES {
+            return None;
+        }
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        Some(Self {
+            arch,
+            address,
+            bytes_hash: hasher.finish(),
+            count,
+            options: *options,
+        })
+    }
+}
+
+struct CacheEntry {
+    /// Kept to rule out hash collisions; a mismatch drops the entry.
+    bytes: Vec<u8>,
+    results: Vec<DisassemblyResult>,
+    /// Position in [`ResultCache::order`].
+    last_used: u64,
+}
+
+pub(super) struct ResultCache {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Keys by last use, least recently used first.
+    order: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    total_bytes: usize,
+    total_instructions: usize,
+}
+
+impl ResultCache {
+    pub(super) fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            total_bytes: 0,
+            total_instructions: 0,
+        }
+    }
+
+    /// Returns a copy of the cached results for `key` if they were produced from `bytes`.
+    pub(super) fn get(&mut self, key: &CacheKey, bytes: &[u8]) -> Option<Vec<DisassemblyResult>> {
+        let entry = self.entries.get(key)?;
+        if entry.bytes != bytes {
+            self.remove(key);
+            return None;
+        }
+        let results = entry.results.clone();
+        self.touch(key);
+        Some(results)
+    }
+
+    /// Caches `results`, evicting least recently used entries until the entry count, total input
+    /// bytes and total instruction count are all within their limits.
+    pub(super) fn insert(&mut self, key: CacheKey, bytes: &[u8], results: Vec<DisassemblyResult>) {
+        if bytes.len() > MAX_CACHED_BYTES || results.len() > MAX_TOTAL_INSTRUCTIONS {
+            return;
+        }
+        self.remove(&key);
+
+        while !self.order.is_empty()
+            && (self.entries.len() >= CACHE_CAPACITY
+                || self.total_bytes + bytes.len() > MAX_TOTAL_BYTES
+                || self.total_instructions + results.len() > MAX_TOTAL_INSTRUCTIONS)
+        {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.remove(&oldest);
+            }
+        }
+
+        self.tick += 1;
+        self.total_bytes += bytes.len();
+        self.total_instructions += results.len();
+        self.order.insert(self.tick, key);
+        self.entries.insert(
+            key,
+            CacheEntry {
+                bytes: bytes.to_vec(),
+                results,
+                last_used: self.tick,
+            },
+        );
+    }
+
+    fn touch(&mut self, key: &CacheKey) {
+        let Some(entry) = self.entries.get_mut(key) else {
+            return;
+        };
+        self.order.remove(&entry.last_used);
+        self.tick += 1;
+        entry.last_used = self.tick;
+        self.order.insert(self.tick, *key);
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.last_used);
+            self.total_bytes -= entry.bytes.len();
+            self.total_instructions -= entry.results.len();
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn totals(&self) -> (usize, usize, usize) {
+        (self.entries.len(), self.total_bytes, self.total_instructions)
+    }
+}
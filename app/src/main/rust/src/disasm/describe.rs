@@ -0,0 +1,175 @@
+//! Single-instruction inspection: what kind of instruction it is and where it branches.
+
+use super::{disassemble_with, Architecture, DisasmOptions, DisassemblyResult, Operand};
+use anyhow::Result;
+
+/// Coarse instruction category, used for tooltips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionCategory {
+    /// Conditional or unconditional jump, direct or indirect.
+    Branch,
+    Call,
+    Return,
+    /// Reads memory (including `pop`).
+    Load,
+    /// Writes memory (including `push`).
+    Store,
+    Other,
+}
+
+impl InstructionCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InstructionCategory::Branch => "branch",
+            InstructionCategory::Call => "call",
+            InstructionCategory::Return => "return",
+            InstructionCategory::Load => "load",
+            InstructionCategory::Store => "store",
+            InstructionCategory::Other => "other",
+        }
+    }
+}
+
+/// One decoded instruction with its category and direct branch or call target.
+#[derive(Debug, Clone)]
+pub struct InstructionDescription {
+    /// The instruction, decoded with detail.
+    pub instruction: DisassemblyResult,
+    pub category: InstructionCategory,
+    /// Target of a direct branch or call; `None` for indirect ones and other categories.
+    pub target: Option<u64>,
+}
+
+/// Decodes the first instruction in `bytes` with detail and classifies it.
+///
+/// Returns `None` if the bytes do not start with a valid instruction.
+pub fn describe_instruction(arch: Architecture, bytes: &[u8], address: u64) -> Result<Option<InstructionDescription>> {
+    let options = DisasmOptions { detail: true, ..Default::default() };
+    let Some(instruction) = disassemble_with(arch, bytes, address, 1, &options)?.into_iter().next() else {
+        return Ok(None);
+    };
+
+    let category = classify(arch, &instruction);
+    let target = direct_target(category, &instruction);
+
+    Ok(Some(InstructionDescription { instruction, category, target }))
+}
+
+/// Returns the target of a direct branch or call, or `None` for indirect ones and other
+/// instructions. Requires detail.
+pub(super) fn direct_target(category: InstructionCategory, insn: &DisassemblyResult) -> Option<u64> {
+    match category {
+        InstructionCategory::Branch | InstructionCategory::Call => insn.operands_detail.iter().rev().find_map(|op| match op {
+            Operand::Imm(imm) => Some(*imm as u64),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Classifies a detailed instruction, preferring Capstone's groups for control flow.
+pub(super) fn classify(arch: Architecture, insn: &DisassemblyResult) -> InstructionCategory {
+    let in_group = |name: &str| insn.groups.iter().any(|g| g == name);
+    let mnemonic = insn.mnemonic.as_str();
+    let has_mem = insn.operands_detail.iter().any(|op| matches!(op, Operand::Mem { .. }));
+
+    if in_group("call") {
+        return InstructionCategory::Call;
+    }
+    if in_group("return") || in_group("ret") || is_return(arch, mnemonic, &insn.operands) {
+        return InstructionCategory::Return;
+    }
+    if in_group("jump") || in_group("branch_relative") {
+        return InstructionCategory::Branch;
+    }
+
+    match arch {
+        Architecture::X86 | Architecture::X86_64 => match mnemonic {
+            "push" => InstructionCategory::Store,
+            "pop" => InstructionCategory::Load,
+            "lea" | "nop" => InstructionCategory::Other,
+            // Intel syntax puts the destination first
+            _ if matches!(insn.operands_detail.first(), Some(Operand::Mem { .. })) => InstructionCategory::Store,
+            _ if has_mem => InstructionCategory::Load,
+            _ => InstructionCategory::Other,
+        },
+        Architecture::ARM32 | Architecture::THUMB | Architecture::ARM64 => {
+            let mnemonic = mnemonic.trim_end_matches(".w");
+            if mnemonic.starts_with("ld") || mnemonic == "pop" || mnemonic == "vpop" {
+                InstructionCategory::Load
+            } else if mnemonic.starts_with("st") || mnemonic == "push" || mnemonic == "vpush" {
+                InstructionCategory::Store
+            } else {
+                InstructionCategory::Other
+            }
+        },
+        Architecture::MIPS | Architecture::MIPS64 => match mnemonic.chars().next() {
+            Some('l') if has_mem => InstructionCategory::Load,
+            Some('s') if has_mem => InstructionCategory::Store,
+            _ => InstructionCategory::Other,
+        },
+    }
+}
+
+/// Returns that Capstone does not put in the return group.
+fn is_return(arch: Architecture, mnemonic: &str, operands: &str) -> bool {
+    match arch {
+        Architecture::ARM32 | Architecture::THUMB => {
+            (mnemonic == "bx" && operands == "lr") || (mnemonic.starts_with("pop") && operands.contains("pc"))
+        },
+        Architecture::MIPS | Architecture::MIPS64 => mnemonic == "jr" && operands == "$ra",
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(arch: Architecture, bytes: &[u8]) -> (InstructionCategory, Option<u64>) {
+        let description = describe_instruction(arch, bytes, 0x1000).unwrap().unwrap();
+        (description.category, description.target)
+    }
+
+    #[test]
+    fn test_describe_arm64() {
+        use InstructionCategory::*;
+
+        // bl #0x2000
+        assert_eq!(describe(Architecture::ARM64, &[0x00, 0x04, 0x00, 0x94]), (Call, Some(0x2000)));
+        // ret
+        assert_eq!(describe(Architecture::ARM64, &[0xc0, 0x03, 0x5f, 0xd6]), (Return, None));
+        // b.ne #0x1010
+        assert_eq!(describe(Architecture::ARM64, &[0x81, 0x00, 0x00, 0x54]), (Branch, Some(0x1010)));
+        // br x0
+        assert_eq!(describe(Architecture::ARM64, &[0x00, 0x00, 0x1f, 0xd6]), (Branch, None));
+        // ldr x0, [x1, #8] ; str x0, [x1] ; mov x0, x1
+        assert_eq!(describe(Architecture::ARM64, &[0x20, 0x04, 0x40, 0xf9]), (Load, None));
+        assert_eq!(describe(Architecture::ARM64, &[0x20, 0x00, 0x00, 0xf9]), (Store, None));
+        assert_eq!(describe(Architecture::ARM64, &[0xe0, 0x03, 0x01, 0xaa]), (Other, None));
+
+        // pop {r4, pc}
+        assert_eq!(describe(Architecture::ARM32, &[0x10, 0x80, 0xbd, 0xe8]), (Return, None));
+
+        // Only the first instruction is described
+        let description = describe_instruction(Architecture::ARM64, &[0xc0, 0x03, 0x5f, 0xd6, 0x1f, 0x20, 0x03, 0xd5], 0x1000)
+            .unwrap()
+            .unwrap();
+        assert_eq!(description.instruction.mnemonic, "ret");
+        assert!(describe_instruction(Architecture::ARM64, &[0xff, 0xff, 0xff, 0xff], 0x1000).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_describe_x86_64() {
+        use InstructionCategory::*;
+
+        // call 0x1005
+        assert_eq!(describe(Architecture::X86_64, &[0xe8, 0x00, 0x00, 0x00, 0x00]), (Call, Some(0x1005)));
+        // mov dword ptr [rax], ebx ; mov eax, dword ptr [rbx]
+        assert_eq!(describe(Architecture::X86_64, &[0x89, 0x18]), (Store, None));
+        assert_eq!(describe(Architecture::X86_64, &[0x8b, 0x03]), (Load, None));
+        // lea rax, [rip] ; push rbp
+        assert_eq!(describe(Architecture::X86_64, &[0x48, 0x8d, 0x05, 0x00, 0x00, 0x00, 0x00]), (Other, None));
+        assert_eq!(describe(Architecture::X86_64, &[0x55]), (Store, None));
+    }
+}
@@ -0,0 +1,116 @@
+//! ARM32 / THUMB mode detection for code of unknown instruction set.
+
+use super::flow::branch_target;
+use super::{Architecture, DisasmOptions, DisassemblyResult, disassemble_with, is_block_terminator};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Mnemonics that decode from random data far more often than they appear in compiled code.
+const UNLIKELY_MNEMONICS: [&str; 14] = [
+    "udf", "bkpt", "hlt", "cdp", "cdp2", "ldc", "ldc2", "ldcl", "stc", "stc2", "stcl", "mcr2", "mrc2", "mcrr2",
+];
+
+/// ARM32 condition field value for "always" (unconditional execution).
+const ARM_COND_ALWAYS: u8 = 0xe;
+
+/// Guesses whether `bytes` is ARM32 or THUMB code.
+///
+/// Both modes are decoded and scored by the number of bytes covered by plausible instructions.
+/// Undecodable bytes, zero padding, rarely used mnemonics, branches into the middle of another
+/// instruction, and unreferenced code right after an unconditional branch score nothing.
+/// Conditional ARM32 instructions score less than unconditional ones since compilers rarely
+/// emit them outside short sequences.
+///
+/// This is a heuristic. Short buffers often decode cleanly in both modes, in which case
+/// THUMB (the default for most 32-bit Android code) is returned.
+pub fn detect_arm_mode(bytes: &[u8], address: u64) -> Result<Architecture> {
+    let arm = score_mode(Architecture::ARM32, bytes, address)?;
+    let thumb = score_mode(Architecture::THUMB, bytes, address)?;
+    Ok(if arm > thumb { Architecture::ARM32 } else { Architecture::THUMB })
+}
+
+fn score_mode(arch: Architecture, bytes: &[u8], address: u64) -> Result<usize> {
+    let options = DisasmOptions { skipdata: true, ..Default::default() };
+    let instructions = disassemble_with(arch, bytes, address, 0, &options)?;
+    let range = address..address + bytes.len() as u64;
+    let boundaries: HashSet<u64> = instructions.iter().map(|insn| insn.address).collect();
+    let targets: HashSet<u64> = instructions
+        .iter()
+        .filter_map(branch_target)
+        .filter(|target| range.contains(target))
+        .collect();
+
+    let mut score = 0;
+    let mut after_terminator = false;
+    for insn in &instructions {
+        // Code directly after an unconditional branch is only plausible if something jumps to it
+        let reachable = !after_terminator || targets.contains(&insn.address);
+        if reachable {
+            score += instruction_score(arch, insn, &boundaries, &range);
+        }
+        after_terminator = is_block_terminator(arch, &insn.mnemonic, &insn.operands);
+    }
+    Ok(score)
+}
+
+fn instruction_score(
+    arch: Architecture,
+    insn: &DisassemblyResult,
+    boundaries: &HashSet<u64>,
+    range: &Range<u64>,
+) -> usize {
+    if insn.mnemonic.starts_with('.') || UNLIKELY_MNEMONICS.contains(&insn.mnemonic.as_str()) {
+        return 0;
+    }
+    // All-zero words decode as `movs r0, r0` / `andeq r0, r0, r0` but are almost always padding
+    if insn.bytes.iter().all(|&b| b == 0) {
+        return 0;
+    }
+    if let Some(target) = branch_target(insn)
+        && range.contains(&target)
+        && !boundaries.contains(&target)
+    {
+        return 0;
+    }
+
+    let len = insn.bytes.len();
+    if arch == Architecture::ARM32 && insn.bytes.get(3).is_some_and(|b| b >> 4 != ARM_COND_ALWAYS) {
+        return len / 4;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words_le(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+
+    fn halfwords_le(halfwords: &[u16]) -> Vec<u8> {
+        halfwords.iter().flat_map(|h| h.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_detect_arm32_function() {
+        // push {r4, lr}; mov r4, r0; mov r0, #1; add r0, r0, r4; cmp r0, #0; beq; sub r0, r0, #1; pop {r4, pc}
+        let code = words_le(&[
+            0xe92d4010, 0xe1a04000, 0xe3a00001, 0xe0800004, 0xe3500000, 0x0a000000, 0xe2400001, 0xe8bd8010,
+        ]);
+        assert_eq!(detect_arm_mode(&code, 0x1000).unwrap(), Architecture::ARM32);
+    }
+
+    #[test]
+    fn test_detect_thumb_function() {
+        // push {r4, lr}; mov r4, r0; movs r0, #1; add r0, r4; cmp r0, #0; beq; subs r0, #1; pop {r4, pc}
+        let code = halfwords_le(&[0xb510, 0x4604, 0x2001, 0x4420, 0x2800, 0xd000, 0x3801, 0xbd10]);
+        assert_eq!(detect_arm_mode(&code, 0x1000).unwrap(), Architecture::THUMB);
+    }
+
+    #[test]
+    fn test_detect_empty_defaults_to_thumb() {
+        assert_eq!(detect_arm_mode(&[], 0x1000).unwrap(), Architecture::THUMB);
+    }
+}
@@ -0,0 +1,220 @@
+//! Control-flow helpers over decoded instruction sequences.
+
+use super::DisassemblyResult;
+use super::pseudo::parse_imm;
+
+/// ARM condition code suffixes used by conditional branches (`bne`, `b.lt`, ...).
+const CONDITION_CODES: [&str; 17] = [
+    "eq", "ne", "cs", "hs", "cc", "lo", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le", "al",
+];
+
+/// A natural loop found from a backward branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopInfo {
+    /// Address of the loop head (the backward branch target).
+    pub head: u64,
+    /// Address of the branch instruction forming the back-edge.
+    pub back_edge: u64,
+}
+
+/// Returns the target of a direct (immediate) branch, or `None` for anything else.
+///
+/// Calls are not treated as branches since they return to the next instruction.
+pub fn branch_target(insn: &DisassemblyResult) -> Option<u64> {
+    if !is_direct_branch(&insn.mnemonic) {
+        return None;
+    }
+    parse_imm(insn.operands.rsplit(',').next()?)
+}
+
+fn is_direct_branch(mnemonic: &str) -> bool {
+    let mnemonic = mnemonic.trim_end_matches(".w").trim_end_matches(".n");
+    match mnemonic {
+        "b" | "cbz" | "cbnz" | "tbz" | "tbnz" | "loop" => true,
+        _ if mnemonic.starts_with("b.") => true,
+        _ if mnemonic.starts_with('j') => true,
+        _ => mnemonic.strip_prefix('b').is_some_and(|cond| CONDITION_CODES.contains(&cond)),
+    }
+}
+
+/// Detects natural loops: a branch whose target lies before it and inside the decoded range.
+///
+/// Only back-edges are reported; no interval or dominator analysis is performed.
+pub fn detect_loops(instructions: &[DisassemblyResult]) -> Vec<LoopInfo> {
+    instructions
+        .iter()
+        .filter_map(|insn| {
+            let target = branch_target(insn)?;
+            let is_back_edge = target < insn.address && instructions.iter().any(|other| other.address == target);
+            is_back_edge.then_some(LoopInfo { head: target, back_edge: insn.address })
+        })
+        .collect()
+}
+
+/// Appends `// loop` to the pseudo-code of every loop head and back-edge instruction.
+pub fn annotate_loops(instructions: &mut [DisassemblyResult], loops: &[LoopInfo]) {
+    for insn in instructions.iter_mut() {
+        let involved = loops.iter().any(|l| l.head == insn.address || l.back_edge == insn.address);
+        if let Some(pseudo) = insn.pseudo_code.as_mut()
+            && involved
+        {
+            pseudo.push_str(" // loop");
+        }
+    }
+}
+
+/// Instructions after a reported boundary within which another boundary of the same kind is
+/// treated as part of the same prologue or epilogue (e.g. `stp x29, x30, ...` then `mov x29, sp`).
+const BOUNDARY_RUN: usize = 4;
+
+/// Kind of function boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundaryKind {
+    /// Likely function start.
+    Prologue,
+    /// Likely function end.
+    Epilogue,
+}
+
+/// A function boundary found by [`find_function_boundaries`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionBoundary {
+    /// Index of the first instruction of the prologue or epilogue.
+    pub index: usize,
+    pub kind: BoundaryKind,
+}
+
+/// Returns true for instructions that typically open an ARM64 or ARM32/THUMB function:
+/// `stp x29, x30, [sp, #-N]!`, `mov x29, sp`, `paciasp`/`pacibsp`, and `push {..., lr}`.
+pub fn is_prologue(insn: &DisassemblyResult) -> bool {
+    let operands = insn.operands.as_str();
+    match insn.mnemonic.as_str() {
+        "stp" => operands.starts_with("x29, x30, [sp") && operands.ends_with("]!"),
+        "mov" => operands == "x29, sp",
+        "paciasp" | "pacibsp" => true,
+        "push" | "push.w" | "stmdb" | "stmdb.w" => operands.contains("lr}"),
+        _ => false,
+    }
+}
+
+/// Returns true for instructions that typically close an ARM64 or ARM32/THUMB function:
+/// `ldp x29, x30, [sp...`, `ret`, and `pop {..., pc}`.
+pub fn is_epilogue(insn: &DisassemblyResult) -> bool {
+    let operands = insn.operands.as_str();
+    match insn.mnemonic.as_str() {
+        "ldp" => operands.starts_with("x29, x30, [sp"),
+        "ret" | "retaa" | "retab" => true,
+        "pop" | "pop.w" | "ldm" | "ldm.w" | "ldmia" | "ldmia.w" => operands.contains("pc}"),
+        _ => false,
+    }
+}
+
+/// Finds likely function starts and ends.
+///
+/// Consecutive prologue (or epilogue) instructions are reported once, at the first of them,
+/// so `stp x29, x30, [sp, #-0x10]!` followed by `mov x29, sp` is a single boundary.
+pub fn find_function_boundaries(instructions: &[DisassemblyResult]) -> Vec<FunctionBoundary> {
+    let mut boundaries: Vec<FunctionBoundary> = Vec::new();
+    for (index, insn) in instructions.iter().enumerate() {
+        let kind = if is_prologue(insn) {
+            BoundaryKind::Prologue
+        } else if is_epilogue(insn) {
+            BoundaryKind::Epilogue
+        } else {
+            continue;
+        };
+
+        let continues_run =
+            boundaries.last().is_some_and(|last| last.kind == kind && index - last.index <= BOUNDARY_RUN);
+        if !continues_run {
+            boundaries.push(FunctionBoundary { index, kind });
+        }
+    }
+    boundaries
+}
+
+/// Appends `// function start` to the pseudo-code of every prologue boundary.
+pub fn annotate_function_starts(instructions: &mut [DisassemblyResult], boundaries: &[FunctionBoundary]) {
+    for boundary in boundaries.iter().filter(|b| b.kind == BoundaryKind::Prologue) {
+        if let Some(pseudo) = instructions.get_mut(boundary.index).and_then(|insn| insn.pseudo_code.as_mut()) {
+            pseudo.push_str(" // function start");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disasm::{disassemble, disassemble_with_pseudo, Architecture};
+
+    // mov w0, #0 ; add w0, w0, #1 ; cmp w0, #0xa ; b.lt #0x1004 ; ret
+    const COUNTING_LOOP: [u8; 20] = [
+        0x00, 0x00, 0x80, 0x52, 0x00, 0x04, 0x00, 0x11, 0x1f, 0x28, 0x00, 0x71, 0xcb, 0xff, 0xff, 0x54, 0xc0, 0x03, 0x5f, 0xd6,
+    ];
+
+    #[test]
+    fn test_detect_counting_loop() {
+        let results = disassemble(Architecture::ARM64, &COUNTING_LOOP, 0x1000, 0).unwrap();
+        assert_eq!(results[3].mnemonic, "b.lt");
+        assert_eq!(branch_target(&results[3]), Some(0x1004));
+
+        let loops = detect_loops(&results);
+        assert_eq!(loops, vec![LoopInfo { head: 0x1004, back_edge: 0x100c }]);
+    }
+
+    #[test]
+    fn test_annotate_loops_in_pseudo() {
+        let results = disassemble_with_pseudo(Architecture::ARM64, &COUNTING_LOOP, 0x1000, 0).unwrap();
+        let annotated: Vec<bool> =
+            results.iter().map(|r| r.pseudo_code.as_deref().unwrap().ends_with("// loop")).collect();
+        assert_eq!(annotated, vec![false, true, false, true, false]);
+    }
+
+    #[test]
+    fn test_arm64_function_boundaries() {
+        // stp x29, x30, [sp, #-0x10]! ; mov x29, sp ; nop ; ldp x29, x30, [sp], #0x10 ; ret ;
+        // stp x29, x30, [sp, #-0x20]!
+        let bytes = [
+            0xfd, 0x7b, 0xbf, 0xa9, 0xfd, 0x03, 0x00, 0x91, 0x1f, 0x20, 0x03, 0xd5, 0xfd, 0x7b, 0xc1, 0xa8, 0xc0, 0x03, 0x5f,
+            0xd6, 0xfd, 0x7b, 0xbe, 0xa9,
+        ];
+        let results = disassemble(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert!(is_prologue(&results[0]));
+        assert!(is_prologue(&results[1]));
+        assert!(is_epilogue(&results[3]));
+        assert!(is_epilogue(&results[4]));
+        assert!(!is_prologue(&results[2]) && !is_epilogue(&results[2]));
+
+        let boundaries = find_function_boundaries(&results);
+        assert_eq!(
+            boundaries,
+            vec![
+                FunctionBoundary { index: 0, kind: BoundaryKind::Prologue },
+                FunctionBoundary { index: 3, kind: BoundaryKind::Epilogue },
+                FunctionBoundary { index: 5, kind: BoundaryKind::Prologue },
+            ]
+        );
+
+        let pseudo = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert!(pseudo[0].pseudo_code.as_deref().unwrap().ends_with("// function start"));
+        assert!(!pseudo[1].pseudo_code.as_deref().unwrap().ends_with("// function start"));
+    }
+
+    #[test]
+    fn test_thumb_function_boundaries() {
+        // push {r4, lr} ; movs r0, #1 ; pop {r4, pc}
+        let bytes = [0x10, 0xb5, 0x01, 0x20, 0x10, 0xbd];
+        let results = disassemble(Architecture::THUMB, &bytes, 0x1000, 0).unwrap();
+        let kinds: Vec<BoundaryKind> = find_function_boundaries(&results).iter().map(|b| b.kind).collect();
+        assert_eq!(kinds, vec![BoundaryKind::Prologue, BoundaryKind::Epilogue]);
+    }
+
+    #[test]
+    fn test_forward_branch_is_not_loop() {
+        // b #0x1008 ; nop ; ret
+        let bytes = [0x02, 0x00, 0x00, 0x14, 0x1f, 0x20, 0x03, 0xd5, 0xc0, 0x03, 0x5f, 0xd6];
+        let results = disassemble(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(branch_target(&results[0]), Some(0x1008));
+        assert!(detect_loops(&results).is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+//! Hex + ASCII dump for regions that do not disassemble sensibly.
+
+use crate::ext::jni::JniErrorCode;
+use anyhow::Result;
+
+/// One row of a hex dump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexDumpRow {
+    pub address: u64,
+    pub bytes: Vec<u8>,
+    /// Printable ASCII rendering of `bytes`, with `.` for anything outside `0x20..=0x7e`.
+    pub ascii: String,
+}
+
+/// Splits `bytes` into rows of `bytes_per_row` bytes starting at `address`.
+///
+/// The last row is shorter when the length is not a multiple of `bytes_per_row`.
+pub fn hexdump(bytes: &[u8], address: u64, bytes_per_row: usize) -> Result<Vec<HexDumpRow>> {
+    if bytes_per_row == 0 {
+        return Err(JniErrorCode::InvalidArgument.error("bytes_per_row must be greater than 0"));
+    }
+
+    Ok(bytes
+        .chunks(bytes_per_row)
+        .enumerate()
+        .map(|(row, chunk)| HexDumpRow {
+            address: address.wrapping_add((row * bytes_per_row) as u64),
+            bytes: chunk.to_vec(),
+            ascii: chunk
+                .iter()
+                .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+                .collect(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hexdump_rows() {
+        let bytes = b"Hello,\x00\x01World\xff!";
+        let rows = hexdump(bytes, 0x1000, 8).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].address, 0x1000);
+        assert_eq!(rows[0].bytes, b"Hello,\x00\x01");
+        assert_eq!(rows[0].ascii, "Hello,..");
+        assert_eq!(rows[1].address, 0x1008);
+        assert_eq!(rows[1].bytes, b"World\xff!");
+        assert_eq!(rows[1].ascii, "World.!");
+    }
+
+    #[test]
+    fn test_hexdump_invalid_row_size() {
+        assert!(hexdump(&[0u8; 4], 0, 0).is_err());
+        assert!(hexdump(&[], 0, 16).unwrap().is_empty());
+    }
+}
@@ -1,44 +1,190 @@
-//! ARM instruction disassembler using Capstone engine.
+//! ARM, x86, and MIPS instruction disassembler using Capstone engine.
 
+mod cache;
+mod describe;
+mod detect;
+pub mod flow;
+mod hexdump;
 mod pseudo;
+mod symbols;
 
+use crate::ext::jni::JniErrorCode;
 use anyhow::{anyhow, Result};
+use capstone::arch::ArchOperand;
 use capstone::prelude::*;
-pub use pseudo::generate_pseudo_code;
+use log::warn;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+pub use describe::{describe_instruction, InstructionCategory, InstructionDescription};
+pub use detect::detect_arm_mode;
+pub use flow::{
+    detect_loops, find_function_boundaries, is_epilogue, is_prologue, BoundaryKind, FunctionBoundary, LoopInfo,
+};
+pub use hexdump::{hexdump, HexDumpRow};
+pub use pseudo::{generate_pseudo_block, generate_pseudo_code};
+pub use symbols::annotate_symbols;
 
 /// Architecture modes for disassembly.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Architecture {
     ARM32 = 0,
     THUMB = 1,
     ARM64 = 2,
+    X86 = 3,
+    X86_64 = 4,
+    MIPS = 5,
+    MIPS64 = 6,
 }
 
 impl Architecture {
+    /// MIPS executes the instruction after a branch (the delay slot) before the branch takes effect.
+    pub fn has_delay_slots(self) -> bool {
+        matches!(self, Architecture::MIPS | Architecture::MIPS64)
+    }
+
     pub fn from_i32(value: i32) -> Result<Self> {
         match value {
             0 => Ok(Architecture::ARM32),
             1 => Ok(Architecture::THUMB),
             2 => Ok(Architecture::ARM64),
-            _ => Err(anyhow!("Invalid architecture value: {}", value)),
+            3 => Ok(Architecture::X86),
+            4 => Ok(Architecture::X86_64),
+            5 => Ok(Architecture::MIPS),
+            6 => Ok(Architecture::MIPS64),
+            _ => Err(JniErrorCode::InvalidArgument.error(format!("Invalid architecture value: {}", value))),
+        }
+    }
+}
+
+thread_local! {
+    /// Per-thread Capstone instances keyed by architecture and big-endian flag.
+    ///
+    /// Building a Capstone engine is comparatively expensive and the UI disassembles small
+    /// windows repeatedly while scrolling, so each worker thread keeps one engine per arch.
+    static CAPSTONE_CACHE: RefCell<HashMap<(Architecture, bool), Capstone>> = RefCell::new(HashMap::new());
+
+    /// Per-thread results of recent [`disassemble_with`] calls, see [`cache`].
+    static RESULT_CACHE: RefCell<cache::ResultCache> = RefCell::new(cache::ResultCache::new());
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of times this thread entered Capstone, to check that cache hits skip it.
+    static CAPSTONE_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+
+    /// Number of Capstone engines this thread built, to check that the cached engine is reused.
+    static CAPSTONE_CREATED: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Operand syntax used by Capstone when formatting instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DisasmSyntax {
+    /// Capstone's default syntax for the architecture.
+    #[default]
+    Default = 0,
+    /// Numbered registers for ARM32/THUMB (`r11`, `r13` instead of `fp`, `sp`).
+    /// ARM64 output is unchanged, as Capstone already prints numbered registers there.
+    NoRegName = 1,
+    /// AT&T syntax for x86/x86_64.
+    Att = 2,
+}
+
+impl DisasmSyntax {
+    pub fn from_i32(value: i32) -> Result<Self> {
+        match value {
+            0 => Ok(DisasmSyntax::Default),
+            1 => Ok(DisasmSyntax::NoRegName),
+            2 => Ok(DisasmSyntax::Att),
+            _ => Err(JniErrorCode::InvalidArgument.error(format!("Invalid syntax value: {}", value))),
+        }
+    }
+
+    /// Capstone syntax for `arch`. Syntaxes that do not apply to the architecture fall back to
+    /// the default, which Capstone selects with the Intel value for ARM and x86. MIPS rejects
+    /// the syntax option entirely, so `None` is returned there.
+    fn capstone_syntax(self, arch: Architecture) -> Option<capstone::Syntax> {
+        match (self, arch) {
+            (_, Architecture::MIPS | Architecture::MIPS64) => None,
+            (DisasmSyntax::NoRegName, Architecture::ARM32 | Architecture::THUMB) => Some(capstone::Syntax::NoRegName),
+            (DisasmSyntax::Att, Architecture::X86 | Architecture::X86_64) => Some(capstone::Syntax::Att),
+            _ => Some(capstone::Syntax::Intel),
         }
     }
 }
 
+/// Options for [`disassemble_with`] and [`disassemble_stream_with`].
+///
+/// The default decodes little-endian with Capstone's default syntax, without instruction
+/// details, and stops at the first undecodable instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DisasmOptions {
+    /// Fill in groups, implicit registers, and structured operands.
+    pub detail: bool,
+    /// Generate pseudo-code (implies `detail`).
+    pub pseudo: bool,
+    /// Keep the original instruction after the pseudo-code as a `// mnemonic operands` comment
+    /// (only with `pseudo`).
+    pub pseudo_source: bool,
+    /// Emit `.byte` entries for undecodable bytes instead of stopping.
+    pub skipdata: bool,
+    /// Decode the bytes as big-endian (ARM and MIPS architectures only).
+    pub big_endian: bool,
+    /// Operand syntax.
+    pub syntax: DisasmSyntax,
+    /// Stop after the first unconditional branch or return, which is included in the result.
+    pub stop_at_branch: bool,
+    /// Decode at most this many bytes (0 = no limit), independently of the instruction count.
+    /// An instruction that would extend past the limit is not decoded.
+    pub max_bytes: usize,
+}
+
 /// Disassembly result item.
 #[derive(Debug, Clone)]
 pub struct DisassemblyResult {
     pub address: u64,
+    /// Capstone instruction ID (`insn.id()`), 0 for skipdata `.byte` entries.
+    pub id: u32,
     pub bytes: Vec<u8>,
     pub mnemonic: String,
     pub operands: String,
     pub pseudo_code: Option<String>,
+    /// Instruction groups, e.g. `jump`, `call`, `return` (detail mode only).
+    pub groups: Vec<String>,
+    /// Registers implicitly read by the instruction (detail mode only).
+    pub regs_read: Vec<String>,
+    /// Registers implicitly written by the instruction (detail mode only).
+    pub regs_written: Vec<String>,
+    /// Structured operands decoded from the instruction detail (detail mode only).
+    pub operands_detail: Vec<Operand>,
+}
+
+impl DisassemblyResult {
+    /// Instruction size in bytes.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// Structured instruction operand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Reg(String),
+    Imm(i64),
+    Mem {
+        base: Option<String>,
+        index: Option<String>,
+        disp: i64,
+    },
 }
 
 /// Disassembles instructions using Capstone.
 ///
+/// Empty input, or bytes too short for a whole instruction, give an empty result rather
+/// than an error; a trailing partial instruction is dropped and logged (see [`unconsumed_tail`]).
+///
 /// # Arguments
-/// * `arch` - Architecture mode (ARM32, THUMB, or ARM64)
+/// * `arch` - Architecture mode (ARM32, THUMB, ARM64, X86, X86_64, MIPS, or MIPS64)
 /// * `bytes` - Instruction bytes to disassemble
 /// * `address` - Starting address for the instructions
 /// * `count` - Maximum number of instructions to disassemble (0 = all)
@@ -51,27 +197,117 @@ pub fn disassemble(
     address: u64,
     count: usize,
 ) -> Result<Vec<DisassemblyResult>> {
-    let cs = create_capstone(arch)?;
+    disassemble_with(arch, bytes, address, count, &DisasmOptions::default())
+}
 
-    let instructions = if count > 0 {
-        cs.disasm_count(bytes, address, count)?
-    } else {
-        cs.disasm_all(bytes, address)?
-    };
+/// Disassembles instructions with an explicit byte order.
+///
+/// Big-endian is only supported for the ARM (ARM32, THUMB, ARM64) and MIPS (MIPS, MIPS64) architectures.
+///
+/// # Arguments
+/// * `arch` - Architecture mode
+/// * `bytes` - Instruction bytes to disassemble
+/// * `address` - Starting address for the instructions
+/// * `count` - Maximum number of instructions to disassemble (0 = all)
+/// * `big_endian` - Decode the bytes as big-endian
+///
+/// # Returns
+/// Vector of disassembly results
+pub fn disassemble_endian(
+    arch: Architecture,
+    bytes: &[u8],
+    address: u64,
+    count: usize,
+    big_endian: bool,
+) -> Result<Vec<DisassemblyResult>> {
+    disassemble_with(arch, bytes, address, count, &DisasmOptions { big_endian, ..Default::default() })
+}
 
-    let mut results = Vec::with_capacity(instructions.len());
+/// Disassembles instructions in skipdata mode.
+///
+/// Undecodable bytes are emitted as `.byte` pseudo-instructions instead of ending the
+/// disassembly, so the result spans the whole byte range when reading mixed code and data.
+///
+/// # Arguments
+/// * `arch` - Architecture mode
+/// * `bytes` - Instruction bytes to disassemble
+/// * `address` - Starting address for the instructions
+/// * `count` - Maximum number of instructions to disassemble (0 = all)
+///
+/// # Returns
+/// Vector of disassembly results
+pub fn disassemble_skipdata(
+    arch: Architecture,
+    bytes: &[u8],
+    address: u64,
+    count: usize,
+) -> Result<Vec<DisassemblyResult>> {
+    disassemble_with(arch, bytes, address, count, &DisasmOptions { skipdata: true, ..Default::default() })
+}
+
+/// Disassembles from `address` until the first unconditional branch or return.
+///
+/// Instructions are decoded one at a time so nothing past the terminator is read.
+/// Decoding also stops when the bytes run out or an instruction cannot be decoded.
+/// The terminating instruction is included in the result.
+pub fn disassemble_until_branch(
+    arch: Architecture,
+    bytes: &[u8],
+    address: u64,
+) -> Result<Vec<DisassemblyResult>> {
+    disassemble_with(arch, bytes, address, 0, &DisasmOptions { stop_at_branch: true, ..Default::default() })
+}
 
-    for insn in instructions.iter() {
-        results.push(DisassemblyResult {
-            address: insn.address(),
-            bytes: insn.bytes().to_vec(),
-            mnemonic: insn.mnemonic().unwrap_or("???").to_string(),
-            operands: insn.op_str().unwrap_or("").to_string(),
-            pseudo_code: None,
-        });
+/// Returns up to `max_insns` instructions immediately preceding `target_address`.
+///
+/// ARM, THUMB, and x86 cannot be decoded backward reliably, so this decodes forward from
+/// `window_start` and keeps the last instructions that end at or before the target.
+/// `window_start` must be a known instruction boundary (for example a function start);
+/// from a misaligned anchor the decoded stream may never line up with `target_address`.
+/// Undecodable bytes are kept as `.byte` entries, as in [`disassemble_skipdata`].
+///
+/// # Arguments
+/// * `arch` - Architecture mode
+/// * `bytes` - Instruction bytes starting at `window_start`
+/// * `window_start` - Address of the first byte, a valid instruction boundary
+/// * `target_address` - Address whose preceding instructions are wanted
+/// * `max_insns` - Maximum number of instructions to return
+///
+/// # Returns
+/// Instructions in address order, the last one ending at or just before `target_address`
+pub fn disassemble_backward(
+    arch: Architecture,
+    bytes: &[u8],
+    window_start: u64,
+    target_address: u64,
+    max_insns: usize,
+) -> Result<Vec<DisassemblyResult>> {
+    if target_address < window_start {
+        return Err(JniErrorCode::InvalidArgument.error(format!(
+            "Target address 0x{:x} is before the window start 0x{:x}",
+            target_address, window_start
+        )));
     }
 
-    Ok(results)
+    if max_insns == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut results = VecDeque::new();
+
+    let options = DisasmOptions { skipdata: true, ..Default::default() };
+    disassemble_stream_with(arch, bytes, window_start, 0, &options, |result| {
+        if result.address + result.bytes.len() as u64 > target_address {
+            return false;
+        }
+        if results.len() == max_insns {
+            results.pop_front();
+        }
+        results.push_back(result);
+        true
+    })?;
+
+    Ok(results.into())
 }
 
 /// Disassembles instructions with pseudo-code generation.
@@ -90,54 +326,476 @@ pub fn disassemble_with_pseudo(
     address: u64,
     count: usize,
 ) -> Result<Vec<DisassemblyResult>> {
-    let mut cs = create_capstone(arch)?;
-    cs.set_detail(true)?;
+    disassemble_with(arch, bytes, address, count, &DisasmOptions { pseudo: true, ..Default::default() })
+}
 
-    let instructions = if count > 0 {
-        cs.disasm_count(bytes, address, count)?
-    } else {
-        cs.disasm_all(bytes, address)?
-    };
+/// Like [`disassemble_with_pseudo`], but each pseudo-code line also keeps the original
+/// instruction as a trailing comment, e.g. `x0 = x1  // mov x0, x1`.
+pub fn disassemble_with_pseudo_source(
+    arch: Architecture,
+    bytes: &[u8],
+    address: u64,
+    count: usize,
+) -> Result<Vec<DisassemblyResult>> {
+    let options = DisasmOptions { pseudo: true, pseudo_source: true, ..Default::default() };
+    disassemble_with(arch, bytes, address, count, &options)
+}
+
+/// Formats results as a plain-text listing, one `address: bytes  mnemonic operands  ; pseudo`
+/// line per instruction.
+///
+/// The bytes and instruction columns are padded to the widest entry so the pseudo-code lines
+/// up; the `; pseudo` column is omitted for results without pseudo-code.
+pub fn format_listing(results: &[DisassemblyResult]) -> String {
+    let rows: Vec<(String, String)> = results
+        .iter()
+        .map(|result| {
+            let bytes = result.bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" ");
+            let insn = if result.operands.is_empty() {
+                result.mnemonic.clone()
+            } else {
+                format!("{} {}", result.mnemonic, result.operands)
+            };
+            (bytes, insn)
+        })
+        .collect();
+    let bytes_width = rows.iter().map(|(bytes, _)| bytes.len()).max().unwrap_or(0);
+    let insn_width = rows.iter().map(|(_, insn)| insn.len()).max().unwrap_or(0);
 
-    let mut results = Vec::with_capacity(instructions.len());
+    let mut lines = Vec::with_capacity(results.len());
+    for (result, (bytes, insn)) in results.iter().zip(rows) {
+        let line = match result.pseudo_code {
+            Some(ref pseudo) => {
+                format!("{:#x}: {:<bytes_width$}  {:<insn_width$}  ; {}", result.address, bytes, insn, pseudo)
+            },
+            None => format!("{:#x}: {:<bytes_width$}  {}", result.address, bytes, insn).trim_end().to_string(),
+        };
+        lines.push(line);
+    }
+    lines.join("\n")
+}
 
-    for insn in instructions.iter() {
-        let mnemonic = insn.mnemonic().unwrap_or("???");
-        let operands = insn.op_str().unwrap_or("");
+/// Disassembles instructions with the given options.
+///
+/// With `pseudo` set, loops and function starts found in the result are also annotated in
+/// the pseudo-code.
+///
+/// # Arguments
+/// * `arch` - Architecture mode
+/// * `bytes` - Instruction bytes to disassemble
+/// * `address` - Starting address for the instructions
+/// * `count` - Maximum number of instructions to disassemble (0 = all)
+/// * `options` - Decoding and output options
+///
+/// # Returns
+/// Vector of disassembly results
+pub fn disassemble_with(
+    arch: Architecture,
+    bytes: &[u8],
+    address: u64,
+    count: usize,
+    options: &DisasmOptions,
+) -> Result<Vec<DisassemblyResult>> {
+    let key = cache::CacheKey::new(arch, bytes, address, count, options);
+    if let Some(key) = key
+        && let Some(results) = RESULT_CACHE.with(|cache| cache.borrow_mut().get(&key, bytes))
+    {
+        return Ok(results);
+    }
 
-        let pseudo = generate_pseudo_code(arch, mnemonic, operands, &cs, &insn);
+    let mut results = Vec::new();
+    disassemble_stream_with(arch, bytes, address, count, options, |result| {
+        results.push(result);
+        true
+    })?;
 
-        results.push(DisassemblyResult {
-            address: insn.address(),
-            bytes: insn.bytes().to_vec(),
-            mnemonic: mnemonic.to_string(),
-            operands: operands.to_string(),
-            pseudo_code: Some(pseudo),
-        });
+    if options.pseudo {
+        let loops = detect_loops(&results);
+        flow::annotate_loops(&mut results, &loops);
+        let boundaries = find_function_boundaries(&results);
+        flow::annotate_function_starts(&mut results, &boundaries);
     }
 
+    if let Some(key) = key {
+        RESULT_CACHE.with(|cache| cache.borrow_mut().insert(key, bytes, results.clone()));
+    }
     Ok(results)
 }
 
-/// Creates a Capstone instance for the specified architecture.
-fn create_capstone(arch: Architecture) -> Result<Capstone> {
+/// Like [`disassemble_with`], but direct branch and call targets found in `symbols` are shown
+/// by name in the operands and pseudo-code, e.g. `bl sub_400abc` and `call sub_400abc`.
+///
+/// Detail is always decoded since the targets are resolved from the structured operands.
+pub fn disassemble_with_symbols(
+    arch: Architecture,
+    bytes: &[u8],
+    address: u64,
+    count: usize,
+    options: &DisasmOptions,
+    symbols: &HashMap<u64, String>,
+) -> Result<Vec<DisassemblyResult>> {
+    let options = DisasmOptions { detail: true, ..*options };
+    let mut results = disassemble_with(arch, bytes, address, count, &options)?;
+    annotate_symbols(arch, &mut results, symbols);
+    Ok(results)
+}
+
+/// Disassembles instructions one at a time, passing each to `f` as soon as it is decoded.
+///
+/// Decoding stops when `f` returns `false`, after `count` instructions (0 = all), when the
+/// bytes (or `max_bytes` of them) run out, or at the first undecodable instruction. The Capstone engine is not held
+/// while `f` runs, so the callback may itself disassemble. In skipdata mode a trailing partial ARM instruction
+/// is passed to `f` as a `.byte` entry.
+///
+/// # Arguments
+/// * `arch` - Architecture mode
+/// * `bytes` - Instruction bytes to disassemble
+/// * `address` - Starting address for the instructions
+/// * `count` - Maximum number of instructions to disassemble (0 = all)
+/// * `f` - Called for every instruction; return `false` to stop early
+///
+/// # Returns
+/// Number of instructions passed to `f`
+pub fn disassemble_stream(
+    arch: Architecture,
+    bytes: &[u8],
+    address: u64,
+    count: usize,
+    f: impl FnMut(DisassemblyResult) -> bool,
+) -> Result<usize> {
+    disassemble_stream_with(arch, bytes, address, count, &DisasmOptions::default(), f)
+}
+
+/// Streaming form of [`disassemble_with`].
+///
+/// Loop annotations need the whole result and are not added to streamed pseudo-code.
+pub fn disassemble_stream_with(
+    arch: Architecture,
+    bytes: &[u8],
+    address: u64,
+    count: usize,
+    options: &DisasmOptions,
+    mut f: impl FnMut(DisassemblyResult) -> bool,
+) -> Result<usize> {
+    let bytes = match options.max_bytes {
+        0 => bytes,
+        max_bytes => &bytes[..bytes.len().min(max_bytes)],
+    };
+    let mut offset = 0usize;
+    let mut emitted = 0usize;
+    // Recently decoded instructions for the ARM64 adrp lookback, Thumb IT blocks, and MIPS delay
+    // slots in pseudo-code
+    let mut recent: Vec<DisassemblyResult> = Vec::new();
+    // Set once a MIPS block terminator is decoded; its delay slot still belongs to the block
+    let mut stop_after_next = false;
+    let cs = CapstoneLease::take(arch, options)?;
+
+    while offset < bytes.len() && (count == 0 || emitted < count) {
+        let instructions = cs.disasm_count(&bytes[offset..], address + offset as u64, 1)?;
+        let next = instructions.iter().next().map(|insn| build_result(&cs, arch, insn, options, &recent));
+        let Some(result) = next else {
+            let remaining = bytes.len() - offset;
+            if let Some(width) = fixed_instruction_width(arch) && remaining < width {
+                let tail_address = address + offset as u64;
+                warn!("{} trailing byte(s) at 0x{:x} do not form a whole {:?} instruction", remaining, tail_address, arch);
+                // Skipdata keeps the whole range visible, so report the tail as data as well
+                if options.skipdata {
+                    emitted += 1;
+                    f(trailing_data(tail_address, &bytes[offset..]));
+                }
+            }
+            break;
+        };
+
+        offset += result.bytes.len();
+        emitted += 1;
+        let terminator = options.stop_at_branch && is_block_terminator(arch, &result.mnemonic, &result.operands);
+        if options.pseudo {
+            if recent.len() == pseudo::ADRP_LOOKBACK {
+                recent.remove(0);
+            }
+            recent.push(result.clone());
+        }
+        if !f(result) || stop_after_next {
+            break;
+        }
+        if terminator {
+            if !arch.has_delay_slots() {
+                break;
+            }
+            stop_after_next = true;
+        }
+    }
+
+    Ok(emitted)
+}
+
+/// Returns how many trailing bytes of a `len`-byte buffer cannot form a whole instruction.
+///
+/// ARM32, ARM64, and MIPS instructions are 4 bytes and THUMB instructions a multiple of 2, so a
+/// buffer cut mid-word (e.g. at the end of a region read) leaves a tail that is never
+/// decoded. Always 0 for the variable-length x86 architectures.
+pub fn unconsumed_tail(arch: Architecture, len: usize) -> usize {
+    fixed_instruction_width(arch).map_or(0, |width| len % width)
+}
+
+/// Instruction alignment in bytes for the fixed-width architectures.
+fn fixed_instruction_width(arch: Architecture) -> Option<usize> {
+    match arch {
+        Architecture::ARM32 | Architecture::ARM64 | Architecture::MIPS | Architecture::MIPS64 => Some(4),
+        Architecture::THUMB => Some(2),
+        Architecture::X86 | Architecture::X86_64 => None,
+    }
+}
+
+/// Builds the `.byte` entry for a trailing partial instruction, formatted like Capstone's skipdata.
+fn trailing_data(address: u64, bytes: &[u8]) -> DisassemblyResult {
+    DisassemblyResult {
+        address,
+        id: 0,
+        bytes: bytes.to_vec(),
+        mnemonic: ".byte".to_string(),
+        operands: bytes.iter().map(|b| format!("0x{:02x}", b)).collect::<Vec<_>>().join(", "),
+        pseudo_code: None,
+        groups: Vec::new(),
+        regs_read: Vec::new(),
+        regs_written: Vec::new(),
+        operands_detail: Vec::new(),
+    }
+}
+
+/// Converts one decoded instruction, adding detail and pseudo-code as requested.
+///
+/// `previous` holds the instructions decoded just before this one.
+fn build_result(
+    cs: &Capstone,
+    arch: Architecture,
+    insn: &capstone::Insn,
+    options: &DisasmOptions,
+    previous: &[DisassemblyResult],
+) -> DisassemblyResult {
+    let mnemonic = insn.mnemonic().unwrap_or("???");
+    let operands = insn.op_str().unwrap_or("");
+
+    let mut result = DisassemblyResult {
+        address: insn.address(),
+        id: insn.id().0,
+        bytes: insn.bytes().to_vec(),
+        mnemonic: mnemonic.to_string(),
+        operands: operands.to_string(),
+        pseudo_code: None,
+        groups: Vec::new(),
+        regs_read: Vec::new(),
+        regs_written: Vec::new(),
+        operands_detail: Vec::new(),
+    };
+
+    if options.pseudo {
+        let it_condition = if arch == Architecture::THUMB { pseudo::it_condition(previous) } else { None };
+        let mut pseudo = match it_condition {
+            Some(cond) => pseudo::predicated(&cond, mnemonic, |plain| generate_pseudo_code(arch, plain, operands, cs, insn)),
+            None => generate_pseudo_code(arch, mnemonic, operands, cs, insn),
+        };
+        if options.pseudo_source {
+            pseudo = pseudo::with_source_comment(pseudo, mnemonic, operands);
+        }
+        if arch == Architecture::ARM64 && let Some(slot) = pseudo::resolve_adrp_load(previous, mnemonic, operands) {
+            pseudo = format!("{} // [0x{:x}]", pseudo, slot);
+        }
+        if arch.has_delay_slots() && previous.last().is_some_and(|prev| pseudo::mips_has_delay_slot(&prev.mnemonic)) {
+            pseudo.push_str(" // delay slot");
+        }
+        result.pseudo_code = Some(pseudo);
+    }
+
+    // Skipdata `.byte` entries have no detail
+    if (options.detail || options.pseudo) && let Ok(detail) = cs.insn_detail(insn) {
+        result.groups = detail.groups().iter().filter_map(|&id| cs.group_name(id)).collect();
+        result.regs_read = detail.regs_read().iter().filter_map(|&id| cs.reg_name(id)).collect();
+        result.regs_written = detail.regs_write().iter().filter_map(|&id| cs.reg_name(id)).collect();
+        result.operands_detail = collect_operands(cs, &detail);
+    }
+
+    result
+}
+
+/// Returns true for unconditional branches and returns that end a basic block.
+fn is_block_terminator(arch: Architecture, mnemonic: &str, operands: &str) -> bool {
+    match arch {
+        Architecture::ARM64 => matches!(mnemonic, "b" | "br" | "ret"),
+        Architecture::ARM32 | Architecture::THUMB => {
+            matches!(mnemonic, "b" | "b.w" | "bx")
+                || (matches!(mnemonic, "pop" | "pop.w" | "ldm" | "ldmia" | "ldmia.w") && operands.contains("pc"))
+        },
+        Architecture::X86 | Architecture::X86_64 => matches!(mnemonic, "jmp" | "ret" | "retf"),
+        Architecture::MIPS | Architecture::MIPS64 => matches!(mnemonic, "b" | "j" | "jr"),
+    }
+}
+
+/// Converts Capstone detail operands into [`Operand`]s.
+///
+/// Operand kinds other than register, immediate, and memory (e.g. floating point or
+/// system registers) are skipped.
+fn collect_operands(cs: &Capstone, detail: &InsnDetail) -> Vec<Operand> {
+    let reg = |id: RegId| if id.0 == 0 { None } else { cs.reg_name(id) };
+
+    detail
+        .arch_detail()
+        .operands()
+        .into_iter()
+        .filter_map(|op| match op {
+            ArchOperand::Arm64Operand(op) => match op.op_type {
+                arch::arm64::Arm64OperandType::Reg(id) => reg(id).map(Operand::Reg),
+                arch::arm64::Arm64OperandType::Imm(imm) => Some(Operand::Imm(imm)),
+                arch::arm64::Arm64OperandType::Mem(mem) => Some(Operand::Mem {
+                    base: reg(mem.base()),
+                    index: reg(mem.index()),
+                    disp: mem.disp() as i64,
+                }),
+                _ => None,
+            },
+            ArchOperand::ArmOperand(op) => match op.op_type {
+                arch::arm::ArmOperandType::Reg(id) => reg(id).map(Operand::Reg),
+                arch::arm::ArmOperandType::Imm(imm) => Some(Operand::Imm(imm as i64)),
+                arch::arm::ArmOperandType::Mem(mem) => Some(Operand::Mem {
+                    base: reg(mem.base()),
+                    index: reg(mem.index()),
+                    disp: mem.disp() as i64,
+                }),
+                _ => None,
+            },
+            ArchOperand::MipsOperand(op) => match op {
+                arch::mips::MipsOperand::Reg(id) => reg(id).map(Operand::Reg),
+                arch::mips::MipsOperand::Imm(imm) => Some(Operand::Imm(imm)),
+                arch::mips::MipsOperand::Mem(mem) => Some(Operand::Mem {
+                    base: reg(mem.base()),
+                    index: None,
+                    disp: mem.disp(),
+                }),
+                _ => None,
+            },
+            ArchOperand::X86Operand(op) => match op.op_type {
+                arch::x86::X86OperandType::Reg(id) => reg(id).map(Operand::Reg),
+                arch::x86::X86OperandType::Imm(imm) => Some(Operand::Imm(imm)),
+                arch::x86::X86OperandType::Mem(mem) => Some(Operand::Mem {
+                    base: reg(mem.base()),
+                    index: reg(mem.index()),
+                    disp: mem.disp(),
+                }),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// This thread's cached Capstone instance for one architecture and byte order, taken out of
+/// [`CAPSTONE_CACHE`] for the duration of a stream and put back when dropped.
+///
+/// While the engine is taken, a nested disassembly on the same thread (e.g. from a stream
+/// callback) builds its own engine instead of re-borrowing the cache.
+struct CapstoneLease {
+    key: (Architecture, bool),
+    cs: Option<Capstone>,
+}
+
+impl CapstoneLease {
+    /// Takes the engine for `arch`, creating it on first use.
+    ///
+    /// Detail, skipdata, and syntax are set once per lease because the cached engine is shared
+    /// between all disassembly paths.
+    fn take(arch: Architecture, options: &DisasmOptions) -> Result<Self> {
+        #[cfg(test)]
+        CAPSTONE_CALLS.with(|calls| calls.set(calls.get() + 1));
+        let key = (arch, options.big_endian);
+        let cs = match CAPSTONE_CACHE.with(|cache| cache.borrow_mut().remove(&key)) {
+            Some(cs) => cs,
+            None => create_capstone(arch, options.big_endian)?,
+        };
+        let mut lease = Self { key, cs: Some(cs) };
+
+        let cs = lease.cs.as_mut().ok_or_else(|| anyhow!("Capstone engine already returned"))?;
+        cs.set_detail(options.detail || options.pseudo)?;
+        cs.set_skipdata(options.skipdata)?;
+        if let Some(syntax) = options.syntax.capstone_syntax(arch) {
+            cs.set_syntax(syntax)?;
+        }
+        Ok(lease)
+    }
+}
+
+impl std::ops::Deref for CapstoneLease {
+    type Target = Capstone;
+
+    fn deref(&self) -> &Capstone {
+        self.cs.as_ref().expect("Capstone engine is only returned on drop")
+    }
+}
+
+impl Drop for CapstoneLease {
+    fn drop(&mut self) {
+        if let Some(cs) = self.cs.take() {
+            // The thread-local is gone during thread teardown; the engine is simply dropped then
+            let _ = CAPSTONE_CACHE.try_with(|cache| cache.borrow_mut().insert(self.key, cs));
+        }
+    }
+}
+
+/// Creates a Capstone instance for the specified architecture and byte order.
+fn create_capstone(arch: Architecture, big_endian: bool) -> Result<Capstone> {
+    #[cfg(test)]
+    CAPSTONE_CREATED.with(|created| created.set(created.get() + 1));
+    let endian = if big_endian { capstone::Endian::Big } else { capstone::Endian::Little };
+    if big_endian && matches!(arch, Architecture::X86 | Architecture::X86_64) {
+        return Err(JniErrorCode::InvalidArgument.error(format!("Big-endian is not supported for {:?}", arch)));
+    }
+
     let cs = match arch {
         Architecture::ARM32 => {
             Capstone::new()
                 .arm()
                 .mode(arch::arm::ArchMode::Arm)
+                .endian(endian)
                 .build()
         }
         Architecture::THUMB => {
             Capstone::new()
                 .arm()
                 .mode(arch::arm::ArchMode::Thumb)
+                .endian(endian)
                 .build()
         }
         Architecture::ARM64 => {
             Capstone::new()
                 .arm64()
                 .mode(arch::arm64::ArchMode::Arm)
+                .endian(endian)
+                .build()
+        }
+        Architecture::X86 => {
+            Capstone::new()
+                .x86()
+                .mode(arch::x86::ArchMode::Mode32)
+                .build()
+        }
+        Architecture::X86_64 => {
+            Capstone::new()
+                .x86()
+                .mode(arch::x86::ArchMode::Mode64)
+                .build()
+        }
+        Architecture::MIPS => {
+            Capstone::new()
+                .mips()
+                .mode(arch::mips::ArchMode::Mips32)
+                .endian(endian)
+                .build()
+        }
+        Architecture::MIPS64 => {
+            Capstone::new()
+                .mips()
+                .mode(arch::mips::ArchMode::Mips64)
+                .endian(endian)
                 .build()
         }
     };
@@ -159,6 +817,557 @@ mod tests {
         assert_eq!(results[0].mnemonic, "mov");
     }
 
+    #[test]
+    fn test_disassemble_max_bytes() {
+        // mov x0, x1 ; nop ; nop ; ret
+        let bytes = vec![0xe0, 0x03, 0x01, 0xaa, 0x1f, 0x20, 0x03, 0xd5, 0x1f, 0x20, 0x03, 0xd5, 0xc0, 0x03, 0x5f, 0xd6];
+        let options = DisasmOptions { max_bytes: 8, ..Default::default() };
+        let results = disassemble_with(Architecture::ARM64, &bytes, 0x1000, 0, &options).unwrap();
+        assert_eq!(results.len(), 2);
+
+        // An instruction crossing the limit is not decoded, and the count still applies
+        let options = DisasmOptions { max_bytes: 10, ..Default::default() };
+        assert_eq!(disassemble_with(Architecture::ARM64, &bytes, 0x1000, 0, &options).unwrap().len(), 2);
+        assert_eq!(disassemble_with(Architecture::ARM64, &bytes, 0x1000, 1, &options).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_format_listing() {
+        // mov x0, x1 ; ret
+        let bytes = vec![0xe0, 0x03, 0x01, 0xaa, 0xc0, 0x03, 0x5f, 0xd6];
+        let results = disassemble(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(format_listing(&results), "0x1000: e0 03 01 aa  mov x0, x1\n0x1004: c0 03 5f d6  ret");
+
+        let results = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        let listing = format_listing(&results);
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines[0], "0x1000: e0 03 01 aa  mov x0, x1  ; x0 = x1");
+        assert!(lines[1].starts_with("0x1004: c0 03 5f d6  ret         ; "));
+        assert!(format_listing(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_empty_and_short_input() {
+        for arch in [Architecture::ARM32, Architecture::THUMB, Architecture::ARM64, Architecture::X86, Architecture::X86_64] {
+            assert!(disassemble(arch, &[], 0x1000, 0).unwrap().is_empty());
+            assert!(disassemble_with_pseudo(arch, &[], 0x1000, 0).unwrap().is_empty());
+            assert!(disassemble_skipdata(arch, &[], 0x1000, 0).unwrap().is_empty());
+            assert!(disassemble(arch, &[0x00], 0x1000, 0).unwrap().len() <= 1);
+            assert!(disassemble_with_pseudo(arch, &[0x00], 0x1000, 0).unwrap().len() <= 1);
+        }
+
+        // A trailing partial instruction is dropped, the complete one before it is kept
+        let results = disassemble(Architecture::ARM64, &[0xc0, 0x03, 0x5f, 0xd6, 0x1f, 0x20], 0x1000, 0).unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_unaligned_tail() {
+        // ret ; two bytes of a cut-off word
+        let bytes = [0xc0, 0x03, 0x5f, 0xd6, 0x1f, 0x20];
+        assert_eq!(unconsumed_tail(Architecture::ARM64, bytes.len()), 2);
+        assert_eq!(unconsumed_tail(Architecture::THUMB, 5), 1);
+        assert_eq!(unconsumed_tail(Architecture::X86_64, 5), 0);
+
+        // Skipdata reports the tail as data instead of dropping it
+        let results = disassemble_skipdata(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].address, 0x1004);
+        assert_eq!(results[1].mnemonic, ".byte");
+        assert_eq!(results[1].operands, "0x1f, 0x20");
+        assert_eq!(results[1].size(), 2);
+
+        let data = disassemble_skipdata(Architecture::ARM64, &[0xff, 0xff, 0xff, 0xff], 0x1000, 0).unwrap();
+        assert_eq!(data[0].operands, "0xff, 0xff, 0xff, 0xff");
+    }
+
+    #[test]
+    fn test_result_cache() {
+        let calls = || CAPSTONE_CALLS.with(|calls| calls.get());
+        // mov x0, x1 ; ret
+        let bytes = [0xe0, 0x03, 0x01, 0xaa, 0xc0, 0x03, 0x5f, 0xd6];
+        let options = DisasmOptions::default();
+
+        let first = disassemble_with(Architecture::ARM64, &bytes, 0x2000, 0, &options).unwrap();
+        let before = calls();
+        let second = disassemble_with(Architecture::ARM64, &bytes, 0x2000, 0, &options).unwrap();
+        assert_eq!(calls(), before);
+        assert_eq!(second.len(), first.len());
+        assert_eq!(second[1].mnemonic, "ret");
+
+        // Different bytes, address or options miss
+        let patched = [0x1f, 0x20, 0x03, 0xd5, 0xc0, 0x03, 0x5f, 0xd6];
+        let results = disassemble_with(Architecture::ARM64, &patched, 0x2000, 0, &options).unwrap();
+        assert_eq!(results[0].mnemonic, "nop");
+        let mut last = calls();
+        assert!(last > before);
+        disassemble_with(Architecture::ARM64, &bytes, 0x3000, 0, &options).unwrap();
+        assert!(calls() > last);
+        last = calls();
+        let pseudo = DisasmOptions { pseudo: true, ..options };
+        disassemble_with(Architecture::ARM64, &bytes, 0x2000, 0, &pseudo).unwrap();
+        assert!(calls() > last);
+    }
+
+    #[test]
+    fn test_stream_takes_capstone_once() {
+        let calls = || CAPSTONE_CALLS.with(|calls| calls.get());
+        // nop ; nop ; nop
+        let bytes = [0x1f, 0x20, 0x03, 0xd5].repeat(3);
+
+        let before = calls();
+        let mut nested = Vec::new();
+        let emitted = disassemble_stream(Architecture::ARM64, &bytes, 0x1000, 0, |result| {
+            // Re-entering from the callback while the outer stream holds the engine
+            nested.push(disassemble_stream(Architecture::ARM64, &result.bytes, result.address, 0, |_| true).unwrap());
+            true
+        })
+        .unwrap();
+        assert_eq!(emitted, 3);
+        assert_eq!(nested, vec![1, 1, 1]);
+        // One lease for the outer stream and one per nested call, not one per instruction
+        assert_eq!(calls() - before, 4);
+    }
+
+    #[test]
+    fn test_result_cache_bounds() {
+        let options = DisasmOptions::default();
+        let totals = || RESULT_CACHE.with(|cache| cache.borrow().totals());
+
+        // Inputs over the per-entry limit are neither hashed nor cached
+        assert!(cache::CacheKey::new(Architecture::ARM64, &vec![0u8; cache::MAX_CACHED_BYTES + 4], 0, 0, &options).is_none());
+
+        // Windows of nops at distinct addresses: the byte budget evicts the oldest entries
+        let nops: Vec<u8> = [0x1f, 0x20, 0x03, 0xd5].repeat(cache::MAX_CACHED_BYTES / 4);
+        for i in 0..8u64 {
+            disassemble_with(Architecture::ARM64, &nops[..cache::MAX_CACHED_BYTES / 2], 0x10_0000 * i, 0, &options).unwrap();
+            let (entries, bytes, instructions) = totals();
+            assert!(entries <= cache::CACHE_CAPACITY);
+            assert!(bytes <= cache::MAX_TOTAL_BYTES);
+            assert!(instructions <= cache::MAX_TOTAL_INSTRUCTIONS);
+        }
+
+        // The most recent window is still cached, the first one was evicted
+        let calls = || CAPSTONE_CALLS.with(|calls| calls.get());
+        let before = calls();
+        disassemble_with(Architecture::ARM64, &nops[..cache::MAX_CACHED_BYTES / 2], 0x10_0000 * 7, 0, &options).unwrap();
+        assert_eq!(calls(), before);
+        disassemble_with(Architecture::ARM64, &nops[..cache::MAX_CACHED_BYTES / 2], 0, 0, &options).unwrap();
+        assert!(calls() > before);
+    }
+
+    #[test]
+    fn test_instruction_id_and_size() {
+        // mov x0, x1 ; ret ; data word
+        let bytes = vec![0xe0, 0x03, 0x01, 0xaa, 0xc0, 0x03, 0x5f, 0xd6, 0xff, 0xff, 0xff, 0xff];
+        let results = disassemble_skipdata(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+
+        assert_eq!(results[0].id, capstone::arch::arm64::Arm64Insn::ARM64_INS_MOV as u32);
+        assert_eq!(results[1].id, capstone::arch::arm64::Arm64Insn::ARM64_INS_RET as u32);
+        assert_eq!(results[1].size(), 4);
+        assert_eq!(results[2].mnemonic, ".byte");
+        assert_eq!(results[2].id, 0);
+    }
+
+    #[test]
+    fn test_arm64_adrp_ldr_got_slot() {
+        // adrp x0, #0x2000 ; ldr x0, [x0, #0x10]
+        let bytes = vec![0x00, 0x00, 0x00, 0xb0, 0x00, 0x08, 0x40, 0xf9];
+        let results = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].mnemonic, "adrp");
+        assert_eq!(results[1].mnemonic, "ldr");
+        assert!(results[1].pseudo_code.as_deref().unwrap().ends_with("// [0x2010]"));
+    }
+
+    #[test]
+    fn test_arm64_adrp_ldr_lookback_stops_at_clobber() {
+        // adrp x0, #0x2000 ; mov w0, #1 ; ldr x0, [x0, #0x10]
+        let bytes = [0x00, 0x00, 0x00, 0xb0, 0x20, 0x00, 0x80, 0x52, 0x00, 0x08, 0x40, 0xf9];
+        let results = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(results[1].mnemonic, "mov");
+        assert!(!results[2].pseudo_code.as_deref().unwrap().contains("// ["));
+
+        // adrp x0, #0x2000 ; bl #0x1008 ; ldr x0, [x0, #0x10]
+        let bytes = [0x00, 0x00, 0x00, 0xb0, 0x01, 0x00, 0x00, 0x94, 0x00, 0x08, 0x40, 0xf9];
+        let results = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(results[1].mnemonic, "bl");
+        assert!(!results[2].pseudo_code.as_deref().unwrap().contains("// ["));
+    }
+
+    #[test]
+    fn test_pseudo_source_comment() {
+        // mov x0, x1 ; ret
+        let bytes = [0xe0, 0x03, 0x01, 0xaa, 0xc0, 0x03, 0x5f, 0xd6];
+        let plain = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(plain[0].pseudo_code.as_deref(), Some("x0 = x1"));
+
+        let results = disassemble_with_pseudo_source(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(results[0].pseudo_code.as_deref(), Some("x0 = x1  // mov x0, x1"));
+        assert!(results[1].pseudo_code.as_deref().unwrap().ends_with("  // ret"));
+
+        // x86 pseudo-code is already the raw instruction
+        let results = disassemble_with_pseudo_source(Architecture::X86_64, &[0x48, 0x89, 0xd8], 0x1000, 0).unwrap();
+        assert_eq!(results[0].pseudo_code.as_deref(), Some("mov rax, rbx"));
+    }
+
+    #[test]
+    fn test_mips_disassemble() {
+        // addiu $sp, $sp, -0x20 ; addiu $a0, $zero, 1 ; jr $ra ; nop ; addiu $v0, $zero, 0
+        let bytes = [
+            0xe0, 0xff, 0xbd, 0x27, 0x01, 0x00, 0x04, 0x24, 0x08, 0x00, 0xe0, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x02, 0x24,
+        ];
+        let results = disassemble_with_pseudo(Architecture::MIPS, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0].mnemonic, "addiu");
+        assert_eq!(results[0].operands, "$sp, $sp, -0x20");
+        assert_eq!(results[1].operands, "$a0, $zero, 1");
+        assert_eq!(results[2].mnemonic, "jr");
+        assert!(results[3].pseudo_code.as_deref().unwrap().ends_with("// delay slot"));
+        assert!(!results[4].pseudo_code.as_deref().unwrap().contains("delay slot"));
+        assert_eq!(generate_pseudo_block(Architecture::MIPS, &results)[3], results[3].pseudo_code.clone().unwrap());
+
+        // The block ends after the delay slot, not at the jump
+        let block = disassemble_until_branch(Architecture::MIPS, &bytes, 0x1000).unwrap();
+        assert_eq!(block.len(), 4);
+
+        // daddiu $sp, $sp, -0x20
+        let results = disassemble(Architecture::MIPS64, &[0xe0, 0xff, 0xbd, 0x67], 0x1000, 0).unwrap();
+        assert_eq!(results[0].mnemonic, "daddiu");
+        assert_eq!(Architecture::from_i32(6).unwrap(), Architecture::MIPS64);
+    }
+
+    #[test]
+    fn test_arm64_disassemble_until_branch() {
+        // mov x0, x1 ; ret ; nop
+        let bytes = vec![0xe0, 0x03, 0x01, 0xaa, 0xc0, 0x03, 0x5f, 0xd6, 0x1f, 0x20, 0x03, 0xd5];
+        let results = disassemble_until_branch(Architecture::ARM64, &bytes, 0x1000).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].mnemonic, "ret");
+
+        // bl does not terminate, b does: bl ; b ; nop
+        let bytes = vec![0x40, 0x00, 0x00, 0x94, 0x02, 0x00, 0x00, 0x14, 0x1f, 0x20, 0x03, 0xd5];
+        let results = disassemble_until_branch(Architecture::ARM64, &bytes, 0x1000).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].mnemonic, "b");
+
+        // Without a terminator, stop when the bytes run out
+        let results = disassemble_until_branch(Architecture::ARM64, &bytes[8..], 0x1008).unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_arm64_disassemble_stream() {
+        // mov x0, x1 ; nop ; ret
+        let bytes = vec![0xe0, 0x03, 0x01, 0xaa, 0x1f, 0x20, 0x03, 0xd5, 0xc0, 0x03, 0x5f, 0xd6];
+
+        let mut addresses = Vec::new();
+        let emitted = disassemble_stream(Architecture::ARM64, &bytes, 0x1000, 0, |insn| {
+            addresses.push(insn.address);
+            true
+        })
+        .unwrap();
+        assert_eq!(emitted, 3);
+        assert_eq!(addresses, vec![0x1000, 0x1004, 0x1008]);
+
+        // Returning false stops after the current instruction
+        let mut seen = 0;
+        let emitted = disassemble_stream(Architecture::ARM64, &bytes, 0x1000, 0, |_| {
+            seen += 1;
+            false
+        })
+        .unwrap();
+        assert_eq!((emitted, seen), (1, 1));
+
+        assert_eq!(disassemble_stream(Architecture::ARM64, &bytes, 0x1000, 2, |_| true).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_disassemble_stream_callback_may_disassemble() {
+        // mov x0, x1 ; nop
+        let bytes = vec![0xe0, 0x03, 0x01, 0xaa, 0x1f, 0x20, 0x03, 0xd5];
+        let emitted = disassemble_stream(Architecture::ARM64, &bytes, 0x1000, 0, |insn| {
+            let again = disassemble(Architecture::ARM64, &insn.bytes, insn.address, 1).unwrap();
+            assert_eq!(again[0].mnemonic, insn.mnemonic);
+            true
+        })
+        .unwrap();
+        assert_eq!(emitted, 2);
+    }
+
+    #[test]
+    fn test_arm32_noregname_syntax() {
+        // mov fp, sp ; str lr, [sp, #-4]!
+        let bytes = [0x0d, 0xb0, 0xa0, 0xe1, 0x04, 0xe0, 0x2d, 0xe5];
+        let options = DisasmOptions { syntax: DisasmSyntax::NoRegName, ..Default::default() };
+
+        let plain = disassemble(Architecture::ARM32, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(plain[0].operands, "fp, sp");
+
+        let numbered = disassemble_with(Architecture::ARM32, &bytes, 0x1000, 0, &options).unwrap();
+        assert_eq!(numbered[0].operands, "r11, r13");
+        assert_eq!(numbered[1].operands, "r14, [r13, #-4]!");
+
+        let pseudo = disassemble_with(Architecture::ARM32, &bytes, 0x1000, 0, &DisasmOptions { pseudo: true, ..options }).unwrap();
+        assert_eq!(pseudo[0].pseudo_code.as_deref(), Some("r11 = r13"));
+
+        // The cached engine goes back to the default syntax afterwards
+        assert_eq!(disassemble(Architecture::ARM32, &bytes, 0x1000, 0).unwrap()[0].operands, "fp, sp");
+    }
+
+    #[test]
+    fn test_x86_att_syntax() {
+        // mov eax, 1
+        let bytes = [0xb8, 0x01, 0x00, 0x00, 0x00];
+        let options = DisasmOptions { syntax: DisasmSyntax::Att, ..Default::default() };
+        assert_eq!(disassemble(Architecture::X86, &bytes, 0x1000, 0).unwrap()[0].operands, "eax, 1");
+        assert_eq!(disassemble_with(Architecture::X86, &bytes, 0x1000, 0, &options).unwrap()[0].operands, "$1, %eax");
+        // Syntaxes for other architectures are ignored
+        let options = DisasmOptions { syntax: DisasmSyntax::NoRegName, ..Default::default() };
+        assert_eq!(disassemble_with(Architecture::X86, &bytes, 0x1000, 0, &options).unwrap()[0].operands, "eax, 1");
+    }
+
+    #[test]
+    fn test_thumb_disassemble_backward() {
+        // push {r4, lr} ; movs r0, #1 ; ldr.w r1, [r2, #4] ; adds r0, r0, r1 ; pop {r4, pc}
+        let bytes = [0x10, 0xb5, 0x01, 0x20, 0xd2, 0xf8, 0x04, 0x10, 0x40, 0x18, 0x10, 0xbd];
+
+        let results = disassemble_backward(Architecture::THUMB, &bytes, 0x1000, 0x1008, 2).unwrap();
+        let mnemonics: Vec<&str> = results.iter().map(|r| r.mnemonic.as_str()).collect();
+        assert_eq!(mnemonics, vec!["movs", "ldr.w"]);
+        assert_eq!(results[1].address, 0x1004);
+
+        // A target inside the 4-byte ldr.w stops before it
+        let results = disassemble_backward(Architecture::THUMB, &bytes, 0x1000, 0x1006, 8).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].mnemonic, "movs");
+
+        assert!(disassemble_backward(Architecture::THUMB, &bytes, 0x1000, 0x1000, 4).unwrap().is_empty());
+        assert!(disassemble_backward(Architecture::THUMB, &bytes, 0x1000, 0xff0, 4).is_err());
+    }
+
+    #[test]
+    fn test_thumb_disassemble_until_branch() {
+        // push {r7, lr} ; pop {r7, pc} ; nop
+        let bytes = vec![0x80, 0xb5, 0x80, 0xbd, 0x00, 0xbf];
+        let results = disassemble_until_branch(Architecture::THUMB, &bytes, 0x1000).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].mnemonic, "pop");
+    }
+
+    #[test]
+    fn test_capstone_cache_reuses_engine() {
+        let created = || CAPSTONE_CREATED.with(|created| created.get());
+        // mov x0, x1
+        let bytes = [0xe0, 0x03, 0x01, 0xaa];
+
+        disassemble(Architecture::ARM64, &bytes, 0x1000, 1).unwrap();
+        let before = created();
+        // Distinct addresses miss the result cache, so each call decodes with Capstone
+        for i in 0..16u64 {
+            assert_eq!(disassemble(Architecture::ARM64, &bytes, 0x1000 + i * 4, 1).unwrap().len(), 1);
+        }
+        assert_eq!(created(), before);
+
+        // Another byte order is a separate engine
+        disassemble_endian(Architecture::ARM64, &bytes, 0x1000, 1, true).unwrap();
+        assert_eq!(created(), before + 1);
+    }
+
+    #[test]
+    fn test_capstone_cache_detail_reset() {
+        let created = || CAPSTONE_CREATED.with(|created| created.get());
+        // bl #0x1100
+        let bytes = [0x40, 0x00, 0x00, 0x94];
+        let detail = DisasmOptions { detail: true, ..Default::default() };
+
+        {
+            let cs = CapstoneLease::take(Architecture::ARM64, &detail).unwrap();
+            let insns = cs.disasm_count(&bytes, 0x1000, 1).unwrap();
+            assert!(cs.insn_detail(insns.iter().next().unwrap()).is_ok());
+        }
+        let before = created();
+        {
+            // The same cached engine, with detail mode switched off again
+            let cs = CapstoneLease::take(Architecture::ARM64, &DisasmOptions::default()).unwrap();
+            let insns = cs.disasm_count(&bytes, 0x1000, 1).unwrap();
+            assert!(cs.insn_detail(insns.iter().next().unwrap()).is_err());
+        }
+        assert_eq!(created(), before);
+
+        let detailed = disassemble_with(Architecture::ARM64, &bytes, 0x1000, 0, &detail).unwrap();
+        assert!(!detailed[0].groups.is_empty());
+        let plain = disassemble(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert!(plain[0].groups.is_empty());
+        assert!(plain[0].operands_detail.is_empty());
+        assert_eq!(plain[0].operands, detailed[0].operands);
+        assert_eq!(created(), before);
+    }
+
+    #[test]
+    fn test_arm64_big_endian() {
+        // nop encoded big-endian
+        let bytes = [0xd5, 0x03, 0x20, 0x1f];
+        let big = disassemble_endian(Architecture::ARM64, &bytes, 0x1000, 0, true).unwrap();
+        assert_eq!(big.len(), 1);
+        assert_eq!(big[0].mnemonic, "nop");
+
+        let little = disassemble_endian(Architecture::ARM64, &bytes, 0x1000, 0, false).unwrap();
+        assert!(little.is_empty() || little[0].mnemonic != "nop");
+    }
+
+    #[test]
+    fn test_arm32_big_endian() {
+        // mov r0, r1 encoded big-endian
+        let bytes = [0xe1, 0xa0, 0x00, 0x01];
+        let big = disassemble_endian(Architecture::ARM32, &bytes, 0x1000, 0, true).unwrap();
+        assert_eq!(big[0].mnemonic, "mov");
+        assert_eq!(big[0].operands, "r0, r1");
+
+        let little = disassemble(Architecture::ARM32, &bytes, 0x1000, 0).unwrap();
+        assert!(little.is_empty() || little[0].mnemonic != "mov");
+    }
+
+    #[test]
+    fn test_arm64_skipdata() {
+        // nop ; <invalid> ; ret
+        let bytes = [0x1f, 0x20, 0x03, 0xd5, 0xff, 0xff, 0xff, 0xff, 0xc0, 0x03, 0x5f, 0xd6];
+
+        let plain = disassemble(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(plain.len(), 1);
+
+        let results = disassemble_skipdata(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        let mnemonics: Vec<&str> = results.iter().map(|r| r.mnemonic.as_str()).collect();
+        assert_eq!(mnemonics, vec!["nop", ".byte", "ret"]);
+        assert_eq!(results[1].address, 0x1004);
+        assert_eq!(results[2].address, 0x1008);
+
+        // Skipdata must not leak into later calls on the cached engine.
+        assert_eq!(disassemble(Architecture::ARM64, &bytes, 0x1000, 0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_arm64_disassemble_with_combined_options() {
+        // nop ; <invalid> ; ret ; nop
+        let bytes = [
+            0x1f, 0x20, 0x03, 0xd5, 0xff, 0xff, 0xff, 0xff, 0xc0, 0x03, 0x5f, 0xd6, 0x1f, 0x20, 0x03, 0xd5,
+        ];
+        let options = DisasmOptions { detail: true, skipdata: true, stop_at_branch: true, ..Default::default() };
+
+        let results = disassemble_with(Architecture::ARM64, &bytes, 0x1000, 0, &options).unwrap();
+        let mnemonics: Vec<&str> = results.iter().map(|r| r.mnemonic.as_str()).collect();
+        assert_eq!(mnemonics, vec!["nop", ".byte", "ret"]);
+        assert!(results[2].groups.iter().any(|g| g == "return"));
+        assert!(results.iter().all(|r| r.pseudo_code.is_none()));
+
+        let plain = disassemble_with(Architecture::ARM64, &bytes, 0x1000, 0, &DisasmOptions::default()).unwrap();
+        assert_eq!(plain.len(), 1);
+        assert!(plain[0].groups.is_empty());
+    }
+
+    #[test]
+    fn test_operands_detail() {
+        // ldr x0, [x1, #8] ; mov x2, #0x10
+        let bytes = [0x20, 0x04, 0x40, 0xf9, 0x02, 0x02, 0x80, 0xd2];
+        let results = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+
+        assert_eq!(
+            results[0].operands_detail,
+            vec![
+                Operand::Reg("x0".to_string()),
+                Operand::Mem { base: Some("x1".to_string()), index: None, disp: 8 },
+            ]
+        );
+        assert_eq!(results[1].operands_detail, vec![Operand::Reg("x2".to_string()), Operand::Imm(0x10)]);
+        assert_eq!(results[0].operands, "x0, [x1, #8]");
+
+        let plain = disassemble(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert!(plain[0].operands_detail.is_empty());
+    }
+
+    #[test]
+    fn test_x86_64_operands_detail() {
+        // mov rax, qword ptr [rbx + rcx*8 + 0x10]
+        let bytes = [0x48, 0x8b, 0x44, 0xcb, 0x10];
+        let results = disassemble_with_pseudo(Architecture::X86_64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(
+            results[0].operands_detail,
+            vec![
+                Operand::Reg("rax".to_string()),
+                Operand::Mem { base: Some("rbx".to_string()), index: Some("rcx".to_string()), disp: 0x10 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_x86_big_endian_rejected() {
+        assert!(disassemble_endian(Architecture::X86, &[0x90], 0, 0, true).is_err());
+    }
+
+    #[test]
+    fn test_arm64_branch_target_absolute() {
+        // bl #0x400b00 ; b.eq #0x400aac
+        let bytes = vec![0x40, 0x00, 0x00, 0x94, 0x40, 0x05, 0x00, 0x54];
+        let results = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x400a00, 0).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].pseudo_code.as_deref(), Some("call 0x400b00"));
+        assert_eq!(results[1].pseudo_code.as_deref(), Some("if (equal) goto 0x400aac"));
+    }
+
+    #[test]
+    fn test_arm64_compare_branch_target_absolute() {
+        // cbz x0, #0x1010 ; tbnz w0, #3, #0x1014
+        let bytes = vec![0x80, 0x00, 0x00, 0xb4, 0x80, 0x00, 0x18, 0x37];
+        let results = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].pseudo_code.as_deref(), Some("if (x0 == 0) goto 0x1010"));
+        assert_eq!(results[1].pseudo_code.as_deref(), Some("if ((w0 & (1<<3)) != 0) goto 0x1014"));
+    }
+
+    #[test]
+    fn test_arm64_detail_groups_and_regs() {
+        // bl #0x1100 ; ret
+        let bytes = vec![0x40, 0x00, 0x00, 0x94, 0xc0, 0x03, 0x5f, 0xd6];
+        let results = disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].groups.iter().any(|g| g == "call"));
+        assert!(results[0].regs_written.iter().any(|r| r == "lr" || r == "x30"));
+        assert!(results[1].groups.iter().any(|g| g == "return"));
+
+        let plain = disassemble(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert!(plain[0].groups.is_empty() && plain[0].regs_read.is_empty() && plain[0].regs_written.is_empty());
+    }
+
+    #[test]
+    fn test_x86_disassemble() {
+        // mov eax, 0x1234 ; ret
+        let bytes = vec![0xb8, 0x34, 0x12, 0x00, 0x00, 0xc3];
+        let results = disassemble(Architecture::X86, &bytes, 0x1000, 0).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].mnemonic, "mov");
+        assert_eq!(results[0].operands, "eax, 0x1234");
+        assert_eq!(results[1].address, 0x1005);
+        assert_eq!(results[1].mnemonic, "ret");
+    }
+
+    #[test]
+    fn test_x86_64_disassemble() {
+        // mov eax, 0x1234 ; mov rax, rbx
+        let bytes = vec![0xb8, 0x34, 0x12, 0x00, 0x00, 0x48, 0x89, 0xd8];
+        let results = disassemble_with_pseudo(Architecture::X86_64, &bytes, 0x1000, 0).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].operands, "eax, 0x1234");
+        assert_eq!(results[1].operands, "rax, rbx");
+        assert_eq!(results[1].pseudo_code.as_deref(), Some("mov rax, rbx"));
+    }
+
     #[test]
     fn test_thumb_disassemble() {
         // movs r0, #42
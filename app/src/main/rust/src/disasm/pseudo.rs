@@ -1,8 +1,10 @@
-//! Pseudo-code generation for ARM instructions.
+//! Pseudo-code generation for ARM and x86 instructions.
 //!
 //! Generates simplified, high-level representations of ARM assembly instructions.
 
-use super::Architecture;
+use super::{Architecture, DisassemblyResult};
+use std::collections::{HashMap, HashSet};
+use capstone::arch::ArchOperand;
 use capstone::Insn;
 use capstone::prelude::*;
 
@@ -14,17 +16,450 @@ pub fn generate_pseudo_code(
     arch: Architecture,
     mnemonic: &str,
     operands: &str,
-    _cs: &Capstone,
-    _insn: &Insn,
+    cs: &Capstone,
+    insn: &Insn,
 ) -> String {
     match arch {
-        Architecture::ARM64 => generate_arm64_pseudo(mnemonic, operands),
-        Architecture::ARM32 | Architecture::THUMB => generate_arm32_pseudo(mnemonic, operands),
+        Architecture::ARM64 => match arm64_branch_target(cs, insn, mnemonic) {
+            Some(target) => generate_arm64_pseudo(mnemonic, &with_branch_target(operands, target)),
+            None => generate_arm64_pseudo(mnemonic, operands),
+        },
+        Architecture::ARM32 | Architecture::THUMB => {
+            let thumb = arch == Architecture::THUMB;
+            match arm32_branch_target(cs, insn, mnemonic) {
+                Some(target) => generate_arm32_pseudo(thumb, mnemonic, &format!("0x{:x}", target)),
+                None => generate_arm32_pseudo(thumb, mnemonic, operands),
+            }
+        },
+        // x86 and MIPS operand order and mnemonics differ from ARM, so keep the raw instruction.
+        Architecture::X86 | Architecture::X86_64 | Architecture::MIPS | Architecture::MIPS64 => {
+            format!("{} {}", mnemonic, operands)
+        },
+    }
+}
+
+/// Returns true for MIPS branches and jumps that execute the following instruction (the delay
+/// slot) before transferring control. Release 6 compact branches (`bc`, `beqzc`, ...) have none.
+pub(super) fn mips_has_delay_slot(mnemonic: &str) -> bool {
+    match mnemonic {
+        "j" | "jal" | "jalx" | "jr" | "jalr" | "jr.hb" | "jalr.hb" => true,
+        "break" | "bitswap" => false,
+        m => m.starts_with('b') && !m.ends_with('c'),
+    }
+}
+
+/// Appends the original instruction to `pseudo` as a `// mnemonic operands` comment.
+///
+/// Pseudo-code that is already the raw instruction (x86) is returned unchanged.
+pub(super) fn with_source_comment(pseudo: String, mnemonic: &str, operands: &str) -> String {
+    let source = if operands.is_empty() { mnemonic.to_string() } else { format!("{} {}", mnemonic, operands) };
+    if pseudo == source {
+        return pseudo;
+    }
+    format!("{}  // {}", pseudo, source)
+}
+
+/// Returns the absolute target of an ARM64 direct branch (`b`, `bl`, `b.cond`, `cbz`, `cbnz`,
+/// `tbz`, `tbnz`).
+///
+/// Capstone resolves the PC-relative offset against the instruction address, so the
+/// immediate operand in the detail is already the absolute target. It is the last immediate,
+/// after the bit number of `tbz`/`tbnz`. Requires detail mode.
+fn arm64_branch_target(cs: &Capstone, insn: &Insn, mnemonic: &str) -> Option<u64> {
+    if !matches!(mnemonic, "b" | "bl" | "cbz" | "cbnz" | "tbz" | "tbnz") && !mnemonic.starts_with("b.") {
+        return None;
+    }
+    let detail = cs.insn_detail(insn).ok()?;
+    detail.arch_detail().operands().into_iter().rev().find_map(|op| match op {
+        ArchOperand::Arm64Operand(op) => match op.op_type {
+            arch::arm64::Arm64OperandType::Imm(imm) => Some(imm as u64),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Replaces the last operand (the branch target) with the resolved absolute address.
+fn with_branch_target(operands: &str, target: u64) -> String {
+    match operands.rsplit_once(',') {
+        Some((head, _)) => format!("{}, 0x{:x}", head, target),
+        None => format!("0x{:x}", target),
+    }
+}
+
+/// Returns the absolute target of an ARM32/THUMB immediate branch (`b`, `bl`, `blx`).
+///
+/// As on ARM64, Capstone reports the resolved target in the detail; for THUMB `blx` it is
+/// already aligned to 4 bytes as the ARM callee requires. Requires detail mode.
+fn arm32_branch_target(cs: &Capstone, insn: &Insn, mnemonic: &str) -> Option<u64> {
+    if !matches!(mnemonic, "b" | "bl" | "blx") {
+        return None;
+    }
+    let detail = cs.insn_detail(insn).ok()?;
+    detail.arch_detail().operands().into_iter().find_map(|op| match op {
+        ArchOperand::ArmOperand(op) => match op.op_type {
+            arch::arm::ArmOperandType::Imm(imm) => Some(imm as u32 as u64),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Maximum number of instructions a Thumb-2 IT block makes conditional.
+const IT_BLOCK_MAX: usize = 4;
+
+/// Returns the conditions an `it`/`itt`/`ite`/... instruction applies to the instructions
+/// after it, e.g. `ite eq` gives `["eq", "ne"]`.
+fn it_block_conditions(mnemonic: &str, operands: &str) -> Option<Vec<String>> {
+    let pattern = mnemonic.strip_prefix('i')?;
+    if !pattern.starts_with('t') || pattern.len() > IT_BLOCK_MAX || !pattern.chars().all(|c| c == 't' || c == 'e') {
+        return None;
+    }
+    let cond = operands.trim();
+    pattern
+        .chars()
+        .map(|c| if c == 't' { Some(cond.to_string()) } else { inverse_condition(cond).map(str::to_string) })
+        .collect()
+}
+
+/// Returns the condition of the Thumb instruction following `previous` if it lies inside an
+/// IT block, or `None` if it executes unconditionally.
+pub(super) fn it_condition(previous: &[DisassemblyResult]) -> Option<String> {
+    for (distance, insn) in previous.iter().rev().take(IT_BLOCK_MAX).enumerate() {
+        if let Some(conditions) = it_block_conditions(&insn.mnemonic, &insn.operands) {
+            return conditions.into_iter().nth(distance);
+        }
+    }
+    None
+}
+
+/// Pseudo-code for an instruction made conditional by an IT block: Capstone appends the
+/// condition to the mnemonic (`moveq`), so it is removed before `body` generates the plain
+/// form, which is then wrapped as `if (cond) { ... }`.
+pub(super) fn predicated(cond: &str, mnemonic: &str, body: impl FnOnce(&str) -> String) -> String {
+    let (base, width) = mnemonic.split_at(mnemonic.find('.').unwrap_or(mnemonic.len()));
+    let plain = match base.strip_suffix(cond) {
+        Some(base) if !base.is_empty() => format!("{}{}", base, width),
+        _ => mnemonic.to_string(),
+    };
+    format!("if ({}) {{ {} }}", condition_name(cond), body(&plain))
+}
+
+/// Maximum number of preceding instructions searched for the `adrp` that feeds a load.
+pub(super) const ADRP_LOOKBACK: usize = 8;
+
+/// Resolves the GOT slot address of an ARM64 `adrp` + `ldr` pair.
+///
+/// `previous` holds the instructions already decoded before the current one. The
+/// lookback stops as soon as the base register is overwritten by anything other
+/// than the matching `adrp`, including its `w` form, a writeback access or a call.
+pub fn resolve_adrp_load(previous: &[DisassemblyResult], mnemonic: &str, operands: &str) -> Option<u64> {
+    if mnemonic != "ldr" {
+        return None;
+    }
+
+    let (base, offset) = parse_mem_operand(operands)?;
+    let (base_reg, _) = arm64_reg(base)?;
+
+    for insn in previous.iter().rev().take(ADRP_LOOKBACK) {
+        if matches!(insn.mnemonic.as_str(), "bl" | "blr")
+            || arm64_base_update(&insn.operands).is_some_and(|(reg, _)| reg == base_reg)
+        {
+            return None;
+        }
+        let ops = split_operands(&insn.operands);
+        let dest_count = if insn.mnemonic == "ldp" { 2 } else { 1 };
+        let writes_base = ops.iter().take(dest_count).any(|op| arm64_reg(op).is_some_and(|(reg, _)| reg == base_reg));
+        if !writes_base {
+            continue;
+        }
+        if insn.mnemonic == "adrp" {
+            let page = parse_imm(ops.get(1)?)?;
+            return Some(page.wrapping_add(offset));
+        }
+        if !insn.mnemonic.starts_with("st") && !matches!(insn.mnemonic.as_str(), "cmp" | "cmn" | "tst") {
+            return None;
+        }
+    }
+
+    None
+}
+
+/// Generates pseudo-code for a window of instructions with constant propagation.
+///
+/// Immediate values moved into ARM64 registers are tracked and substituted into later
+/// instructions that read them. Tracking is conservative: a register is forgotten as soon
+/// as it is loaded from memory or written by anything that cannot be folded, and every
+/// register is forgotten after a call. An `add` of an immediate to a page loaded by `adrp`
+/// is folded into the absolute address. Other architectures fall back to per-instruction output.
+pub fn generate_pseudo_block(arch: Architecture, instructions: &[DisassemblyResult]) -> Vec<String> {
+    match arch {
+        Architecture::ARM64 => {
+            let mut known: HashMap<u32, u64> = HashMap::new();
+            let mut pages: HashSet<u32> = HashSet::new();
+            instructions
+                .iter()
+                .map(|insn| propagate_arm64(&mut known, &mut pages, &insn.mnemonic, &insn.operands))
+                .collect()
+        },
+        Architecture::ARM32 | Architecture::THUMB => {
+            let thumb = arch == Architecture::THUMB;
+            instructions
+                .iter()
+                .enumerate()
+                .map(|(i, insn)| {
+                    let generate = |mnemonic: &str| generate_arm32_pseudo(thumb, mnemonic, &insn.operands);
+                    match it_condition(&instructions[..i]).filter(|_| thumb) {
+                        Some(cond) => predicated(&cond, &insn.mnemonic, generate),
+                        None => generate(&insn.mnemonic),
+                    }
+                })
+                .collect()
+        },
+        Architecture::X86 | Architecture::X86_64 => {
+            instructions.iter().map(|insn| format!("{} {}", insn.mnemonic, insn.operands)).collect()
+        },
+        Architecture::MIPS | Architecture::MIPS64 => {
+            let mut in_delay_slot = false;
+            instructions
+                .iter()
+                .map(|insn| {
+                    let mut pseudo = format!("{} {}", insn.mnemonic, insn.operands);
+                    if in_delay_slot {
+                        pseudo.push_str(" // delay slot");
+                    }
+                    in_delay_slot = mips_has_delay_slot(&insn.mnemonic);
+                    pseudo
+                })
+                .collect()
+        },
+    }
+}
+
+/// Generates pseudo-code for one ARM64 instruction and updates the known register values.
+///
+/// `pages` holds the registers whose known value is an `adrp` page base.
+fn propagate_arm64(known: &mut HashMap<u32, u64>, pages: &mut HashSet<u32>, mnemonic: &str, operands: &str) -> String {
+    let ops = split_operands(operands);
+    let dest_count = match mnemonic {
+        "ldp" => 2,
+        "str" | "strb" | "strh" | "stur" | "stp" | "cmp" | "cmn" | "tst" | "cbz" | "cbnz" | "tbz" | "tbnz" | "ret" => 0,
+        _ if mnemonic.starts_with('b') && !mnemonic.starts_with("bic") && !mnemonic.starts_with("bf") => 0,
+        _ => 1,
+    };
+
+    // Substitute known values into source register operands (memory operands are left alone).
+    let substituted: Vec<String> = ops
+        .iter()
+        .enumerate()
+        .map(|(i, op)| match arm64_reg(op) {
+            Some((reg, _)) if i >= dest_count => known.get(&reg).map_or_else(|| op.to_string(), |v| format_imm(*v)),
+            _ => op.to_string(),
+        })
+        .collect();
+    let mut pseudo = generate_arm64_pseudo(mnemonic, &substituted.join(", "));
+
+    if matches!(mnemonic, "bl" | "blr") {
+        known.clear();
+        pages.clear();
+        return pseudo;
+    }
+
+    let value = match (mnemonic, substituted.as_slice()) {
+        ("mov" | "movz" | "adrp" | "adr", [_, src]) => parse_imm(src),
+        ("add", [_, a, b]) => parse_imm(a).zip(parse_imm(b)).map(|(a, b)| a.wrapping_add(b)),
+        ("sub", [_, a, b]) => parse_imm(a).zip(parse_imm(b)).map(|(a, b)| a.wrapping_sub(b)),
+        _ => None,
+    };
+
+    // page + page offset: show the resulting address instead of the sum
+    if mnemonic == "add"
+        && let (Some(v), [dest, base, _]) = (value, ops.as_slice())
+        && arm64_reg(base).is_some_and(|(reg, _)| pages.contains(&reg))
+    {
+        pseudo = format!("{} = 0x{:x}", dest, v);
+    }
+
+    for op in ops.iter().take(dest_count) {
+        if let Some((reg, is_w)) = arm64_reg(op) {
+            match value {
+                // Writes to a w register zero-extend into the full x register.
+                Some(v) if dest_count == 1 => known.insert(reg, if is_w { v & 0xffff_ffff } else { v }),
+                _ => known.remove(&reg),
+            };
+            if mnemonic == "adrp" && value.is_some() {
+                pages.insert(reg);
+            } else {
+                pages.remove(&reg);
+            }
+        }
+    }
+
+    // Pre- and post-indexed accesses also move their base register.
+    if let Some((reg, step)) = arm64_base_update(operands) {
+        match (known.get(&reg), step) {
+            (Some(&v), Some(step)) => known.insert(reg, v.wrapping_add(step as u64)),
+            _ => known.remove(&reg),
+        };
+        pages.remove(&reg);
     }
+
+    pseudo
+}
+
+/// Base register moved by a writeback access, with the step when it is an immediate.
+fn arm64_base_update(operands: &str) -> Option<(u32, Option<i64>)> {
+    if let Some(wb) = arm64_writeback(operands) {
+        return arm64_reg(wb.base).map(|(reg, _)| (reg, Some(wb.step)));
+    }
+    // Register post-index (`ld1 {v0.16b}, [x1], x2`): the new base is unknown.
+    let ops = split_operands(operands);
+    let mem_index = ops.iter().position(|op| op.starts_with('['))?;
+    if mem_index + 1 == ops.len() && !ops[mem_index].ends_with("]!") {
+        return None;
+    }
+    let (base, _) = parse_mem_operand(ops[mem_index])?;
+    arm64_reg(base).map(|(reg, _)| (reg, None))
+}
+
+/// Parses an ARM64 general-purpose register (`x0`..`x30`, `w0`..`w30`) into its index.
+fn arm64_reg(op: &str) -> Option<(u32, bool)> {
+    let (is_w, digits) = match op.as_bytes().first()? {
+        b'x' => (false, &op[1..]),
+        b'w' => (true, &op[1..]),
+        _ => return None,
+    };
+    let index = digits.parse::<u32>().ok()?;
+    (index <= 30).then_some((index, is_w))
+}
+
+/// Splits an operand string on commas that are not inside a memory operand.
+fn split_operands(operands: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in operands.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                out.push(operands[start..i].trim());
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    let last = operands[start..].trim();
+    if !last.is_empty() {
+        out.push(last);
+    }
+    out
+}
+
+/// Formats an immediate the way Capstone does: decimal below 10, hex otherwise.
+fn format_imm(value: u64) -> String {
+    if value < 10 { format!("#{}", value) } else { format!("#0x{:x}", value) }
+}
+
+/// Parses the `[base, #offset]` part of a load/store operand string.
+fn parse_mem_operand(operands: &str) -> Option<(&str, u64)> {
+    let start = operands.find('[')?;
+    let end = operands[start..].find(']')? + start;
+    let mut parts = operands[start + 1..end].split(',').map(|s| s.trim());
+    let base = parts.next()?;
+    let offset = match parts.next() {
+        Some(imm) => parse_imm(imm)?,
+        None => 0,
+    };
+    Some((base, offset))
+}
+
+/// Names an `sp`/`x29` relative load/store operand as a stack local, e.g. `[sp, #0x10]` -> `local_0x10`
+/// and `[x29, #-0x8]` -> `local_0x8`.
+///
+/// Pre- and post-indexed forms also move the base register and are handled by [`arm64_writeback`].
+fn stack_local(operands: &str) -> Option<String> {
+    let ops = split_operands(operands);
+    let [_, mem] = ops.as_slice() else {
+        return None;
+    };
+    if !mem.ends_with(']') {
+        return None;
+    }
+    let (base, offset) = parse_mem_operand(mem)?;
+    matches!(base, "sp" | "x29").then(|| format!("local_0x{:x}", (offset as i64).unsigned_abs()))
+}
+
+/// Parses an immediate such as `#0x1000`, `#-0x8` or `#16`.
+pub(super) fn parse_imm(imm: &str) -> Option<u64> {
+    let imm = imm.trim().trim_start_matches('#');
+    let (negative, digits) = match imm.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, imm),
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+        None => digits.parse::<u64>().ok()?,
+    };
+    Some(if negative { value.wrapping_neg() } else { value })
+}
+
+/// Base register update of a pre-indexed (`[x1, #8]!`) or post-indexed (`[x1], #8`) access.
+struct Writeback<'a> {
+    /// Operands with the access rewritten to the plain `[base]` form.
+    operands: String,
+    base: &'a str,
+    step: i64,
+    pre: bool,
+}
+
+/// Detects ARM64 writeback addressing, where the load/store also moves its base register.
+fn arm64_writeback(operands: &str) -> Option<Writeback<'_>> {
+    let ops = split_operands(operands);
+    let (mem_index, pre) = match ops.as_slice() {
+        [.., mem] if mem.ends_with("]!") => (ops.len() - 1, true),
+        [.., mem, imm] if mem.ends_with(']') && imm.starts_with('#') => (ops.len() - 2, false),
+        _ => return None,
+    };
+    let mem = ops[mem_index];
+    let (base, offset) = if pre {
+        parse_mem_operand(mem)?
+    } else {
+        let (base, _) = parse_mem_operand(mem)?;
+        (base, parse_imm(ops[mem_index + 1])?)
+    };
+
+    let mut rewritten: Vec<String> = ops[..mem_index].iter().map(|op| op.to_string()).collect();
+    rewritten.push(format!("[{}]", base));
+    Some(Writeback { operands: rewritten.join(", "), base, step: offset as i64, pre })
 }
 
 /// Generates pseudo-code for ARM64 instructions.
+///
+/// Writeback loads and stores get the base register update as a separate statement, before the
+/// access for pre-indexed forms and after it for post-indexed ones: `x1 += 8; x0 = *([x1])_qword`.
 fn generate_arm64_pseudo(mnemonic: &str, operands: &str) -> String {
+    let writeback_access = matches!(
+        mnemonic,
+        "ldr" | "ldrb" | "ldrh" | "ldrsb" | "ldrsh" | "ldrsw" | "ldp" | "str" | "strb" | "strh" | "stp"
+    );
+    match writeback_access.then(|| arm64_writeback(operands)).flatten() {
+        Some(wb) => {
+            // The base moves, so an sp/x29 offset does not name a fixed stack slot
+            let access = arm64_pseudo_body(mnemonic, &wb.operands, false);
+            let magnitude = wb.step.unsigned_abs();
+            let step = if magnitude < 10 { magnitude.to_string() } else { format!("0x{:x}", magnitude) };
+            let update = format!("{} {}= {}", wb.base, if wb.step < 0 { '-' } else { '+' }, step);
+            if wb.pre { format!("{}; {}", update, access) } else { format!("{}; {}", access, update) }
+        },
+        None => arm64_pseudo_body(mnemonic, operands, true),
+    }
+}
+
+/// Pseudo-code for one ARM64 instruction without writeback handling.
+///
+/// `stack_locals` enables naming `sp`/`x29` relative accesses as stack locals.
+fn arm64_pseudo_body(mnemonic: &str, operands: &str, stack_locals: bool) -> String {
     let ops: Vec<&str> = operands.split(',').map(|s| s.trim()).collect();
 
     match mnemonic {
@@ -38,20 +473,26 @@ fn generate_arm64_pseudo(mnemonic: &str, operands: &str) -> String {
         },
 
         // Load instructions
-        "ldr" | "ldrb" | "ldrh" | "ldrsb" | "ldrsh" | "ldrsw" => {
-            if ops.len() >= 2 {
-                let size = match mnemonic {
-                    "ldrb" | "ldrsb" => "byte",
-                    "ldrh" | "ldrsh" => "word",
-                    "ldrsw" => "dword",
-                    _ => "qword",
-                };
-                format!("{} = *({})_{}", ops[0], ops[1], size)
+        "ldr" | "ldur" => {
+            let ops = split_operands(operands);
+            if stack_locals && let Some(local) = stack_local(operands) {
+                format!("{} = {}", ops[0], local)
+            } else if ops.len() >= 2 {
+                format!("{} = *({})_qword", ops[0], ops[1])
             } else {
                 format!("{} {}", mnemonic, operands)
             }
         },
 
+        "ldrb" | "ldrh" | "ldrsb" | "ldrsh" | "ldrsw" => {
+            let ops = split_operands(operands);
+            let dst_bits = if ops[0].starts_with('w') { 32 } else { 64 };
+            match (ops.get(1), extending_load_cast(mnemonic, dst_bits)) {
+                (Some(src), Some(cast)) => format!("{} = {}*({})", ops[0], cast, src),
+                _ => format!("{} {}", mnemonic, operands),
+            }
+        },
+
         "ldp" => {
             if ops.len() >= 3 {
                 format!("{} = *{}; {} = *({}+8)", ops[0], ops[2], ops[1], ops[2])
@@ -61,8 +502,11 @@ fn generate_arm64_pseudo(mnemonic: &str, operands: &str) -> String {
         },
 
         // Store instructions
-        "str" | "strb" | "strh" => {
-            if ops.len() >= 2 {
+        "str" | "strb" | "strh" | "stur" => {
+            let ops = split_operands(operands);
+            if stack_locals && matches!(mnemonic, "str" | "stur") && let Some(local) = stack_local(operands) {
+                format!("{} = {}", local, ops[0])
+            } else if ops.len() >= 2 {
                 let size = match mnemonic {
                     "strb" => "byte",
                     "strh" => "word",
@@ -82,6 +526,23 @@ fn generate_arm64_pseudo(mnemonic: &str, operands: &str) -> String {
             }
         },
 
+        // PC-relative address formation
+        "adrp" => {
+            if ops.len() >= 2 {
+                format!("{} = page({})", ops[0], ops[1].trim_start_matches('#'))
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        "adr" => {
+            if ops.len() >= 2 {
+                format!("{} = {}", ops[0], ops[1].trim_start_matches('#'))
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
         // Arithmetic operations
         "add" | "adds" => {
             if ops.len() >= 3 {
@@ -119,6 +580,91 @@ fn generate_arm64_pseudo(mnemonic: &str, operands: &str) -> String {
             }
         },
 
+        // Floating point operations
+        "fmov" => {
+            if ops.len() >= 2 {
+                format!("{} = {}", ops[0], ops[1])
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        "fadd" | "fsub" | "fmul" | "fdiv" => {
+            if ops.len() >= 3 {
+                let op = match mnemonic {
+                    "fadd" => "+",
+                    "fsub" => "-",
+                    "fmul" => "*",
+                    _ => "/",
+                };
+                format!("{} = {} {} {}", ops[0], ops[1], op, ops[2])
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        "fmadd" | "fmsub" => {
+            if ops.len() >= 4 {
+                let op = if mnemonic == "fmadd" { "+" } else { "-" };
+                format!("{} = {} {} {} * {}", ops[0], ops[3], op, ops[1], ops[2])
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        "fneg" | "fabs" | "fsqrt" => {
+            if ops.len() >= 2 {
+                match mnemonic {
+                    "fneg" => format!("{} = -{}", ops[0], ops[1]),
+                    "fabs" => format!("{} = fabs({})", ops[0], ops[1]),
+                    _ => format!("{} = sqrt({})", ops[0], ops[1]),
+                }
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        "fcmp" | "fcmpe" => {
+            if ops.len() >= 2 {
+                format!("flags = {} - {}", ops[0], ops[1])
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        // Conversions: the destination register picks the cast type
+        "scvtf" | "ucvtf" | "fcvt" => {
+            let unsigned = mnemonic == "ucvtf";
+            match (ops.as_slice(), ops.first().and_then(|dst| arm64_fp_type(dst))) {
+                ([dst, src], Some(to)) => match arm64_int_type(src, unsigned) {
+                    Some(from) if unsigned => format!("{} = ({})({}){}", dst, to, from, src),
+                    _ => format!("{} = ({}){}", dst, to, src),
+                },
+                _ => format!("{} {}", mnemonic, operands),
+            }
+        },
+
+        "fcvtzs" | "fcvtzu" | "fcvtms" | "fcvtmu" | "fcvtps" | "fcvtpu" | "fcvtas" | "fcvtau" | "fcvtns" | "fcvtnu" => {
+            let unsigned = mnemonic.ends_with('u');
+            match (ops.as_slice(), ops.first().and_then(|dst| arm64_int_type(dst, unsigned))) {
+                ([dst, src], Some(to)) => {
+                    // fcvtz* truncates like a C cast; the other modes round first
+                    let rounding = match &mnemonic[4..5] {
+                        "m" => Some("floor"),
+                        "p" => Some("ceil"),
+                        "a" => Some("round"),
+                        "n" => Some("rint"),
+                        _ => None,
+                    };
+                    match rounding {
+                        Some(func) => format!("{} = ({}){}({})", dst, to, func, src),
+                        None => format!("{} = ({}){}", dst, to, src),
+                    }
+                },
+                _ => format!("{} {}", mnemonic, operands),
+            }
+        },
+
         // Logical operations
         "and" | "ands" => {
             if ops.len() >= 3 {
@@ -186,6 +732,43 @@ fn generate_arm64_pseudo(mnemonic: &str, operands: &str) -> String {
             }
         },
 
+        // Conditional select
+        "csel" | "csinc" | "csinv" | "csneg" => {
+            if ops.len() >= 4 {
+                let otherwise = match mnemonic {
+                    "csinc" => format!("{} + 1", ops[2]),
+                    "csinv" => format!("~{}", ops[2]),
+                    "csneg" => format!("-{}", ops[2]),
+                    _ => ops[2].to_string(),
+                };
+                format!("{} = ({}) ? {} : {}", ops[0], condition_name(ops[3]), ops[1], otherwise)
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        "cinc" | "cinv" | "cneg" => {
+            if ops.len() >= 3 {
+                let then = match mnemonic {
+                    "cinc" => format!("{} + 1", ops[1]),
+                    "cinv" => format!("~{}", ops[1]),
+                    _ => format!("-{}", ops[1]),
+                };
+                format!("{} = ({}) ? {} : {}", ops[0], condition_name(ops[2]), then, ops[1])
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        "cset" | "csetm" => {
+            if ops.len() >= 2 {
+                let then = if mnemonic == "cset" { "1" } else { "-1" };
+                format!("{} = ({}) ? {} : 0", ops[0], condition_name(ops[1]), then)
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
         // Branch instructions
         "b" => format!("goto {}", operands),
         "bl" => format!("call {}", operands),
@@ -201,6 +784,25 @@ fn generate_arm64_pseudo(mnemonic: &str, operands: &str) -> String {
         "b.lt" | "blt" => format!("if (less) goto {}", operands),
         "b.le" | "ble" => format!("if (less_equal) goto {}", operands),
 
+        // Compare or test a bit and branch
+        "cbz" | "cbnz" => {
+            if ops.len() >= 2 {
+                let cmp = if mnemonic == "cbz" { "==" } else { "!=" };
+                format!("if ({} {} 0) goto {}", ops[0], cmp, ops[1])
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
+        "tbz" | "tbnz" => {
+            if ops.len() >= 3 {
+                let cmp = if mnemonic == "tbz" { "==" } else { "!=" };
+                format!("if (({} & (1<<{})) {} 0) goto {}", ops[0], ops[1].trim_start_matches('#'), cmp, ops[2])
+            } else {
+                format!("{} {}", mnemonic, operands)
+            }
+        },
+
         // System/special
         "nop" => "// no operation".to_string(),
         "dmb" | "dsb" | "isb" => format!("{}() // memory barrier", mnemonic),
@@ -210,8 +812,91 @@ fn generate_arm64_pseudo(mnemonic: &str, operands: &str) -> String {
     }
 }
 
+/// C type name of an ARM64 scalar floating point register (`h`, `s`, `d`).
+fn arm64_fp_type(reg: &str) -> Option<&'static str> {
+    match reg.as_bytes().first()? {
+        b'h' => Some("half"),
+        b's' => Some("float"),
+        b'd' => Some("double"),
+        _ => None,
+    }
+}
+
+/// C type name of an ARM64 general purpose register (`w`, `x`).
+fn arm64_int_type(reg: &str, unsigned: bool) -> Option<&'static str> {
+    match (reg.as_bytes().first()?, unsigned) {
+        (b'w', false) => Some("int32"),
+        (b'w', true) => Some("uint32"),
+        (b'x', false) => Some("int64"),
+        (b'x', true) => Some("uint64"),
+        _ => None,
+    }
+}
+
+/// Cast chain for a partial-width load into a `dst_bits` wide register, e.g. `(int64)(int8)` for `ldrsb x0`.
+///
+/// The inner cast is the loaded width, the outer one the sign or zero extension to the register.
+fn extending_load_cast(mnemonic: &str, dst_bits: u32) -> Option<String> {
+    let (signed, src_bits) = match mnemonic {
+        "ldrb" => (false, 8),
+        "ldrh" => (false, 16),
+        "ldrsb" => (true, 8),
+        "ldrsh" => (true, 16),
+        "ldrsw" => (true, 32),
+        _ => return None,
+    };
+    let sign = if signed { "" } else { "u" };
+    Some(format!("({sign}int{dst_bits})({sign}int{src_bits})"))
+}
+
+/// Returns true for a single ARM32 register operand (`r0`..`r12`, `sb`, `sl`, `fp`, `ip`, `sp`, `lr`, `pc`).
+fn is_arm32_register(op: &str) -> bool {
+    matches!(op, "sb" | "sl" | "fp" | "ip" | "sp" | "lr" | "pc")
+        || op.strip_prefix('r').is_some_and(|n| n.parse::<u8>().is_ok_and(|n| n <= 15))
+}
+
+/// Maps an ARM condition code to the readable name used in pseudo-code.
+fn condition_name(cond: &str) -> &str {
+    match cond {
+        "eq" => "equal",
+        "ne" => "not_equal",
+        "gt" => "greater",
+        "ge" => "greater_equal",
+        "lt" => "less",
+        "le" => "less_equal",
+        "hi" => "unsigned_greater",
+        "hs" | "cs" => "unsigned_greater_equal",
+        "lo" | "cc" => "unsigned_less",
+        "ls" => "unsigned_less_equal",
+        "mi" => "negative",
+        "pl" => "positive_or_zero",
+        "vs" => "overflow",
+        "vc" => "no_overflow",
+        "al" => "always",
+        _ => cond,
+    }
+}
+
+/// Returns the opposite ARM condition code, used for the `e` (else) slots of an IT block.
+fn inverse_condition(cond: &str) -> Option<&'static str> {
+    const PAIRS: [(&str, &str); 8] =
+        [("eq", "ne"), ("cs", "cc"), ("hs", "lo"), ("mi", "pl"), ("vs", "vc"), ("hi", "ls"), ("ge", "lt"), ("gt", "le")];
+    PAIRS.iter().find_map(|&(a, b)| {
+        if cond == a {
+            Some(b)
+        } else if cond == b {
+            Some(a)
+        } else {
+            None
+        }
+    })
+}
+
 /// Generates pseudo-code for ARM32/Thumb instructions.
-fn generate_arm32_pseudo(mnemonic: &str, operands: &str) -> String {
+///
+/// `thumb` is the instruction set the code is decoded in; it decides which way an
+/// immediate `blx` switches.
+fn generate_arm32_pseudo(thumb: bool, mnemonic: &str, operands: &str) -> String {
     let ops: Vec<&str> = operands.split(',').map(|s| s.trim()).collect();
 
     match mnemonic {
@@ -224,19 +909,22 @@ fn generate_arm32_pseudo(mnemonic: &str, operands: &str) -> String {
             }
         },
 
-        "ldr" | "ldrb" | "ldrh" | "ldrsb" | "ldrsh" => {
+        "ldr" => {
             if ops.len() >= 2 {
-                let size = match mnemonic {
-                    "ldrb" | "ldrsb" => "byte",
-                    "ldrh" | "ldrsh" => "word",
-                    _ => "dword",
-                };
-                format!("{} = *({})_{}", ops[0], ops[1], size)
+                format!("{} = *({})_dword", ops[0], ops[1])
             } else {
                 format!("{} {}", mnemonic, operands)
             }
         },
 
+        "ldrb" | "ldrh" | "ldrsb" | "ldrsh" => {
+            let ops = split_operands(operands);
+            match (ops.get(1), extending_load_cast(mnemonic, 32)) {
+                (Some(src), Some(cast)) => format!("{} = {}*({})", ops[0], cast, src),
+                _ => format!("{} {}", mnemonic, operands),
+            }
+        },
+
         "str" | "strb" | "strh" => {
             if ops.len() >= 2 {
                 let size = match mnemonic {
@@ -271,7 +959,14 @@ fn generate_arm32_pseudo(mnemonic: &str, operands: &str) -> String {
         },
 
         "b" => format!("goto {}", operands),
-        "bl" | "blx" => format!("call {}", operands),
+        "bl" => format!("call {}", operands),
+        // blx always changes instruction set for an immediate target; the register form
+        // (like bx) switches according to bit 0 of the register at run time.
+        "blx" if is_arm32_register(operands) => format!("call {} // mode from bit 0 of {}", operands, operands),
+        "blx" => format!("call {} // switch to {}", operands, if thumb { "ARM" } else { "THUMB" }),
+        "bx" if operands != "lr" && is_arm32_register(operands) => {
+            format!("goto {} // mode from bit 0 of {}", operands, operands)
+        },
         "bx" => format!("goto {}", operands),
         "pop" => format!("restore {}", operands),
         "push" => format!("save {}", operands),
@@ -288,14 +983,227 @@ mod tests {
     fn test_arm64_pseudo() {
         assert_eq!(generate_arm64_pseudo("mov", "x0, x1"), "x0 = x1");
         assert_eq!(generate_arm64_pseudo("ldr", "x0, [x1]"), "x0 = *([x1])_qword");
+        assert_eq!(generate_arm64_pseudo("ldr", "x0, [sp, #0x10]"), "x0 = local_0x10");
+        assert_eq!(generate_arm64_pseudo("stur", "x1, [x29, #-8]"), "local_0x8 = x1");
+        assert_eq!(generate_arm64_pseudo("str", "w1, [sp]"), "local_0x0 = w1");
+        // Pre/post-indexed accesses move the base register and are not stack locals
+        assert_eq!(generate_arm64_pseudo("ldr", "x0, [sp], #0x10"), "x0 = *([sp])_qword; sp += 0x10");
+        assert!(!generate_arm64_pseudo("str", "x0, [sp, #-0x10]!").contains("local_"));
+        assert_eq!(generate_arm64_pseudo("ldrsb", "x0, [x1]"), "x0 = (int64)(int8)*([x1])");
+        assert_eq!(generate_arm64_pseudo("ldrsh", "w0, [x1, #2]"), "w0 = (int32)(int16)*([x1, #2])");
+        assert_eq!(generate_arm64_pseudo("ldrsw", "x0, [x1]"), "x0 = (int64)(int32)*([x1])");
+        assert_eq!(generate_arm64_pseudo("ldrb", "w0, [x1]"), "w0 = (uint32)(uint8)*([x1])");
+        assert_eq!(generate_arm64_pseudo("ldrh", "w0, [x1]"), "w0 = (uint32)(uint16)*([x1])");
         assert_eq!(generate_arm64_pseudo("add", "x0, x1, x2"), "x0 = x1 + x2");
         assert_eq!(generate_arm64_pseudo("b", "#0x1000"), "goto #0x1000");
+        assert_eq!(generate_arm64_pseudo("cbz", "x0, #0x1000"), "if (x0 == 0) goto #0x1000");
+        assert_eq!(generate_arm64_pseudo("cbnz", "w1, #0x1000"), "if (w1 != 0) goto #0x1000");
+        assert_eq!(generate_arm64_pseudo("tbz", "w0, #3, #0x1000"), "if ((w0 & (1<<3)) == 0) goto #0x1000");
+        assert_eq!(generate_arm64_pseudo("tbnz", "x2, #0x3f, #0x1000"), "if ((x2 & (1<<0x3f)) != 0) goto #0x1000");
+    }
+
+    #[test]
+    fn test_arm64_writeback_pseudo() {
+        // Pre-index: the base is updated before the access
+        assert_eq!(generate_arm64_pseudo("ldr", "x0, [x1, #8]!"), "x1 += 8; x0 = *([x1])_qword");
+        assert_eq!(generate_arm64_pseudo("str", "x0, [sp, #-0x10]!"), "sp -= 0x10; *([sp])_qword = x0");
+        assert_eq!(
+            generate_arm64_pseudo("stp", "x29, x30, [sp, #-0x20]!"),
+            "sp -= 0x20; *[sp] = x29; *([sp]+8) = x30"
+        );
+        // Post-index: the access uses the old base, which is updated afterwards
+        assert_eq!(generate_arm64_pseudo("ldr", "x0, [x1], #8"), "x0 = *([x1])_qword; x1 += 8");
+        assert_eq!(generate_arm64_pseudo("ldrb", "w2, [x3], #1"), "w2 = (uint32)(uint8)*([x3]); x3 += 1");
+        assert_eq!(
+            generate_arm64_pseudo("ldp", "x29, x30, [sp], #0x20"),
+            "x29 = *[sp]; x30 = *([sp]+8); sp += 0x20"
+        );
+        // Plain offsets are unchanged
+        assert_eq!(generate_arm64_pseudo("ldr", "x0, [x1, #8]"), "x0 = *([x1, #8])_qword");
+        assert_eq!(generate_arm64_pseudo("strh", "w0, [x1, #2]"), "*([x1, #2])_word = w0");
+    }
+
+    #[test]
+    fn test_arm64_writeback_pseudo_from_bytes() {
+        // ldr x0, [x1, #8]! ; ldr x0, [x1], #8
+        let bytes = [0x20, 0x8c, 0x40, 0xf8, 0x20, 0x84, 0x40, 0xf8];
+        let results = crate::disasm::disassemble_with_pseudo(Architecture::ARM64, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(results[0].operands, "x0, [x1, #8]!");
+        assert_eq!(results[0].pseudo_code.as_deref(), Some("x1 += 8; x0 = *([x1])_qword"));
+        assert_eq!(results[1].operands, "x0, [x1], #8");
+        assert_eq!(results[1].pseudo_code.as_deref(), Some("x0 = *([x1])_qword; x1 += 8"));
+    }
+
+    #[test]
+    fn test_arm64_conditional_select_pseudo() {
+        assert_eq!(generate_arm64_pseudo("cset", "w0, eq"), "w0 = (equal) ? 1 : 0");
+        assert_eq!(generate_arm64_pseudo("csetm", "x0, ne"), "x0 = (not_equal) ? -1 : 0");
+        assert_eq!(generate_arm64_pseudo("csel", "x0, x1, x2, lt"), "x0 = (less) ? x1 : x2");
+        assert_eq!(generate_arm64_pseudo("csinc", "w0, w1, w2, ge"), "w0 = (greater_equal) ? w1 : w2 + 1");
+        assert_eq!(generate_arm64_pseudo("csneg", "w0, w1, w2, hi"), "w0 = (unsigned_greater) ? w1 : -w2");
+        assert_eq!(generate_arm64_pseudo("cinc", "w0, w1, le"), "w0 = (less_equal) ? w1 + 1 : w1");
+        assert_eq!(generate_arm64_pseudo("cneg", "x0, x1, mi"), "x0 = (negative) ? -x1 : x1");
+    }
+
+    #[test]
+    fn test_arm64_fp_pseudo() {
+        assert_eq!(generate_arm64_pseudo("fadd", "s0, s1, s2"), "s0 = s1 + s2");
+        assert_eq!(generate_arm64_pseudo("fdiv", "d0, d1, d2"), "d0 = d1 / d2");
+        assert_eq!(generate_arm64_pseudo("fmadd", "s0, s1, s2, s3"), "s0 = s3 + s1 * s2");
+        assert_eq!(generate_arm64_pseudo("fmov", "s0, #1.00000000"), "s0 = #1.00000000");
+        assert_eq!(generate_arm64_pseudo("fcmp", "s0, #0.0"), "flags = s0 - #0.0");
+        assert_eq!(generate_arm64_pseudo("fneg", "d0, d1"), "d0 = -d1");
+    }
+
+    #[test]
+    fn test_arm64_fp_conversion_pseudo() {
+        assert_eq!(generate_arm64_pseudo("scvtf", "d0, w1"), "d0 = (double)w1");
+        assert_eq!(generate_arm64_pseudo("ucvtf", "s0, x1"), "s0 = (float)(uint64)x1");
+        assert_eq!(generate_arm64_pseudo("fcvt", "d0, s1"), "d0 = (double)s1");
+        assert_eq!(generate_arm64_pseudo("fcvtzs", "w0, s1"), "w0 = (int32)s1");
+        assert_eq!(generate_arm64_pseudo("fcvtzu", "x0, d1"), "x0 = (uint64)d1");
+        assert_eq!(generate_arm64_pseudo("fcvtms", "w0, d1"), "w0 = (int32)floor(d1)");
+        // Fixed-point forms are left as raw text
+        assert_eq!(generate_arm64_pseudo("scvtf", "d0, w1, #16"), "scvtf d0, w1, #16");
+    }
+
+    #[test]
+    fn test_arm64_fp_pseudo_from_bytes() {
+        // fadd s0, s1, s2 ; scvtf d0, w1
+        let bytes = [0x20, 0x28, 0x22, 0x1e, 0x20, 0x00, 0x62, 0x1e];
+        let pseudo = generate_pseudo_block(Architecture::ARM64, &decode(&bytes));
+        assert_eq!(pseudo, vec!["s0 = s1 + s2", "d0 = (double)w1"]);
+    }
+
+    fn decode(bytes: &[u8]) -> Vec<DisassemblyResult> {
+        crate::disasm::disassemble(Architecture::ARM64, bytes, 0x1000, 0).unwrap()
+    }
+
+    #[test]
+    fn test_pseudo_block_constant_propagation() {
+        // mov x0, #4 ; add x1, x0, #8 ; add x3, x1, #1 ; ldr x0, [x2] ; add x3, x0, #1
+        let bytes = [
+            0x80, 0x00, 0x80, 0xd2, 0x01, 0x20, 0x00, 0x91, 0x23, 0x04, 0x00, 0x91, 0x40, 0x00, 0x40, 0xf9, 0x03, 0x04, 0x00,
+            0x91,
+        ];
+        let pseudo = generate_pseudo_block(Architecture::ARM64, &decode(&bytes));
+        assert_eq!(pseudo, vec!["x0 = #4", "x1 = #4 + #8", "x3 = #0xc + #1", "x0 = *([x2])_qword", "x3 = x0 + #1"]);
+    }
+
+    #[test]
+    fn test_pseudo_block_writeback_moves_base() {
+        // mov x1, #0x100 ; ldr x0, [x1], #8 ; add x2, x1, #1 ; ldr x0, [x1, #8]! ; add x2, x1, #1
+        let bytes = [
+            0x01, 0x20, 0x80, 0xd2, 0x20, 0x84, 0x40, 0xf8, 0x22, 0x04, 0x00, 0x91, 0x20, 0x8c, 0x40, 0xf8, 0x22, 0x04, 0x00,
+            0x91,
+        ];
+        let pseudo = generate_pseudo_block(Architecture::ARM64, &decode(&bytes));
+        assert_eq!(pseudo[2], "x2 = #0x108 + #1");
+        assert_eq!(pseudo[4], "x2 = #0x110 + #1");
+
+        // mov x1, #0x100 ; ld1 {v0.16b}, [x1], x2 ; add x2, x1, #1
+        let bytes = [0x01, 0x20, 0x80, 0xd2, 0x20, 0x70, 0xc2, 0x4c, 0x22, 0x04, 0x00, 0x91];
+        let results = decode(&bytes);
+        assert_eq!(results[1].mnemonic, "ld1");
+        let pseudo = generate_pseudo_block(Architecture::ARM64, &results);
+        assert_eq!(pseudo[2], "x2 = x1 + #1");
+    }
+
+    #[test]
+    fn test_pseudo_block_call_clears_tracking() {
+        // mov w0, #4 ; add x1, x0, #8 ; bl #0x1010 ; add x1, x0, #8
+        let bytes = [0x80, 0x00, 0x80, 0x52, 0x01, 0x20, 0x00, 0x91, 0x02, 0x00, 0x00, 0x94, 0x01, 0x20, 0x00, 0x91];
+        let pseudo = generate_pseudo_block(Architecture::ARM64, &decode(&bytes));
+        assert_eq!(pseudo[1], "x1 = #4 + #8");
+        assert_eq!(pseudo[3], "x1 = x0 + #8");
+    }
+
+    #[test]
+    fn test_arm64_adrp_adr_pseudo() {
+        // adrp x0, #0x1000 ; add x0, x0, #0xabc ; adr x1, #0x1008 ; add x2, x1, #4
+        let bytes = [0x00, 0x00, 0x00, 0x90, 0x00, 0xf0, 0x2a, 0x91, 0x01, 0x00, 0x00, 0x10, 0x22, 0x10, 0x00, 0x91];
+        let results = decode(&bytes);
+        let single: Vec<String> = results.iter().map(|r| generate_arm64_pseudo(&r.mnemonic, &r.operands)).collect();
+        assert_eq!(single[0], "x0 = page(0x1000)");
+        assert_eq!(single[1], "x0 = x0 + #0xabc");
+        assert_eq!(single[2], "x1 = 0x1008");
+
+        let pseudo = generate_pseudo_block(Architecture::ARM64, &results);
+        assert_eq!(pseudo, vec!["x0 = page(0x1000)", "x0 = 0x1abc", "x1 = 0x1008", "x2 = #0x1008 + #4"]);
+    }
+
+    #[test]
+    fn test_split_operands() {
+        assert_eq!(split_operands("x0, [x1, #8]"), vec!["x0", "[x1, #8]"]);
+        assert_eq!(split_operands("x0, x1, [x2, #0x10]!"), vec!["x0", "x1", "[x2, #0x10]!"]);
+        assert!(split_operands("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_imm() {
+        assert_eq!(parse_imm("#0x1000"), Some(0x1000));
+        assert_eq!(parse_imm("#16"), Some(16));
+        assert_eq!(parse_imm("#-0x8"), Some((-8i64) as u64));
+        assert_eq!(parse_imm("x1"), None);
     }
 
     #[test]
     fn test_arm32_pseudo() {
-        assert_eq!(generate_arm32_pseudo("mov", "r0, r1"), "r0 = r1");
-        assert_eq!(generate_arm32_pseudo("ldr", "r0, [r1]"), "r0 = *([r1])_dword");
-        assert_eq!(generate_arm32_pseudo("add", "r0, r1, r2"), "r0 = r1 + r2");
+        assert_eq!(generate_arm32_pseudo(false, "mov", "r0, r1"), "r0 = r1");
+        assert_eq!(generate_arm32_pseudo(false, "ldr", "r0, [r1]"), "r0 = *([r1])_dword");
+        assert_eq!(generate_arm32_pseudo(false, "ldrsb", "r0, [r1]"), "r0 = (int32)(int8)*([r1])");
+        assert_eq!(generate_arm32_pseudo(false, "ldrsh", "r0, [r1, #2]"), "r0 = (int32)(int16)*([r1, #2])");
+        assert_eq!(generate_arm32_pseudo(false, "ldrb", "r0, [r1]"), "r0 = (uint32)(uint8)*([r1])");
+        assert_eq!(generate_arm32_pseudo(false, "ldrh", "r0, [r1]"), "r0 = (uint32)(uint16)*([r1])");
+        assert_eq!(generate_arm32_pseudo(false, "add", "r0, r1, r2"), "r0 = r1 + r2");
+    }
+
+    #[test]
+    fn test_arm32_interworking_pseudo() {
+        assert_eq!(generate_arm32_pseudo(false, "blx", "#0x1010"), "call #0x1010 // switch to THUMB");
+        assert_eq!(generate_arm32_pseudo(true, "blx", "#0x1008"), "call #0x1008 // switch to ARM");
+        assert_eq!(generate_arm32_pseudo(true, "blx", "r3"), "call r3 // mode from bit 0 of r3");
+        assert_eq!(generate_arm32_pseudo(false, "bx", "r12"), "goto r12 // mode from bit 0 of r12");
+        assert_eq!(generate_arm32_pseudo(false, "bx", "lr"), "goto lr");
+        assert_eq!(generate_arm32_pseudo(true, "bl", "#0x2000"), "call #0x2000");
+    }
+
+    #[test]
+    fn test_arm32_blx_uses_detail_target() {
+        // ARM: blx #0x1010 ; blx r3
+        let bytes = [0x02, 0x00, 0x00, 0xfa, 0x33, 0xff, 0x2f, 0xe1];
+        let results = crate::disasm::disassemble_with_pseudo(Architecture::ARM32, &bytes, 0x1000, 0).unwrap();
+        assert_eq!(results[0].pseudo_code.as_deref(), Some("call 0x1010 // switch to THUMB"));
+        assert_eq!(results[1].pseudo_code.as_deref(), Some("call r3 // mode from bit 0 of r3"));
+
+        // THUMB: blx #0x1008 (target aligned to 4 for the ARM callee)
+        let bytes = [0x00, 0xf0, 0x02, 0xe8];
+        let results = crate::disasm::disassemble_with_pseudo(Architecture::THUMB, &bytes, 0x1002, 0).unwrap();
+        assert_eq!(results[0].pseudo_code.as_deref(), Some("call 0x1008 // switch to ARM"));
+    }
+
+    #[test]
+    fn test_thumb_it_block_pseudo() {
+        // cmp r0, #0 ; ite eq ; moveq r0, #1 ; movne r0, #0 ; it ne ; bxne lr ; bx lr
+        let bytes = [0x00, 0x28, 0x0c, 0xbf, 0x01, 0x20, 0x00, 0x20, 0x18, 0xbf, 0x70, 0x47, 0x70, 0x47];
+        let results = crate::disasm::disassemble_with_pseudo(Architecture::THUMB, &bytes, 0x1000, 0).unwrap();
+        let pseudo: Vec<&str> = results.iter().map(|r| r.pseudo_code.as_deref().unwrap()).collect();
+
+        // Decoded one at a time, Capstone may or may not add the condition suffix (`moveq` or `movs`)
+        assert_eq!(pseudo[2], "if (equal) { r0 = #1 }");
+        assert_eq!(pseudo[3], "if (not_equal) { r0 = #0 }");
+        assert_eq!(pseudo[5], "if (not_equal) { goto lr }");
+        // The block ends after its last slot
+        assert_eq!(pseudo[6], "goto lr");
+
+        let block = generate_pseudo_block(Architecture::THUMB, &results);
+        assert_eq!(block[2..], pseudo[2..]);
+
+        assert_eq!(it_block_conditions("itete", "gt").unwrap(), vec!["gt", "le", "gt", "le"]);
+        let generate = |mnemonic: &str| generate_arm32_pseudo(true, mnemonic, "r0, #1");
+        assert_eq!(predicated("eq", "moveq", generate), "if (equal) { r0 = #1 }");
+        assert_eq!(predicated("ne", "ldrne.w", |mnemonic: &str| mnemonic.to_string()), "if (not_equal) { ldr.w }");
+        assert!(it_block_conditions("isb", "sy").is_none());
+        assert!(it_block_conditions("ite", "al").is_none());
     }
 }
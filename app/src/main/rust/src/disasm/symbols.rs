@@ -0,0 +1,85 @@
+//! Symbol names for direct branch and call targets.
+
+use super::describe::{classify, direct_target};
+use super::{Architecture, DisassemblyResult};
+use std::collections::HashMap;
+
+/// Replaces direct branch and call targets found in `symbols` with their names, in both the
+/// operands and the pseudo-code (`bl #0x400abc` becomes `bl sub_400abc`).
+///
+/// Targets are resolved from the structured operands, so the results need detail.
+pub fn annotate_symbols(arch: Architecture, results: &mut [DisassemblyResult], symbols: &HashMap<u64, String>) {
+    if symbols.is_empty() {
+        return;
+    }
+    for result in results.iter_mut() {
+        let Some(target) = direct_target(classify(arch, result), result) else {
+            continue;
+        };
+        let Some(name) = symbols.get(&target) else {
+            continue;
+        };
+        result.operands = replace_address(&result.operands, target, name);
+        if let Some(pseudo) = result.pseudo_code.as_mut() {
+            *pseudo = replace_address(pseudo, target, name);
+        }
+    }
+}
+
+/// Replaces every `0x<target>` (with an optional leading `#`) in `text` by `name`.
+///
+/// Longer hex numbers that merely start with the target are left alone.
+fn replace_address(text: &str, target: u64, name: &str) -> String {
+    let needle = format!("0x{:x}", target);
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(&needle) {
+        let end = pos + needle.len();
+        if rest[end..].starts_with(|c: char| c.is_ascii_hexdigit()) {
+            out.push_str(&rest[..end]);
+        } else {
+            out.push_str(rest[..pos].strip_suffix('#').unwrap_or(&rest[..pos]));
+            out.push_str(name);
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disasm::{disassemble_with_symbols, DisasmOptions};
+
+    #[test]
+    fn test_replace_address() {
+        assert_eq!(replace_address("#0x400abc", 0x400abc, "init"), "init");
+        assert_eq!(replace_address("x0, #0x1010", 0x1010, "loop_end"), "x0, loop_end");
+        assert_eq!(replace_address("0x10100", 0x1010, "f"), "0x10100");
+    }
+
+    #[test]
+    fn test_disassemble_with_symbols() {
+        let symbols = HashMap::from([(0x400b00u64, "sub_400b00".to_string()), (0x400aacu64, "retry".to_string())]);
+
+        // bl #0x400b00 ; b.eq #0x400aac ; ret
+        let bytes = [0x40, 0x00, 0x00, 0x94, 0x40, 0x05, 0x00, 0x54, 0xc0, 0x03, 0x5f, 0xd6];
+        let options = DisasmOptions { pseudo: true, ..Default::default() };
+        let results = disassemble_with_symbols(Architecture::ARM64, &bytes, 0x400a00, 0, &options, &symbols).unwrap();
+        assert_eq!(results[0].operands, "sub_400b00");
+        assert_eq!(results[0].pseudo_code.as_deref(), Some("call sub_400b00"));
+        assert_eq!(results[1].operands, "retry");
+        assert_eq!(results[1].pseudo_code.as_deref(), Some("if (equal) goto retry"));
+        assert_eq!(results[2].operands, "");
+
+        // call 0x1005 with a target that is not in the map
+        let results = disassemble_with_symbols(Architecture::X86_64, &[0xe8, 0x00, 0x00, 0x00, 0x00], 0x1000, 0, &DisasmOptions::default(), &symbols)
+            .unwrap();
+        assert_eq!(results[0].operands, "0x1005");
+        let symbols = HashMap::from([(0x1005u64, "memcpy".to_string())]);
+        let results = disassemble_with_symbols(Architecture::X86_64, &[0xe8, 0x00, 0x00, 0x00, 0x00], 0x1000, 0, &DisasmOptions::default(), &symbols)
+            .unwrap();
+        assert_eq!(results[0].operands, "memcpy");
+    }
+}
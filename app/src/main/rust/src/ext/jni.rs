@@ -1,6 +1,7 @@
 use anyhow::Context;
 use jni::JNIEnv;
 use jni::objects::{JClass, JString};
+use std::cell::RefCell;
 
 /// 抛出 RuntimeException 异常，支持格式化字符串
 #[macro_export] 
@@ -17,6 +18,82 @@ macro_rules! throw_runtime_exception {
 
 pub type JniResult<T> = anyhow::Result<T>;
 
+/// 结构化错误码，Java 侧可据此分支处理而无需解析异常消息
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JniErrorCode {
+    None = 0,
+    Unknown = 1,
+    InvalidArgument = 2,
+    OutOfBounds = 3,
+    DiskFull = 4,
+    Io = 5,
+    NotInitialized = 6,
+    Jni = 7,
+}
+
+impl JniErrorCode {
+    /// 构造携带错误码的 anyhow 错误
+    pub fn error(self, message: impl Into<String>) -> anyhow::Error {
+        anyhow::Error::new(CodedError {
+            code: self,
+            message: message.into(),
+        })
+    }
+
+    /// 沿错误链查找错误码，未显式标注时根据底层错误类型推断
+    pub fn classify(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(coded) = cause.downcast_ref::<CodedError>() {
+                return coded.code;
+            }
+            if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+                return if io.kind() == std::io::ErrorKind::StorageFull {
+                    JniErrorCode::DiskFull
+                } else {
+                    JniErrorCode::Io
+                };
+            }
+            if cause.downcast_ref::<jni::errors::Error>().is_some() {
+                return JniErrorCode::Jni;
+            }
+        }
+        JniErrorCode::Unknown
+    }
+}
+
+/// 携带错误码的错误
+#[derive(Debug)]
+pub struct CodedError {
+    pub code: JniErrorCode,
+    pub message: String,
+}
+
+impl std::fmt::Display for CodedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CodedError {}
+
+thread_local! {
+    /// 当前线程最近一次 JNI 调用失败的错误码与消息
+    static LAST_ERROR: RefCell<Option<(JniErrorCode, String)>> = const { RefCell::new(None) };
+}
+
+/// 记录错误到当前线程的 LAST_ERROR
+pub fn record_last_error(error: &anyhow::Error) {
+    let code = JniErrorCode::classify(error);
+    let message = format!("{:#}", error);
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some((code, message)));
+}
+
+/// 获取当前线程最近一次记录的错误
+pub fn last_error() -> Option<(JniErrorCode, String)> {
+    LAST_ERROR.with(|last| last.borrow().clone())
+}
+
 pub trait JniResultExt<T> {
     fn or_throw(self, env: &mut JNIEnv) -> T;
 }
@@ -24,6 +101,7 @@ pub trait JniResultExt<T> {
 impl<T: Default> JniResultExt<T> for JniResult<T> {
     fn or_throw(self, env: &mut JNIEnv) -> T {
         self.unwrap_or_else(|e| {
+            record_last_error(&e);
             let _ = env.throw(format!("{:#}", e));
             T::default()
         })
@@ -46,3 +124,41 @@ impl<'l> JNIEnvExt<'l> for JNIEnv<'l> {
             .context("Failed to create JString")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_error_code_classify() {
+        let err = JniErrorCode::InvalidArgument.error("Invalid architecture value: 9");
+        assert_eq!(JniErrorCode::classify(&err), JniErrorCode::InvalidArgument);
+
+        // 外层 context 不影响错误码
+        let err = Err::<(), _>(JniErrorCode::OutOfBounds.error("Index out of bounds: 5 >= 3"))
+            .context("Remove failed")
+            .unwrap_err();
+        assert_eq!(JniErrorCode::classify(&err), JniErrorCode::OutOfBounds);
+
+        let err = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::StorageFull));
+        assert_eq!(JniErrorCode::classify(&err), JniErrorCode::DiskFull);
+
+        let err = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        assert_eq!(JniErrorCode::classify(&err), JniErrorCode::Io);
+
+        assert_eq!(JniErrorCode::classify(&anyhow::anyhow!("plain message")), JniErrorCode::Unknown);
+    }
+
+    #[test]
+    fn test_record_last_error() {
+        let err = Err::<(), _>(JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))
+            .context("Search failed")
+            .unwrap_err();
+        record_last_error(&err);
+
+        let (code, message) = last_error().unwrap();
+        assert_eq!(code, JniErrorCode::NotInitialized);
+        assert_eq!(message, "Search failed: SearchEngineManager not initialized");
+    }
+}
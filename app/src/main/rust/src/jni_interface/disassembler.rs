@@ -1,13 +1,21 @@
 //! JNI methods for Disassembler
 
-use anyhow::anyhow;
-use crate::disasm::{Architecture, disassemble, disassemble_with_pseudo};
-use crate::ext::jni::{JniResult, JniResultExt};
+use anyhow::{Context, anyhow};
+use crate::core::DRIVER_MANAGER;
+use crate::disasm::{
+    Architecture, BoundaryKind, DisasmOptions, DisasmSyntax, Operand, describe_instruction, detect_arm_mode, disassemble, disassemble_backward,
+    disassemble_skipdata, disassemble_with, disassemble_with_pseudo, disassemble_with_pseudo_source, disassemble_with_symbols, find_function_boundaries, format_listing, hexdump,
+};
+use crate::ext::jni::{JniErrorCode, JniResult, JniResultExt};
 use jni::JNIEnv;
-use jni::objects::{JByteArray, JClass, JObject, JObjectArray, JString};
-use jni::sys::{jint, jlong, jobjectArray, jsize};
+use jni::objects::{JByteArray, JLongArray, JObject, JObjectArray, JString};
+use jni::sys::{jboolean, jint, jlong, jlongArray, jobject, jobjectArray, jsize, jstring, JNI_TRUE};
 use jni_macro::jni_method;
 use log::{debug, error};
+use std::collections::HashMap;
+
+/// Largest window `nativeDisassembleAddress` reads from the target process in one call.
+const MAX_ADDRESS_READ_LEN: jint = 1024 * 1024;
 
 /// Converts DisassemblyResult to Java object
 fn disasm_result_to_jobject<'l>(
@@ -34,20 +42,121 @@ fn disasm_result_to_jobject<'l>(
         JObject::null()
     };
 
-    // DisassemblyResult(address: Long, bytes: String, mnemonic: String, operands: String, pseudoCode: String?)
+    let groups = string_vec_to_jarray(env, &result.groups)?;
+    let regs_read = string_vec_to_jarray(env, &result.regs_read)?;
+    let regs_written = string_vec_to_jarray(env, &result.regs_written)?;
+    let operands_detail = operands_to_jarray(env, &result.operands_detail)?;
+
+    // DisassemblyResult(address: Long, bytes: String, mnemonic: String, operands: String, pseudoCode: String?,
+    //                   groups: Array<String>, regsRead: Array<String>, regsWritten: Array<String>,
+    //                   operandsDetail: Array<DisassemblyOperand>, id: Int, size: Int)
     Ok(env.new_object(
         class,
-        "(JLjava/lang/String;Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;)V",
+        "(JLjava/lang/String;Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;[Ljava/lang/String;[Ljava/lang/String;[Ljava/lang/String;[Lmoe/fuqiuluo/mamu/driver/DisassemblyOperand;II)V",
         &[
             (result.address as jlong).into(),
             (&bytes_str).into(),
             (&mnemonic_str).into(),
             (&operands_str).into(),
             (&pseudo_str).into(),
+            (&groups).into(),
+            (&regs_read).into(),
+            (&regs_written).into(),
+            (&operands_detail).into(),
+            (result.id as jint).into(),
+            (result.size() as jint).into(),
         ],
     )?)
 }
 
+/// Converts structured operands to a Java DisassemblyOperand[]
+fn operands_to_jarray<'l>(env: &mut JNIEnv<'l>, operands: &[Operand]) -> JniResult<JObjectArray<'l>> {
+    let class = env.find_class("moe/fuqiuluo/mamu/driver/DisassemblyOperand")?;
+    let array = env.new_object_array(operands.len() as jsize, &class, JObject::null())?;
+
+    for (i, operand) in operands.iter().enumerate() {
+        // DisassemblyOperand(type: Int, reg: String?, imm: Long, base: String?, index: String?, disp: Long)
+        let (kind, reg, imm, base, index, disp) = match operand {
+            Operand::Reg(reg) => (0, Some(reg), 0, None, None, 0),
+            Operand::Imm(imm) => (1, None, *imm, None, None, 0),
+            Operand::Mem { base, index, disp } => (2, None, 0, base.as_ref(), index.as_ref(), *disp),
+        };
+
+        let reg: JObject = match reg {
+            Some(reg) => env.new_string(reg)?.into(),
+            None => JObject::null(),
+        };
+        let base: JObject = match base {
+            Some(base) => env.new_string(base)?.into(),
+            None => JObject::null(),
+        };
+        let index: JObject = match index {
+            Some(index) => env.new_string(index)?.into(),
+            None => JObject::null(),
+        };
+
+        let obj = env.new_object(
+            &class,
+            "(ILjava/lang/String;JLjava/lang/String;Ljava/lang/String;J)V",
+            &[
+                (kind as jint).into(),
+                (&reg).into(),
+                (imm as jlong).into(),
+                (&base).into(),
+                (&index).into(),
+                (disp as jlong).into(),
+            ],
+        )?;
+        env.set_object_array_element(&array, i as jsize, obj)?;
+    }
+
+    Ok(array)
+}
+
+/// Converts a list of strings to a Java String[]
+fn string_vec_to_jarray<'l>(env: &mut JNIEnv<'l>, values: &[String]) -> JniResult<JObjectArray<'l>> {
+    let array = env.new_object_array(values.len() as jsize, "java/lang/String", JObject::null())?;
+    for (i, value) in values.iter().enumerate() {
+        let jstr = env.new_string(value)?;
+        env.set_object_array_element(&array, i as jsize, jstr)?;
+    }
+    Ok(array)
+}
+
+/// Converts results to a Java DisassemblyResult[]
+fn disasm_results_to_jarray(env: &mut JNIEnv, results: &[crate::disasm::DisassemblyResult]) -> JniResult<jobjectArray> {
+    let result_class = env.find_class("moe/fuqiuluo/mamu/driver/DisassemblyResult")?;
+    let array = env.new_object_array(results.len() as jsize, result_class, JObject::null())?;
+
+    for (i, result) in results.iter().enumerate() {
+        let obj = disasm_result_to_jobject(env, result)?;
+        env.set_object_array_element(&array, i as jsize, obj)?;
+    }
+
+    Ok(array.into_raw())
+}
+
+/// Shared body of the byte-array entry points that differ only in their [`DisasmOptions`]
+fn disassemble_bytes_to_jarray(
+    env: &mut JNIEnv,
+    arch: jint,
+    bytes: &JByteArray,
+    address: jlong,
+    count: jint,
+    options: &DisasmOptions,
+) -> JniResult<jobjectArray> {
+    debug!("Disassemble: arch={}, address=0x{:x}, count={}, options={:?}", arch, address, count, options);
+
+    let architecture = Architecture::from_i32(arch)?;
+    let byte_array = env.convert_byte_array(bytes)?;
+
+    let results = disassemble_with(architecture, &byte_array, address as u64, count as usize, options)
+        .context("Disassembly failed")?;
+
+    debug!("Disassembled {} instructions", results.len());
+    disasm_results_to_jarray(env, &results)
+}
+
 #[jni_method(
     85,
     "moe/fuqiuluo/mamu/driver/Disassembler",
@@ -61,30 +170,213 @@ pub fn jni_disassemble(
     bytes: JByteArray,
     address: jlong,
     count: jint,
+) -> jobjectArray {
+    disassemble_bytes_to_jarray(&mut env, arch, &bytes, address, count, &DisasmOptions::default()).or_throw(&mut env)
+}
+
+#[jni_method(
+    85,
+    "moe/fuqiuluo/mamu/driver/Disassembler",
+    "nativeDisassembleEndian",
+    "(I[BJIZ)[Lmoe/fuqiuluo/mamu/driver/DisassemblyResult;"
+)]
+pub fn jni_disassemble_endian(
+    mut env: JNIEnv,
+    _obj: JObject,
+    arch: jint,
+    bytes: JByteArray,
+    address: jlong,
+    count: jint,
+    big_endian: jboolean,
+) -> jobjectArray {
+    let options = DisasmOptions { big_endian: big_endian == JNI_TRUE, ..Default::default() };
+    disassemble_bytes_to_jarray(&mut env, arch, &bytes, address, count, &options).or_throw(&mut env)
+}
+
+#[jni_method(
+    85,
+    "moe/fuqiuluo/mamu/driver/Disassembler",
+    "nativeDisassembleWithSyntax",
+    "(I[BJIIZ)[Lmoe/fuqiuluo/mamu/driver/DisassemblyResult;"
+)]
+#[allow(clippy::too_many_arguments)] // Parameters mirror the Kotlin external declaration
+pub fn jni_disassemble_with_syntax(
+    mut env: JNIEnv,
+    _obj: JObject,
+    arch: jint,
+    bytes: JByteArray,
+    address: jlong,
+    count: jint,
+    syntax: jint,
+    pseudo: jboolean,
 ) -> jobjectArray {
     (|| -> JniResult<jobjectArray> {
-        debug!("Disassemble: arch={}, address=0x{:x}, count={}", arch, address, count);
+        let options = DisasmOptions {
+            pseudo: pseudo == JNI_TRUE,
+            syntax: DisasmSyntax::from_i32(syntax)?,
+            ..Default::default()
+        };
+        disassemble_bytes_to_jarray(&mut env, arch, &bytes, address, count, &options)
+    })()
+    .or_throw(&mut env)
+}
 
-        // Convert architecture
-        let architecture = Architecture::from_i32(arch)
-            .map_err(|e| anyhow!("Invalid architecture: {}", e))?;
+/// Returns the instructions preceding `target_address`, decoded forward from `window_start`.
+#[jni_method(
+    85,
+    "moe/fuqiuluo/mamu/driver/Disassembler",
+    "nativeDisassembleBackward",
+    "(I[BJJI)[Lmoe/fuqiuluo/mamu/driver/DisassemblyResult;"
+)]
+pub fn jni_disassemble_backward(
+    mut env: JNIEnv,
+    _obj: JObject,
+    arch: jint,
+    bytes: JByteArray,
+    window_start: jlong,
+    target_address: jlong,
+    max_insns: jint,
+) -> jobjectArray {
+    (|| -> JniResult<jobjectArray> {
+        debug!(
+            "Disassemble backward: arch={}, window_start=0x{:x}, target=0x{:x}, max_insns={}",
+            arch, window_start, target_address, max_insns
+        );
 
-        // Get bytes
+        let architecture = Architecture::from_i32(arch)?;
         let byte_array = env.convert_byte_array(&bytes)?;
 
-        // Disassemble
-        let results = disassemble(architecture, &byte_array, address as u64, count as usize)
-            .map_err(|e| anyhow!("Disassembly failed: {}", e))?;
+        let results = disassemble_backward(
+            architecture,
+            &byte_array,
+            window_start as u64,
+            target_address as u64,
+            max_insns.max(0) as usize,
+        )
+        .context("Disassembly failed")?;
 
         debug!("Disassembled {} instructions", results.len());
+        disasm_results_to_jarray(&mut env, &results)
+    })()
+    .or_throw(&mut env)
+}
 
-        // Create result array
-        let result_class = env.find_class("moe/fuqiuluo/mamu/driver/DisassemblyResult")?;
-        let array = env.new_object_array(results.len() as jsize, result_class, JObject::null())?;
+#[jni_method(
+    85,
+    "moe/fuqiuluo/mamu/driver/Disassembler",
+    "nativeDisassembleSkipData",
+    "(I[BJI)[Lmoe/fuqiuluo/mamu/driver/DisassemblyResult;"
+)]
+pub fn jni_disassemble_skipdata(
+    mut env: JNIEnv,
+    _obj: JObject,
+    arch: jint,
+    bytes: JByteArray,
+    address: jlong,
+    count: jint,
+) -> jobjectArray {
+    let options = DisasmOptions { skipdata: true, ..Default::default() };
+    disassemble_bytes_to_jarray(&mut env, arch, &bytes, address, count, &options).or_throw(&mut env)
+}
 
-        // Fill array
-        for (i, result) in results.iter().enumerate() {
-            let obj = disasm_result_to_jobject(&mut env, result)?;
+#[jni_method(
+    85,
+    "moe/fuqiuluo/mamu/driver/Disassembler",
+    "nativeDisassembleAddress",
+    "(IJII)[Lmoe/fuqiuluo/mamu/driver/DisassemblyResult;"
+)]
+pub fn jni_disassemble_address(
+    mut env: JNIEnv,
+    _obj: JObject,
+    arch: jint,
+    address: jlong,
+    length: jint,
+    count: jint,
+) -> jobjectArray {
+    (|| -> JniResult<jobjectArray> {
+        debug!("Disassemble address: arch={}, address=0x{:x}, length={}, count={}", arch, address, length, count);
+
+        let architecture = Architecture::from_i32(arch)?;
+        if !(0..=MAX_ADDRESS_READ_LEN).contains(&length) {
+            return Err(JniErrorCode::InvalidArgument.error(format!(
+                "Invalid length: {} (must be between 0 and {})",
+                length, MAX_ADDRESS_READ_LEN
+            )));
+        }
+
+        // Read from the bound process, truncating at the first unreadable page.
+        // A zero length (scrolled to the end of a region) yields an empty result.
+        let mut byte_array = vec![0u8; length as usize];
+        let readable = if byte_array.is_empty() {
+            0
+        } else {
+            let driver_manager = DRIVER_MANAGER
+                .read()
+                .map_err(|_| anyhow!("Failed to acquire DriverManager read lock"))?;
+            driver_manager.read_memory_prefix(address as u64, &mut byte_array)?
+        };
+        if readable < byte_array.len() {
+            debug!("Partial read at 0x{:x}: {} of {} bytes", address, readable, byte_array.len());
+        }
+        byte_array.truncate(readable);
+
+        let results = disassemble(architecture, &byte_array, address as u64, count as usize)
+            .context("Disassembly failed")?;
+        disasm_results_to_jarray(&mut env, &results)
+    })()
+    .or_throw(&mut env)
+}
+
+/// Returns the listing as one text block, avoiding a Java object per instruction.
+#[jni_method(85, "moe/fuqiuluo/mamu/driver/Disassembler", "nativeDisassembleText", "(I[BJIZ)Ljava/lang/String;")]
+pub fn jni_disassemble_text(
+    mut env: JNIEnv,
+    _obj: JObject,
+    arch: jint,
+    bytes: JByteArray,
+    address: jlong,
+    count: jint,
+    with_pseudo: jboolean,
+) -> jstring {
+    (|| -> JniResult<jstring> {
+        let architecture = Architecture::from_i32(arch)?;
+        let byte_array = env.convert_byte_array(&bytes)?;
+
+        let results = if with_pseudo == JNI_TRUE {
+            disassemble_with_pseudo(architecture, &byte_array, address as u64, count as usize)
+        } else {
+            disassemble(architecture, &byte_array, address as u64, count as usize)
+        }
+        .context("Disassembly failed")?;
+
+        debug!("Formatted {} instructions as text", results.len());
+        Ok(env.new_string(format_listing(&results))?.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+#[jni_method(85, "moe/fuqiuluo/mamu/driver/Disassembler", "nativeHexDump", "([BJI)[Lmoe/fuqiuluo/mamu/driver/HexDumpRow;")]
+pub fn jni_hexdump(mut env: JNIEnv, _obj: JObject, bytes: JByteArray, address: jlong, bytes_per_row: jint) -> jobjectArray {
+    (|| -> JniResult<jobjectArray> {
+        if bytes_per_row <= 0 {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid bytes per row: {}", bytes_per_row)));
+        }
+        let byte_array = env.convert_byte_array(&bytes)?;
+        let rows = hexdump(&byte_array, address as u64, bytes_per_row as usize)?;
+
+        let row_class = env.find_class("moe/fuqiuluo/mamu/driver/HexDumpRow")?;
+        let array = env.new_object_array(rows.len() as jsize, &row_class, JObject::null())?;
+        for (i, row) in rows.iter().enumerate() {
+            let bytes_hex = row.bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" ");
+            let bytes_str = env.new_string(bytes_hex)?;
+            let ascii_str = env.new_string(&row.ascii)?;
+
+            // HexDumpRow(address: Long, bytes: String, ascii: String)
+            let obj = env.new_object(
+                &row_class,
+                "(JLjava/lang/String;Ljava/lang/String;)V",
+                &[(row.address as jlong).into(), (&bytes_str).into(), (&ascii_str).into()],
+            )?;
             env.set_object_array_element(&array, i as jsize, obj)?;
         }
 
@@ -93,11 +385,89 @@ pub fn jni_disassemble(
     .or_throw(&mut env)
 }
 
+#[jni_method(85, "moe/fuqiuluo/mamu/driver/Disassembler", "nativeDetectArmMode", "([BJ)I")]
+pub fn jni_detect_arm_mode(mut env: JNIEnv, _obj: JObject, bytes: JByteArray, address: jlong) -> jint {
+    (|| -> JniResult<jint> {
+        let byte_array = env.convert_byte_array(&bytes)?;
+        let arch = detect_arm_mode(&byte_array, address as u64).context("ARM mode detection failed")?;
+
+        debug!("Detected {:?} for {} bytes at 0x{:x}", arch, byte_array.len(), address);
+        Ok(arch as jint)
+    })()
+    .or_throw(&mut env)
+}
+
+/// Describes the first instruction in the bytes, or returns null if it cannot be decoded.
+#[jni_method(
+    85,
+    "moe/fuqiuluo/mamu/driver/Disassembler",
+    "nativeDescribeInstruction",
+    "(I[BJ)Lmoe/fuqiuluo/mamu/driver/InstructionDescription;"
+)]
+pub fn jni_describe_instruction(mut env: JNIEnv, _obj: JObject, arch: jint, bytes: JByteArray, address: jlong) -> jobject {
+    (|| -> JniResult<jobject> {
+        let architecture = Architecture::from_i32(arch)?;
+        let byte_array = env.convert_byte_array(&bytes)?;
+        let Some(description) = describe_instruction(architecture, &byte_array, address as u64).context("Disassembly failed")? else {
+            return Ok(JObject::null().into_raw());
+        };
+
+        let insn = &description.instruction;
+        let mnemonic_str = env.new_string(&insn.mnemonic)?;
+        let operands_str = env.new_string(&insn.operands)?;
+        let category_str = env.new_string(description.category.as_str())?;
+        let target = match description.target {
+            Some(target) => env.new_object("java/lang/Long", "(J)V", &[(target as jlong).into()])?,
+            None => JObject::null(),
+        };
+
+        // InstructionDescription(address: Long, mnemonic: String, operands: String, category: String,
+        //                        branchTarget: Long?, size: Int)
+        let obj = env.new_object(
+            "moe/fuqiuluo/mamu/driver/InstructionDescription",
+            "(JLjava/lang/String;Ljava/lang/String;Ljava/lang/String;Ljava/lang/Long;I)V",
+            &[
+                (insn.address as jlong).into(),
+                (&mnemonic_str).into(),
+                (&operands_str).into(),
+                (&category_str).into(),
+                (&target).into(),
+                (insn.size() as jint).into(),
+            ],
+        )?;
+        Ok(obj.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+/// Returns the addresses of likely function starts (prologues) in the bytes.
+#[jni_method(85, "moe/fuqiuluo/mamu/driver/Disassembler", "nativeFindFunctionStarts", "(I[BJ)[J")]
+pub fn jni_find_function_starts(mut env: JNIEnv, _obj: JObject, arch: jint, bytes: JByteArray, address: jlong) -> jlongArray {
+    (|| -> JniResult<jlongArray> {
+        let architecture = Architecture::from_i32(arch)?;
+        let byte_array = env.convert_byte_array(&bytes)?;
+
+        let results = disassemble_skipdata(architecture, &byte_array, address as u64, 0).context("Disassembly failed")?;
+        let starts: Vec<jlong> = find_function_boundaries(&results)
+            .iter()
+            .filter(|boundary| boundary.kind == BoundaryKind::Prologue)
+            .map(|boundary| results[boundary.index].address as jlong)
+            .collect();
+
+        debug!("Found {} function starts in {} instructions", starts.len(), results.len());
+
+        let array = env.new_long_array(starts.len() as jsize)?;
+        env.set_long_array_region(&array, 0, &starts)?;
+        Ok(array.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
 #[jni_method(
     85,
     "moe/fuqiuluo/mamu/driver/Disassembler",
     "nativeGeneratePseudoCode",
-    "(I[BJI)[Lmoe/fuqiuluo/mamu/driver/DisassemblyResult;"
+    "(I[BJIZ)[Lmoe/fuqiuluo/mamu/driver/DisassemblyResult;"
 )]
 pub fn jni_generate_pseudo_code(
     mut env: JNIEnv,
@@ -106,37 +476,77 @@ pub fn jni_generate_pseudo_code(
     bytes: JByteArray,
     address: jlong,
     count: jint,
+    with_source: jboolean,
 ) -> jobjectArray {
     (|| -> JniResult<jobjectArray> {
+        let with_source = with_source == JNI_TRUE;
         debug!(
-            "Generate pseudo-code: arch={}, address=0x{:x}, count={}",
-            arch, address, count
+            "Generate pseudo-code: arch={}, address=0x{:x}, count={}, with_source={}",
+            arch, address, count, with_source
         );
 
         // Convert architecture
-        let architecture = Architecture::from_i32(arch)
-            .map_err(|e| anyhow!("Invalid architecture: {}", e))?;
+        let architecture = Architecture::from_i32(arch)?;
 
         // Get bytes
         let byte_array = env.convert_byte_array(&bytes)?;
 
         // Disassemble with pseudo-code
-        let results = disassemble_with_pseudo(architecture, &byte_array, address as u64, count as usize)
-            .map_err(|e| anyhow!("Pseudo-code generation failed: {}", e))?;
+        let results = if with_source {
+            disassemble_with_pseudo_source(architecture, &byte_array, address as u64, count as usize)
+        } else {
+            disassemble_with_pseudo(architecture, &byte_array, address as u64, count as usize)
+        }
+        .context("Pseudo-code generation failed")?;
 
         debug!("Generated pseudo-code for {} instructions", results.len());
+        disasm_results_to_jarray(&mut env, &results)
+    })()
+    .or_throw(&mut env)
+}
 
-        // Create result array
-        let result_class = env.find_class("moe/fuqiuluo/mamu/driver/DisassemblyResult")?;
-        let array = env.new_object_array(results.len() as jsize, result_class, JObject::null())?;
+/// Disassembles with direct branch and call targets replaced by names from a symbol table,
+/// given as parallel address and name arrays.
+#[jni_method(
+    85,
+    "moe/fuqiuluo/mamu/driver/Disassembler",
+    "nativeDisassembleWithSymbols",
+    "(I[BJIZ[J[Ljava/lang/String;)[Lmoe/fuqiuluo/mamu/driver/DisassemblyResult;"
+)]
+#[allow(clippy::too_many_arguments)] // Parameters mirror the Kotlin external declaration
+pub fn jni_disassemble_with_symbols(
+    mut env: JNIEnv,
+    _obj: JObject,
+    arch: jint,
+    bytes: JByteArray,
+    address: jlong,
+    count: jint,
+    pseudo: jboolean,
+    symbol_addresses: JLongArray,
+    symbol_names: JObjectArray,
+) -> jobjectArray {
+    (|| -> JniResult<jobjectArray> {
+        let architecture = Architecture::from_i32(arch)?;
+        let byte_array = env.convert_byte_array(&bytes)?;
 
-        // Fill array
-        for (i, result) in results.iter().enumerate() {
-            let obj = disasm_result_to_jobject(&mut env, result)?;
-            env.set_object_array_element(&array, i as jsize, obj)?;
+        let symbol_count = env.get_array_length(&symbol_addresses)?;
+        if env.get_array_length(&symbol_names)? != symbol_count {
+            return Err(JniErrorCode::InvalidArgument.error("Symbol addresses and names must have the same length"));
+        }
+        let mut addresses = vec![0i64; symbol_count as usize];
+        env.get_long_array_region(&symbol_addresses, 0, &mut addresses)?;
+        let mut symbols = HashMap::with_capacity(addresses.len());
+        for (i, symbol_address) in addresses.into_iter().enumerate() {
+            let name = JString::from(env.get_object_array_element(&symbol_names, i as jsize)?);
+            let name: String = env.get_string(&name)?.into();
+            symbols.insert(symbol_address as u64, name);
         }
 
-        Ok(array.into_raw())
+        let options = DisasmOptions { pseudo: pseudo == JNI_TRUE, ..Default::default() };
+        let results = disassemble_with_symbols(architecture, &byte_array, address as u64, count as usize, &options, &symbols)
+            .context("Disassembly failed")?;
+        debug!("Disassembled {} instructions with {} symbols", results.len(), symbols.len());
+        disasm_results_to_jarray(&mut env, &results)
     })()
     .or_throw(&mut env)
 }
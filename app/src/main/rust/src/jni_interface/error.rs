@@ -0,0 +1,26 @@
+//! JNI methods for NativeErrors
+
+use crate::ext::jni::{JniResult, JniResultExt, last_error};
+use jni::JNIEnv;
+use jni::objects::{JObject, JValue};
+use jni::sys::jobject;
+use jni_macro::jni_method;
+
+/// Returns the last error recorded on the calling thread, or null if no call has failed yet.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/NativeErrors", "nativeLastError", "()Lmoe/fuqiuluo/mamu/driver/NativeError;")]
+pub fn jni_last_error(mut env: JNIEnv, _obj: JObject) -> jobject {
+    (|| -> JniResult<jobject> {
+        let Some((code, message)) = last_error() else {
+            return Ok(JObject::null().into_raw());
+        };
+
+        let class = env.find_class("moe/fuqiuluo/mamu/driver/NativeError")?;
+        let message_str = env.new_string(message)?;
+
+        // data class NativeError(val code: Int, val message: String)
+        let obj = env.new_object(class, "(ILjava/lang/String;)V", &[JValue::Int(code as i32), JValue::Object(&message_str)])?;
+
+        Ok(obj.into_raw())
+    })()
+    .or_throw(&mut env)
+}
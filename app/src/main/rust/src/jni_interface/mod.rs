@@ -7,4 +7,5 @@ pub mod driver;
 pub mod search;
 pub mod mem_ops;
 pub mod disassembler;
-pub mod driver_installer;
\ No newline at end of file
+pub mod driver_installer;
+pub mod error;
\ No newline at end of file
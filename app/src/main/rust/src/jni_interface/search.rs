@@ -1,15 +1,15 @@
 //! JNI methods for SearchEngine.
 
 use crate::core::DRIVER_MANAGER;
-use crate::ext::jni::{JniResult, JniResultExt};
+use crate::ext::jni::{JniErrorCode, JniResult, JniResultExt};
 use crate::search::SearchResultItem;
-use crate::search::engine::{SEARCH_ENGINE_MANAGER, SHARED_BUFFER_SIZE, SearchProgressCallback};
+use crate::search::engine::{SEARCH_ENGINE_MANAGER, SHARED_BUFFER_SIZE, SearchEngineManager, SearchProgressCallback};
 use crate::search::parser::parse_search_query;
-use crate::search::result_manager::SearchResultMode;
-use crate::search::types::ValueType;
+use crate::search::result_manager::{FuzzySearchResultManager, SearchResultMode};
+use crate::search::types::{ByteOrder, ValueType};
 use anyhow::anyhow;
-use jni::objects::{GlobalRef, JIntArray, JLongArray, JObject, JString, JValue};
-use jni::sys::{JNI_FALSE, JNI_TRUE, jboolean, jint, jlong, jobjectArray};
+use jni::objects::{GlobalRef, JByteArray, JIntArray, JLongArray, JObject, JObjectArray, JString, JValue};
+use jni::sys::{JNI_FALSE, JNI_TRUE, jboolean, jint, jintArray, jlong, jlongArray, jobject, jobjectArray, jstring};
 use jni::{JNIEnv, JavaVM};
 use jni_macro::jni_method;
 use log::{Level, error, log_enabled, warn};
@@ -52,62 +52,12 @@ fn jint_to_value_type(value: jint) -> Option<ValueType> {
         5 => Some(ValueType::Double),
         6 => Some(ValueType::Auto),
         7 => Some(ValueType::Xor),
+        100 => Some(ValueType::String),
+        102 => Some(ValueType::Bytes),
         _ => None,
     }
 }
 
-fn format_value(bytes: &[u8], typ: ValueType) -> String {
-    match typ {
-        ValueType::Byte => {
-            if bytes.len() >= 1 {
-                format!("{}", bytes[0])
-            } else {
-                "N/A".to_string()
-            }
-        },
-        ValueType::Word => {
-            if bytes.len() >= 2 {
-                let value = u16::from_le_bytes([bytes[0], bytes[1]]);
-                format!("{}", value)
-            } else {
-                "N/A".to_string()
-            }
-        },
-        ValueType::Dword | ValueType::Auto | ValueType::Xor => {
-            if bytes.len() >= 4 {
-                let value = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
-                format!("{}", value)
-            } else {
-                "N/A".to_string()
-            }
-        },
-        ValueType::Qword => {
-            if bytes.len() >= 8 {
-                let value = u64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]);
-                format!("{}", value)
-            } else {
-                "N/A".to_string()
-            }
-        },
-        ValueType::Float => {
-            if bytes.len() >= 4 {
-                let value = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
-                format!("{}", value)
-            } else {
-                "N/A".to_string()
-            }
-        },
-        ValueType::Double => {
-            if bytes.len() >= 8 {
-                let value = f64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]);
-                format!("{}", value)
-            } else {
-                "N/A".to_string()
-            }
-        },
-    }
-}
-
 #[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeInitSearchEngine", "(JLjava/lang/String;J)Z")]
 pub fn jni_init_search_engine(mut env: JNIEnv, _class: JObject, memory_buffer_size: jlong, cache_dir: JString, chunk_size: jlong) -> jboolean {
     (|| -> JniResult<jboolean> {
@@ -177,7 +127,7 @@ pub fn jni_start_search_async(
     (|| -> JniResult<jboolean> {
         let query: String = env.get_string(&query_str)?.into();
 
-        let value_type = jint_to_value_type(default_type).ok_or_else(|| anyhow!("Invalid value type: {}", default_type))?;
+        let value_type = jint_to_value_type(default_type).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type: {}", default_type)))?;
 
         let search_query = parse_search_query(&query, value_type).map_err(|e| anyhow!("Parse error: {}", e))?;
 
@@ -202,13 +152,39 @@ pub fn jni_start_search_async(
     .or_throw(&mut env)
 }
 
+/// Synchronous signature (AOB) search, e.g. `48 8B ?? ?? 89`. Replaces current results with exact
+/// results of type Bytes and returns the match count.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSignatureSearch", "(Ljava/lang/String;[J)J")]
+pub fn jni_signature_search(mut env: JNIEnv, _class: JObject, pattern: JString, regions: JLongArray) -> jlong {
+    (|| -> JniResult<jlong> {
+        let pattern: String = env.get_string(&pattern)?.into();
+
+        let regions_len = env.get_array_length(&regions)? as usize;
+        if !regions_len.is_multiple_of(2) {
+            return Err(anyhow!("Regions array length must be even"));
+        }
+
+        let mut regions_buf = vec![0i64; regions_len];
+        env.get_long_array_region(&regions, 0, &mut regions_buf)?;
+
+        let memory_regions: Vec<(u64, u64)> = regions_buf.chunks(2).map(|chunk| (chunk[0] as u64, chunk[1] as u64)).collect();
+
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(manager.signature_search(&pattern, &memory_regions)? as jlong)
+    })()
+    .or_throw(&mut env)
+}
+
 /// Starts an async refine search. Returns immediately.
 #[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeStartRefineAsync", "(Ljava/lang/String;I)Z")]
 pub fn jni_start_refine_async(mut env: JNIEnv, _class: JObject, query_str: JString, default_type: jint) -> jboolean {
     (|| -> JniResult<jboolean> {
         let query: String = env.get_string(&query_str)?.into();
 
-        let value_type = jint_to_value_type(default_type).ok_or_else(|| anyhow!("Invalid value type: {}", default_type))?;
+        let value_type = jint_to_value_type(default_type).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type: {}", default_type)))?;
 
         let search_query = parse_search_query(&query, value_type).map_err(|e| anyhow!("Parse error: {}", e))?;
 
@@ -269,7 +245,7 @@ pub fn jni_search(
     (|| -> JniResult<jlong> {
         let query: String = env.get_string(&query_str)?.into();
 
-        let value_type = jint_to_value_type(default_type).ok_or_else(|| anyhow!("Invalid value type: {}", default_type))?;
+        let value_type = jint_to_value_type(default_type).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type: {}", default_type)))?;
 
         let search_query = parse_search_query(&query, value_type).map_err(|e| anyhow!("Parse error: {}", e))?;
 
@@ -325,7 +301,7 @@ pub fn jni_get_results(mut env: JNIEnv, _class: JObject, start: jint, size: jint
             // Diagnostic log - always print to help debug timing issues
             warn!("[DIAG] jni_get_results: mode={:?}, total_count={}, requesting start={}, size={}", current_mode, total_count, start, size);
         }
-        let mut results = search_manager
+        let results = search_manager
             .get_results(start as usize, size as usize)?
             .into_iter()
             .enumerate()
@@ -335,109 +311,271 @@ pub fn jni_get_results(mut env: JNIEnv, _class: JObject, start: jint, size: jint
         if log_enabled!(Level::Debug) {
             warn!("[DIAG] jni_get_results: got {} results", results.len());
         }
-        let filter = search_manager.get_filter();
-        if filter.is_active() {
-            results = results
-                .into_iter()
-                .filter(|(_idx, item)| {
-                    if filter.enable_address_filter {
-                        let addr = match item {
-                            SearchResultItem::Exact(exact) => exact.address,
-                            SearchResultItem::Fuzzy(fuzzy) => fuzzy.address,
-                        };
-                        if addr < filter.address_start || addr > filter.address_end {
-                            return false;
-                        }
-                    }
 
-                    if filter.enable_type_filter && filter.type_ids.is_empty().not() {
-                        let typ = match item {
-                            SearchResultItem::Exact(exact) => exact.typ,
-                            SearchResultItem::Fuzzy(fuzzy) => fuzzy.value_type,
-                        };
-                        if !filter.type_ids.contains(&typ) {
-                            return false;
-                        }
-                    }
+        Ok(build_result_array(&mut env, &search_manager, results)?.into_raw())
+    })()
+    .or_throw(&mut env)
+}
 
-                    true
-                })
-                .collect::<Vec<(usize, SearchResultItem)>>();
+/// 按当前值排序获取模糊结果，nativePosition 仍为结果在地址顺序中的全局索引
+#[jni_method(
+    70,
+    "moe/fuqiuluo/mamu/driver/SearchEngine",
+    "nativeGetResultsSortedByValue",
+    "(ZII)[Lmoe/fuqiuluo/mamu/driver/SearchResultItem;"
+)]
+pub fn jni_get_results_sorted_by_value(mut env: JNIEnv, _class: JObject, descending: jboolean, start: jint, size: jint) -> jobjectArray {
+    (|| -> JniResult<jobjectArray> {
+        if start < 0 || size < 0 {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid range start={} size={}", start, size)));
         }
 
-        // 根据模式选择不同的 Java 类
-        let (class, is_fuzzy) = match current_mode {
-            SearchResultMode::Exact => {
-                (env.find_class("moe/fuqiuluo/mamu/driver/ExactSearchResultItem")?, false)
-            },
-            SearchResultMode::Fuzzy => {
-                (env.find_class("moe/fuqiuluo/mamu/driver/FuzzySearchResultItem")?, true)
-            },
-        };
+        let search_manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        let results = search_manager.get_results_sorted_by_value(descending != JNI_FALSE, start as usize, size as usize)?;
+
+        Ok(build_result_array(&mut env, &search_manager, results)?.into_raw())
+    })()
+    .or_throw(&mut env)
+}
 
-        let array = env.new_object_array(results.len() as jint, &class, JObject::null())?;
+/// 获取一页结果，页码超出范围时取最后一页
+/// 返回 SearchResultPage(items, page, totalPages)，items 的 nativePosition 为结果的全局索引
+#[jni_method(
+    70,
+    "moe/fuqiuluo/mamu/driver/SearchEngine",
+    "nativeGetResultPage",
+    "(II)Lmoe/fuqiuluo/mamu/driver/SearchResultPage;"
+)]
+pub fn jni_get_result_page(mut env: JNIEnv, _class: JObject, page: jint, page_size: jint) -> jobject {
+    (|| -> JniResult<jobject> {
+        if page < 0 || page_size <= 0 {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid page {} with size {}", page, page_size)));
+        }
 
-        let driver_manager = DRIVER_MANAGER.read().map_err(|_| anyhow!("Failed to acquire DriverManager read lock"))?;
+        let search_manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
 
-        for (i, (native_position, item)) in results.into_iter().enumerate() {
-            let obj = match item {
-                SearchResultItem::Exact(exact) => {
-                    let value_str = {
-                        let size = exact.typ.size();
-                        let mut buffer = vec![0u8; size];
+        let (items, total_pages) = search_manager.get_page(page as usize, page_size as usize)?;
+        let page = (page as usize).min(total_pages.saturating_sub(1));
+        let results = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| (page * page_size as usize + index, item))
+            .collect::<Vec<(usize, SearchResultItem)>>();
 
-                        if driver_manager.read_memory_unified(exact.address, &mut buffer, None).is_ok() {
-                            format_value(&buffer, exact.typ)
-                        } else {
-                            "N/A".to_string()
-                        }
-                    };
+        let array = build_result_array(&mut env, &search_manager, results)?;
+        let page_obj = env.new_object(
+            "moe/fuqiuluo/mamu/driver/SearchResultPage",
+            "([Lmoe/fuqiuluo/mamu/driver/SearchResultItem;II)V",
+            &[JValue::Object(&array), JValue::Int(page as jint), JValue::Int(total_pages as jint)],
+        )?;
+        Ok(page_obj.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+/// 打开结果游标，用于分批读取大量结果而不一次性构造巨大的数组，用完须调用 nativeCloseCursor
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeOpenResultCursor", "()J")]
+pub fn jni_open_result_cursor(mut env: JNIEnv, _class: JObject) -> jlong {
+    (|| -> JniResult<jlong> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
 
-                    let value_jstring = env.new_string(&value_str)?;
-
-                    env.new_object(
-                        &class,
-                        "(JJILjava/lang/String;)V",
-                        &[
-                            JValue::Long(native_position as i64),
-                            JValue::Long(exact.address as i64),
-                            JValue::Int(exact.typ.to_id()),
-                            JValue::Object(&value_jstring),
-                        ],
-                    )?
-                },
-                SearchResultItem::Fuzzy(fuzzy) => {
-                    let buffer = fuzzy.value.as_ref();
-                    let current_value_str = format_value(&buffer, fuzzy.value_type);
-
-                    let current_value_jstring = env.new_string(&current_value_str)?;
-
-                    // data class FuzzySearchResultItem(
-                    //     override val nativePosition: Long,
-                    //     val address: Long,
-                    //     val value: String,
-                    //     val valueType: Int
-                    // ): SearchResultItem
-                    env.new_object(
-                        &class,
-                        "(JJLjava/lang/String;I)V",
-                        &[
-                            JValue::Long(native_position as i64),
-                            JValue::Long(fuzzy.address as i64),
-                            JValue::Object(&current_value_jstring),
-                            JValue::Int(fuzzy.value_type.to_id()),
-                        ],
-                    )?
-                },
-            };
-            env.set_object_array_element(&array, i as jint, obj)?;
+        Ok(manager.open_result_cursor()? as jlong)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 从游标读取下一批至多 batchSize 个结果，nativePosition 为结果的全局索引
+/// 批次经过当前过滤器筛选，可能少于 batchSize 甚至为空；全部读完后返回 null
+#[jni_method(
+    70,
+    "moe/fuqiuluo/mamu/driver/SearchEngine",
+    "nativeCursorNext",
+    "(JI)[Lmoe/fuqiuluo/mamu/driver/SearchResultItem;"
+)]
+pub fn jni_cursor_next(mut env: JNIEnv, _class: JObject, handle: jlong, batch_size: jint) -> jobjectArray {
+    (|| -> JniResult<jobjectArray> {
+        if batch_size <= 0 {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid batch size: {}", batch_size)));
         }
 
-        Ok(array.into_raw())
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        let results = manager.cursor_next(handle as u64, batch_size as usize)?;
+        if results.is_empty() {
+            return Ok(JObject::null().into_raw());
+        }
+
+        Ok(build_result_array(&mut env, &manager, results)?.into_raw())
     })()
     .or_throw(&mut env)
 }
 
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeCloseCursor", "(J)Z")]
+pub fn jni_close_cursor(mut env: JNIEnv, _class: JObject, handle: jlong) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(if manager.close_result_cursor(handle as u64) { JNI_TRUE } else { JNI_FALSE })
+    })()
+    .or_throw(&mut env)
+}
+
+/// 按当前过滤器筛选结果并转换为 Java 结果数组，`results` 中的索引作为 nativePosition
+fn build_result_array<'local>(
+    env: &mut JNIEnv<'local>,
+    search_manager: &SearchEngineManager,
+    mut results: Vec<(usize, SearchResultItem)>,
+) -> JniResult<JObjectArray<'local>> {
+    let current_mode = search_manager.get_current_mode()?;
+    let filter = search_manager.get_filter();
+    if filter.is_active() {
+        results = results
+            .into_iter()
+            .filter(|(_idx, item)| {
+                if filter.enable_address_filter {
+                    let addr = match item {
+                        SearchResultItem::Exact(exact) => exact.address,
+                        SearchResultItem::Fuzzy(fuzzy) => fuzzy.address,
+                    };
+                    if addr < filter.address_start || addr > filter.address_end {
+                        return false;
+                    }
+                }
+
+                if filter.enable_type_filter && filter.type_ids.is_empty().not() {
+                    let typ = match item {
+                        SearchResultItem::Exact(exact) => exact.typ,
+                        SearchResultItem::Fuzzy(fuzzy) => fuzzy.value_type,
+                    };
+                    if !filter.type_ids.contains(&typ) {
+                        return false;
+                    }
+                }
+
+                true
+            })
+            .collect::<Vec<(usize, SearchResultItem)>>();
+    }
+
+    // 根据模式选择不同的 Java 类
+    let (class, is_fuzzy) = match current_mode {
+        SearchResultMode::Exact => {
+            (env.find_class("moe/fuqiuluo/mamu/driver/ExactSearchResultItem")?, false)
+        },
+        SearchResultMode::Fuzzy => {
+            (env.find_class("moe/fuqiuluo/mamu/driver/FuzzySearchResultItem")?, true)
+        },
+    };
+
+    let array = env.new_object_array(results.len() as jint, &class, JObject::null())?;
+
+    let driver_manager = DRIVER_MANAGER.read().map_err(|_| anyhow!("Failed to acquire DriverManager read lock"))?;
+
+    for (i, (native_position, item)) in results.into_iter().enumerate() {
+        let obj = match item {
+            SearchResultItem::Exact(exact) => {
+                let value_str = {
+                    let size = exact.typ.size();
+                    let mut buffer = vec![0u8; size];
+
+                    if driver_manager.read_memory_unified(exact.address, &mut buffer, None).is_ok() {
+                        exact.format_value(&buffer)
+                    } else {
+                        "N/A".to_string()
+                    }
+                };
+
+                let value_jstring = env.new_string(&value_str)?;
+
+                env.new_object(
+                    &class,
+                    "(JJILjava/lang/String;)V",
+                    &[
+                        JValue::Long(native_position as i64),
+                        JValue::Long(exact.address as i64),
+                        JValue::Int(exact.typ.to_id()),
+                        JValue::Object(&value_jstring),
+                    ],
+                )?
+            },
+            SearchResultItem::Fuzzy(fuzzy) if fuzzy.value_type.is_variable_length() => {
+                // 变长值只有短内容内联保存，其余按记录的长度重新读取
+                let current_bytes = fuzzy.variable_bytes().or_else(|| {
+                    let mut buffer = vec![0u8; fuzzy.value_size()];
+                    driver_manager.read_memory_unified(fuzzy.address, &mut buffer, None).ok().map(|_| buffer)
+                });
+                let current_value_str = match current_bytes {
+                    Some(bytes) => fuzzy.value_type.format_bytes(&bytes),
+                    None => "N/A".to_string(),
+                };
+                let current_value_jstring = env.new_string(&current_value_str)?;
+                let history = search_manager.fuzzy_history(native_position);
+                let previous_value_jstring: JObject = match history.and_then(|history| history.variable_bytes_prev(fuzzy.value_type)) {
+                    Some(bytes) => env.new_string(fuzzy.value_type.format_bytes(&bytes))?.into(),
+                    None => JObject::null(),
+                };
+
+                env.new_object(
+                    &class,
+                    "(JJLjava/lang/String;ILjava/lang/String;I)V",
+                    &[
+                        JValue::Long(native_position as i64),
+                        JValue::Long(fuzzy.address as i64),
+                        JValue::Object(&current_value_jstring),
+                        JValue::Int(fuzzy.value_type.to_id()),
+                        JValue::Object(&previous_value_jstring),
+                        JValue::Int(history.map_or(0, |history| history.tag) as jint),
+                    ],
+                )?
+            },
+            SearchResultItem::Fuzzy(fuzzy) => {
+                let current_value_jstring = env.new_string(fuzzy.format_value())?;
+                // 未开启历史跟踪时没有上一次的值
+                let history = search_manager.fuzzy_history(native_position);
+                let previous_value_jstring: JObject = match history {
+                    Some(history) => env.new_string(history.format_prev_value(fuzzy.value_type))?.into(),
+                    None => JObject::null(),
+                };
+
+                // data class FuzzySearchResultItem(
+                //     override val nativePosition: Long,
+                //     val address: Long,
+                //     val value: String,
+                //     val valueType: Int,
+                //     val previousValue: String?,
+                //     val tag: Int
+                // ): SearchResultItem
+                env.new_object(
+                    &class,
+                    "(JJLjava/lang/String;ILjava/lang/String;I)V",
+                    &[
+                        JValue::Long(native_position as i64),
+                        JValue::Long(fuzzy.address as i64),
+                        JValue::Object(&current_value_jstring),
+                        JValue::Int(fuzzy.value_type.to_id()),
+                        JValue::Object(&previous_value_jstring),
+                        JValue::Int(history.map_or(0, |history| history.tag) as jint),
+                    ],
+                )?
+            },
+        };
+        env.set_object_array_element(&array, i as jint, obj)?;
+    }
+
+    Ok(array)
+}
+
 #[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetTotalResultCount", "()J")]
 pub fn jni_get_total_result_count(mut env: JNIEnv, _class: JObject) -> jlong {
     (|| -> JniResult<jlong> {
@@ -454,6 +592,183 @@ pub fn jni_get_total_result_count(mut env: JNIEnv, _class: JObject) -> jlong {
     .or_throw(&mut env)
 }
 
+/// 导出当前结果到文件，format: 0 = CSV, 1 = JSON
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeExportResults", "(Ljava/lang/String;I)Z")]
+pub fn jni_export_results(mut env: JNIEnv, _class: JObject, path: JString, format: jint) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let path: String = env.get_string(&path)?.into();
+        let as_json = match format {
+            0 => false,
+            1 => true,
+            _ => return Err(JniErrorCode::InvalidArgument.error(format!("Unknown export format: {}", format))),
+        };
+
+        let manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        manager.export_results(std::path::Path::new(&path), as_json)?;
+        Ok(JNI_TRUE)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 比较两个会话文件，返回 SessionDiff(appeared, disappeared, changed)，三组地址均按升序排列
+#[jni_method(
+    70,
+    "moe/fuqiuluo/mamu/driver/SearchEngine",
+    "nativeDiffSessions",
+    "(Ljava/lang/String;Ljava/lang/String;)Lmoe/fuqiuluo/mamu/driver/SessionDiff;"
+)]
+pub fn jni_diff_sessions(mut env: JNIEnv, _class: JObject, old_path: JString, new_path: JString) -> jobject {
+    (|| -> JniResult<jobject> {
+        let old_path: String = env.get_string(&old_path)?.into();
+        let new_path: String = env.get_string(&new_path)?.into();
+
+        let diff = FuzzySearchResultManager::diff_sessions(std::path::Path::new(&old_path), std::path::Path::new(&new_path))?;
+
+        let to_array = |addresses: Vec<u64>| -> JniResult<JLongArray> {
+            let addresses: Vec<jlong> = addresses.into_iter().map(|address| address as jlong).collect();
+            let array = env.new_long_array(addresses.len() as jint)?;
+            env.set_long_array_region(&array, 0, &addresses)?;
+            Ok(array)
+        };
+        let appeared = to_array(diff.appeared)?;
+        let disappeared = to_array(diff.disappeared)?;
+        let changed = to_array(diff.changed)?;
+
+        let diff_obj = env.new_object(
+            "moe/fuqiuluo/mamu/driver/SessionDiff",
+            "([J[J[J)V",
+            &[JValue::Object(&appeared), JValue::Object(&disappeared), JValue::Object(&changed)],
+        )?;
+        Ok(diff_obj.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+/// 保存当前结果的快照（替换之前的快照），返回快照中的结果数量
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSaveResultSnapshot", "()J")]
+pub fn jni_save_result_snapshot(mut env: JNIEnv, _class: JObject) -> jlong {
+    (|| -> JniResult<jlong> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(manager.save_result_snapshot()? as jlong)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 恢复并丢弃最近保存的快照，没有快照时返回 false
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeRestoreResultSnapshot", "()Z")]
+pub fn jni_restore_result_snapshot(mut env: JNIEnv, _class: JObject) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(if manager.restore_result_snapshot()? { JNI_TRUE } else { JNI_FALSE })
+    })()
+    .or_throw(&mut env)
+}
+
+/// 将最近保存的快照追加到当前结果之后并丢弃快照，返回合并后的结果数量，没有快照时返回 -1
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeMergeResultSnapshot", "()J")]
+pub fn jni_merge_result_snapshot(mut env: JNIEnv, _class: JObject) -> jlong {
+    (|| -> JniResult<jlong> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(manager.merge_result_snapshot()?.map_or(-1, |count| count as jlong))
+    })()
+    .or_throw(&mut env)
+}
+
+/// 将模糊结果全部落盘，返回磁盘文件路径，没有结果时返回 null；进程被回收后用该路径调用 nativeRestoreResults 恢复
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativePersistResults", "()Ljava/lang/String;")]
+pub fn jni_persist_results(mut env: JNIEnv, _class: JObject) -> jstring {
+    (|| -> JniResult<jstring> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        match manager.persist_results()? {
+            Some(path) => Ok(env.new_string(path.to_string_lossy())?.into_raw()),
+            None => Ok(std::ptr::null_mut()),
+        }
+    })()
+    .or_throw(&mut env)
+}
+
+/// 从 nativePersistResults 落盘的文件恢复模糊结果，返回恢复的结果数量
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeRestoreResults", "(Ljava/lang/String;)J")]
+pub fn jni_restore_results(mut env: JNIEnv, _class: JObject, path: JString) -> jlong {
+    (|| -> JniResult<jlong> {
+        let path: String = env.get_string(&path)?.into();
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(manager.restore_results(std::path::Path::new(&path))? as jlong)
+    })()
+    .or_throw(&mut env)
+}
+
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeHasResultSnapshot", "()Z")]
+pub fn jni_has_result_snapshot(mut env: JNIEnv, _class: JObject) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        Ok(if manager.has_result_snapshot() { JNI_TRUE } else { JNI_FALSE })
+    })()
+    .or_throw(&mut env)
+}
+
+/// 返回 [memoryBytes, diskBytes]，仅统计存活结果
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetUsageStats", "()[J")]
+pub fn jni_get_usage_stats(mut env: JNIEnv, _class: JObject) -> jlongArray {
+    (|| -> JniResult<jlongArray> {
+        let manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        let stats = manager.get_usage_stats()?;
+        let array = env.new_long_array(2)?;
+        env.set_long_array_region(&array, 0, &[stats.memory_bytes as jlong, stats.disk_bytes as jlong])?;
+        Ok(array.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+/// 一次返回当前模式的结果数量与占用，供频繁刷新的状态栏使用
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetResultStats", "()Lmoe/fuqiuluo/mamu/driver/ResultStats;")]
+pub fn jni_get_result_stats(mut env: JNIEnv, _class: JObject) -> jobject {
+    (|| -> JniResult<jobject> {
+        let manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        let stats = manager.get_usage_stats()?;
+        let obj = env.new_object(
+            "moe/fuqiuluo/mamu/driver/ResultStats",
+            "(JJJJJ)V",
+            &[
+                JValue::Long(stats.total_count as jlong),
+                JValue::Long(stats.memory_count as jlong),
+                JValue::Long(stats.disk_count as jlong),
+                JValue::Long(stats.memory_bytes as jlong),
+                JValue::Long(stats.disk_bytes as jlong),
+            ],
+        )?;
+        Ok(obj.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
 #[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeClearSearchResults", "()V")]
 pub fn jni_clear_result(mut env: JNIEnv, _class: JObject) {
     (|| -> JniResult<()> {
@@ -526,6 +841,242 @@ pub fn jni_keep_only_results(mut env: JNIEnv, _class: JObject, indices_array: JI
     .or_throw(&mut env)
 }
 
+/// 返回与 address 相距不超过 window 字节的结果索引
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeFindNear", "(JJ)[I")]
+pub fn jni_find_near(mut env: JNIEnv, _class: JObject, address: jlong, window: jlong) -> jintArray {
+    (|| -> JniResult<jintArray> {
+        let manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        let indices: Vec<jint> = manager
+            .find_near(address as u64, window as u64)?
+            .into_iter()
+            .map(|(index, _)| index as jint)
+            .collect();
+
+        let array = env.new_int_array(indices.len() as jint)?;
+        env.set_int_array_region(&array, 0, &indices)?;
+        Ok(array.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+/// 返回所有结果的地址，顺序与结果索引一致，用于交给指针扫描
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetResultAddresses", "()[J")]
+pub fn jni_get_result_addresses(mut env: JNIEnv, _class: JObject) -> jlongArray {
+    (|| -> JniResult<jlongArray> {
+        let addresses: Vec<jlong> = {
+            let manager = SEARCH_ENGINE_MANAGER
+                .read()
+                .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+            manager.get_addresses()?.into_iter().map(|address| address as jlong).collect()
+        };
+
+        let array = env.new_long_array(addresses.len() as jint)?;
+        env.set_long_array_region(&array, 0, &addresses)?;
+        Ok(array.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+/// 把地址连续的模糊结果合并为区段，stride 为 0 时使用值大小
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetContiguousRuns", "(I)[Lmoe/fuqiuluo/mamu/driver/AddressRun;")]
+pub fn jni_get_contiguous_runs(mut env: JNIEnv, _class: JObject, stride: jint) -> jobjectArray {
+    (|| -> JniResult<jobjectArray> {
+        if stride < 0 {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid stride: {}", stride)));
+        }
+        let runs = {
+            let manager = SEARCH_ENGINE_MANAGER
+                .read()
+                .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+            manager.contiguous_runs(stride as usize)?
+        };
+
+        let run_class = env.find_class("moe/fuqiuluo/mamu/driver/AddressRun")?;
+        let array = env.new_object_array(runs.len() as jint, &run_class, JObject::null())?;
+        for (i, (start, count)) in runs.into_iter().enumerate() {
+            let run = env.new_object(&run_class, "(JI)V", &[JValue::Long(start as jlong), JValue::Int(count as jint)])?;
+            env.set_object_array_element(&array, i as jint, run)?;
+        }
+
+        Ok(array.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeFilterAddressRange", "(JJ)Z")]
+pub fn jni_filter_address_range(mut env: JNIEnv, _class: JObject, min: jlong, max: jlong) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.filter_address_range(min as u64, max as u64)?;
+
+        Ok(JNI_TRUE)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 与另一组地址求交（如另一次搜索导出的地址），地址无需有序，返回剩余结果数量
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeIntersectResults", "([J)J")]
+pub fn jni_intersect_results(mut env: JNIEnv, _class: JObject, addresses: JLongArray) -> jlong {
+    (|| -> JniResult<jlong> {
+        let addresses_len = env.get_array_length(&addresses)? as usize;
+        let mut addresses_buf = vec![0i64; addresses_len];
+        env.get_long_array_region(&addresses, 0, &mut addresses_buf)?;
+
+        let mut addresses: Vec<u64> = addresses_buf.into_iter().map(|address| address as u64).collect();
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(manager.intersect_results(&addresses)? as jlong)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 用新值更新单个模糊结果（用户修改内存后调用），当前值移入上一次的值
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeUpdateFuzzyResult", "(I[B)Z")]
+pub fn jni_update_fuzzy_result(mut env: JNIEnv, _class: JObject, index: jint, value_bytes: JByteArray) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        if index < 0 {
+            return Err(JniErrorCode::OutOfBounds.error(format!("Index out of bounds: {}", index)));
+        }
+        let value_bytes = env.convert_byte_array(&value_bytes)?;
+
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.update_fuzzy_result(index as usize, &value_bytes)?;
+
+        Ok(JNI_TRUE)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 设置之后扫描得到的模糊结果的分组标签（0..=65535）
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSetResultTag", "(I)V")]
+pub fn jni_set_result_tag(mut env: JNIEnv, _class: JObject, tag: jint) {
+    (|| -> JniResult<()> {
+        let tag = u16::try_from(tag).map_err(|_| JniErrorCode::InvalidArgument.error(format!("Invalid result tag: {}", tag)))?;
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.set_fuzzy_tag(tag)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 开启或关闭模糊结果的历史跟踪（上一次的值、最小/最大值、变化次数与分组标签）
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSetHistoryTracking", "(Z)V")]
+pub fn jni_set_history_tracking(mut env: JNIEnv, _class: JObject, enabled: jboolean) {
+    (|| -> JniResult<()> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.set_history_tracking(enabled != JNI_FALSE)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 设置之后新增结果要求的地址对齐（2 的幂），地址须按 min(alignment, 值类型大小) 对齐，0 表示不限制
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSetRequiredAlignment", "(I)V")]
+pub fn jni_set_required_alignment(mut env: JNIEnv, _class: JObject, alignment: jint) {
+    (|| -> JniResult<()> {
+        if alignment < 0 {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid alignment: {}", alignment)));
+        }
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.set_required_alignment(if alignment == 0 { None } else { Some(alignment as usize) })
+    })()
+    .or_throw(&mut env)
+}
+
+/// 仅保留指定标签的模糊结果，返回保留数量
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeKeepOnlyTag", "(I)J")]
+pub fn jni_keep_only_tag(mut env: JNIEnv, _class: JObject, tag: jint) -> jlong {
+    (|| -> JniResult<jlong> {
+        let tag = u16::try_from(tag).map_err(|_| JniErrorCode::InvalidArgument.error(format!("Invalid result tag: {}", tag)))?;
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(manager.keep_only_tag(tag)? as jlong)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 移除指定标签的模糊结果，返回移除数量
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeRemoveTag", "(I)J")]
+pub fn jni_remove_tag(mut env: JNIEnv, _class: JObject, tag: jint) -> jlong {
+    (|| -> JniResult<jlong> {
+        let tag = u16::try_from(tag).map_err(|_| JniErrorCode::InvalidArgument.error(format!("Invalid result tag: {}", tag)))?;
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(manager.remove_tag(tag)? as jlong)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 模糊结果的值分布直方图
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetValueHistogram", "(I)[Lmoe/fuqiuluo/mamu/driver/HistogramBucket;")]
+pub fn jni_get_value_histogram(mut env: JNIEnv, _class: JObject, buckets: jint) -> jobjectArray {
+    (|| -> JniResult<jobjectArray> {
+        if buckets <= 0 {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid histogram bucket count: {}", buckets)));
+        }
+        let histogram = {
+            let manager = SEARCH_ENGINE_MANAGER
+                .read()
+                .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+            manager.value_histogram(buckets as usize)?
+        };
+
+        let bucket_class = env.find_class("moe/fuqiuluo/mamu/driver/HistogramBucket")?;
+        let array = env.new_object_array(histogram.len() as jint, &bucket_class, JObject::null())?;
+        for (i, (min, max, count)) in histogram.into_iter().enumerate() {
+            let bucket = env.new_object(
+                &bucket_class,
+                "(DDJ)V",
+                &[JValue::Double(min), JValue::Double(max), JValue::Long(count as jlong)],
+            )?;
+            env.set_object_array_element(&array, i as jint, bucket)?;
+        }
+
+        Ok(array.into_raw())
+    })()
+    .or_throw(&mut env)
+}
+
+/// Releases spare memory and disk space held by the result set after it has been narrowed.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeShrinkResults", "()Z")]
+pub fn jni_shrink_results(mut env: JNIEnv, _class: JObject) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.shrink_results()?;
+
+        Ok(JNI_TRUE)
+    })()
+    .or_throw(&mut env)
+}
+
 #[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSetFilter", "(ZJJZ[I)V")]
 pub fn jni_set_filter(
     mut env: JNIEnv,
@@ -572,6 +1123,26 @@ pub fn jni_clear_filter(mut env: JNIEnv, _class: JObject) {
     .or_throw(&mut env)
 }
 
+/// 值类型占用的字节数，变长类型返回 1
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeValueTypeSize", "(I)I")]
+pub fn jni_value_type_size(mut env: JNIEnv, _class: JObject, type_id: jint) -> jint {
+    (|| -> JniResult<jint> {
+        let value_type = ValueType::from_id(type_id).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type id: {}", type_id)))?;
+        Ok(value_type.size() as jint)
+    })()
+    .or_throw(&mut env)
+}
+
+/// 值类型的自然对齐字节数
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeValueTypeAlignment", "(I)I")]
+pub fn jni_value_type_alignment(mut env: JNIEnv, _class: JObject, type_id: jint) -> jint {
+    (|| -> JniResult<jint> {
+        let value_type = ValueType::from_id(type_id).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type id: {}", type_id)))?;
+        Ok(value_type.alignment() as jint)
+    })()
+    .or_throw(&mut env)
+}
+
 #[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetCurrentSearchMode", "()I")]
 pub fn jni_get_current_search_mode(mut env: JNIEnv, _class: JObject) -> jint {
     (|| -> JniResult<jint> {
@@ -619,6 +1190,90 @@ pub fn jni_get_compatibility_mode(mut env: JNIEnv, _class: JObject) -> jboolean
     .or_throw(&mut env)
 }
 
+/// Sets unsigned comparison for fuzzy refine.
+/// When enabled, integer values are compared as unsigned during fuzzy refine.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSetUnsignedCompare", "(Z)V")]
+pub fn jni_set_unsigned_compare(mut env: JNIEnv, _class: JObject, enabled: jboolean) {
+    (|| -> JniResult<()> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.set_unsigned_compare(enabled != JNI_FALSE);
+        Ok(())
+    })()
+    .or_throw(&mut env)
+}
+
+/// Gets unsigned comparison for fuzzy refine.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetUnsignedCompare", "()Z")]
+pub fn jni_get_unsigned_compare(mut env: JNIEnv, _class: JObject) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        Ok(if manager.get_unsigned_compare() { JNI_TRUE } else { JNI_FALSE })
+    })()
+    .or_throw(&mut env)
+}
+
+/// Sets wrap-aware difference for fuzzy refine.
+/// When enabled, increase/decrease amounts are computed at the value type's native width.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSetWrappingCompare", "(Z)V")]
+pub fn jni_set_wrapping_compare(mut env: JNIEnv, _class: JObject, enabled: jboolean) {
+    (|| -> JniResult<()> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.set_wrapping_compare(enabled != JNI_FALSE);
+        Ok(())
+    })()
+    .or_throw(&mut env)
+}
+
+/// Gets wrap-aware difference for fuzzy refine.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetWrappingCompare", "()Z")]
+pub fn jni_get_wrapping_compare(mut env: JNIEnv, _class: JObject) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        Ok(if manager.get_wrapping_compare() { JNI_TRUE } else { JNI_FALSE })
+    })()
+    .or_throw(&mut env)
+}
+
+/// Sets the target byte order for fuzzy search.
+/// When enabled, values read by the fuzzy scan, refine and result updates are decoded as big-endian.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeSetBigEndian", "(Z)V")]
+pub fn jni_set_big_endian(mut env: JNIEnv, _class: JObject, enabled: jboolean) {
+    (|| -> JniResult<()> {
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        manager.set_byte_order(if enabled != JNI_FALSE { ByteOrder::Big } else { ByteOrder::Little });
+        Ok(())
+    })()
+    .or_throw(&mut env)
+}
+
+/// Gets whether fuzzy search decodes values as big-endian.
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeGetBigEndian", "()Z")]
+pub fn jni_get_big_endian(mut env: JNIEnv, _class: JObject) -> jboolean {
+    (|| -> JniResult<jboolean> {
+        let manager = SEARCH_ENGINE_MANAGER
+            .read()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager read lock"))?;
+
+        Ok(if manager.get_byte_order() == ByteOrder::Big { JNI_TRUE } else { JNI_FALSE })
+    })()
+    .or_throw(&mut env)
+}
+
 /// Legacy synchronous refine search method.
 #[jni_method(
     70,
@@ -630,7 +1285,7 @@ pub fn jni_refine_search(mut env: JNIEnv, _class: JObject, query_str: JString, d
     (|| -> JniResult<jlong> {
         let query: String = env.get_string(&query_str)?.into();
 
-        let value_type = jint_to_value_type(default_type).ok_or_else(|| anyhow!("Invalid value type: {}", default_type))?;
+        let value_type = jint_to_value_type(default_type).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type: {}", default_type)))?;
 
         let search_query = parse_search_query(&query, value_type).map_err(|e| anyhow!("Parse error: {}", e))?;
 
@@ -721,7 +1376,7 @@ pub fn jni_add_results_from_addresses(mut env: JNIEnv, _class: JObject, addresse
 #[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeStartFuzzySearchAsync", "(I[JZ)Z")]
 pub fn jni_start_fuzzy_search_async(mut env: JNIEnv, _class: JObject, value_type_id: jint, regions: JLongArray, keep_results: jboolean) -> jboolean {
     (|| -> JniResult<jboolean> {
-        let value_type = jint_to_value_type(value_type_id).ok_or_else(|| anyhow!("Invalid value type: {}", value_type_id))?;
+        let value_type = jint_to_value_type(value_type_id).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type: {}", value_type_id)))?;
 
         let regions_len = env.get_array_length(&regions)? as usize;
         if regions_len % 2 != 0 {
@@ -759,6 +1414,13 @@ pub fn jni_start_fuzzy_search_async(mut env: JNIEnv, _class: JObject, value_type
 ///   - 8: DecreasedByRange(param1, param2)
 ///   - 9: IncreasedByPercent(param1 / 100.0)
 ///   - 10: DecreasedByPercent(param1 / 100.0)
+///   - 11: Between(param1, param2)
+///   - 12: BetweenFloat(param1, param2 as raw f64 bits)
+///   - 13: ChangedAtLeast(param1)
+///   - 14: IncreasedByTolerance(param1, param2)
+///   - 15: DecreasedByTolerance(param1, param2)
+///   - 16: EqualsNow(param1)
+///   - 17: EqualsNowFloat(param1 as raw f64 bits)
 /// - param1: First parameter for conditions that need it
 /// - param2: Second parameter for range conditions
 #[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeStartFuzzyRefineAsync", "(IJJ)Z")]
@@ -766,7 +1428,7 @@ pub fn jni_start_fuzzy_refine_async(mut env: JNIEnv, _class: JObject, condition_
     use crate::search::types::FuzzyCondition;
 
     (|| -> JniResult<jboolean> {
-        let condition = FuzzyCondition::from_id(condition_id, param1, param2).ok_or_else(|| anyhow!("Invalid fuzzy condition id: {}", condition_id))?;
+        let condition = FuzzyCondition::from_id(condition_id, param1, param2).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid fuzzy condition id: {}", condition_id)))?;
 
         if condition.is_initial() {
             return Err(anyhow!("Cannot use Initial condition for refine search"));
@@ -781,4 +1443,36 @@ pub fn jni_start_fuzzy_refine_async(mut env: JNIEnv, _class: JObject, condition_
         Ok(JNI_TRUE)
     })()
     .or_throw(&mut env)
+}
+
+/// 用调用方读取的新值同步细化模糊结果，返回剩余结果数量
+///
+/// fresh_values 按当前结果顺序排列，每项 8 字节（值放在低位）；condition_id/param1/param2 含义同
+/// nativeStartFuzzyRefineAsync。满足条件的结果更新为新值，其余移除
+#[jni_method(70, "moe/fuqiuluo/mamu/driver/SearchEngine", "nativeFuzzyRefine", "(IJJ[B)J")]
+pub fn jni_fuzzy_refine(
+    mut env: JNIEnv,
+    _class: JObject,
+    condition_id: jint,
+    param1: jlong,
+    param2: jlong,
+    fresh_values: JByteArray,
+) -> jlong {
+    use crate::search::types::FuzzyCondition;
+
+    (|| -> JniResult<jlong> {
+        let condition = FuzzyCondition::from_id(condition_id, param1, param2).ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid fuzzy condition id: {}", condition_id)))?;
+
+        if condition.is_initial() {
+            return Err(anyhow!("Cannot use Initial condition for refine search"));
+        }
+
+        let fresh_values = env.convert_byte_array(&fresh_values)?;
+        let mut manager = SEARCH_ENGINE_MANAGER
+            .write()
+            .map_err(|_| anyhow!("Failed to acquire SearchEngineManager write lock"))?;
+
+        Ok(manager.refine_fuzzy_in_place(&fresh_values, condition)? as jlong)
+    })()
+    .or_throw(&mut env)
 }
\ No newline at end of file
@@ -0,0 +1,214 @@
+//! AOB (array-of-bytes) 特征码扫描
+//!
+//! 特征码格式为空格分隔的十六进制字节，`??` 或 `?` 表示通配符，例如 `48 8B ?? ?? 90`。
+
+use super::manager::PAGE_SIZE;
+use crate::core::DRIVER_MANAGER;
+use crate::ext::jni::JniErrorCode;
+use crate::wuwa::PageStatusBitmap;
+use anyhow::{anyhow, Result};
+use log::{debug, log_enabled, warn, Level};
+use memchr::memchr_iter;
+
+/// 解析后的特征码：`Some(b)` 为需精确匹配的字节，`None` 为通配符
+pub fn parse_aob_pattern(pattern: &str) -> Result<Vec<Option<u8>>> {
+    let mut out = Vec::new();
+    for token in pattern.split_whitespace() {
+        match token {
+            "?" | "??" => out.push(None),
+            _ if token.len() == 2 => {
+                let byte = u8::from_str_radix(token, 16)
+                    .map_err(|_| JniErrorCode::InvalidArgument.error(format!("Invalid AOB byte '{}' in pattern '{}'", token, pattern)))?;
+                out.push(Some(byte));
+            },
+            _ => {
+                return Err(JniErrorCode::InvalidArgument.error(format!("Invalid AOB token '{}' in pattern '{}'", token, pattern)));
+            },
+        }
+    }
+
+    if out.is_empty() {
+        return Err(JniErrorCode::InvalidArgument.error("Empty AOB pattern"));
+    }
+    if out.iter().all(Option::is_none) {
+        return Err(JniErrorCode::InvalidArgument.error(format!("AOB pattern '{}' contains only wildcards", pattern)));
+    }
+    Ok(out)
+}
+
+/// 在 haystack 中扫描特征码，返回匹配处的绝对地址（base + 偏移）
+pub fn aob_scan(haystack: &[u8], base: u64, pattern: &str) -> Result<Vec<u64>> {
+    let pattern = parse_aob_pattern(pattern)?;
+    Ok(aob_scan_parsed(haystack, base, &pattern))
+}
+
+/// 使用已解析的特征码扫描 haystack
+///
+/// 以特征码中第一个非通配字节为锚点，用 memchr 定位候选位置后再逐字节校验。
+pub fn aob_scan_parsed(haystack: &[u8], base: u64, pattern: &[Option<u8>]) -> Vec<u64> {
+    if pattern.is_empty() || pattern.len() > haystack.len() {
+        return vec![];
+    }
+    let Some((anchor_offset, anchor)) = pattern.iter().enumerate().find_map(|(i, b)| b.map(|b| (i, b))) else {
+        return vec![];
+    };
+    let last_start = haystack.len() - pattern.len();
+
+    let mut out = Vec::new();
+    for pos in memchr_iter(anchor, &haystack[anchor_offset..=last_start + anchor_offset]) {
+        let window = &haystack[pos..pos + pattern.len()];
+        if pattern.iter().zip(window).all(|(p, b)| p.is_none_or(|p| p == *b)) {
+            out.push(base + pos as u64);
+        }
+    }
+    out
+}
+
+/// 在目标进程的 [start, end) 中扫描特征码
+pub fn search_region_aob(pattern: &[Option<u8>], start: u64, end: u64, chunk_size: usize) -> Result<Vec<u64>> {
+    let driver_manager = DRIVER_MANAGER.read().map_err(|_| anyhow!("Failed to acquire DriverManager lock"))?;
+
+    Ok(scan_region_chunks(pattern, start, end, chunk_size, |addr, buf| {
+        let mut page_status = PageStatusBitmap::new(buf.len(), addr as usize);
+        match driver_manager.read_memory_unified(addr, buf, Some(&mut page_status)) {
+            Ok(_) => Some(page_status),
+            Err(error) => {
+                if log_enabled!(Level::Debug) {
+                    warn!("Failed to read memory at 0x{:X} - 0x{:X}, err: {:?}", addr, addr + buf.len() as u64, error);
+                }
+                None
+            },
+        }
+    }))
+}
+
+/// 分块扫描 [start, end)，读取由 `read` 完成，返回 None 表示整块读取失败
+///
+/// 每块从页对齐地址开始，相邻块重叠 pattern.len() - 1 字节，使跨块的匹配既不遗漏也不重复；
+/// 匹配范围内只要有一页读取失败，该匹配即被丢弃。
+pub(crate) fn scan_region_chunks(
+    pattern: &[Option<u8>],
+    start: u64,
+    end: u64,
+    chunk_size: usize,
+    mut read: impl FnMut(u64, &mut [u8]) -> Option<PageStatusBitmap>,
+) -> Vec<u64> {
+    let page_size = *PAGE_SIZE as u64;
+    let overlap = pattern.len().saturating_sub(1) as u64;
+    // 块至少要比重叠部分多一页，否则无法前进
+    let chunk_size = (chunk_size as u64).max(overlap + page_size);
+
+    let mut results = Vec::new();
+    let mut chunk_buffer = vec![0u8; chunk_size as usize];
+    let mut current = start & !(page_size - 1);
+    // 小于该地址的起始位置已在前一块中完整校验过
+    let mut min_addr = start;
+
+    while current < end {
+        let chunk_end = (current + chunk_size).min(end);
+        let chunk_len = (chunk_end - current) as usize;
+
+        if let Some(page_status) = read(current, &mut chunk_buffer[..chunk_len]) {
+            for addr in aob_scan_parsed(&chunk_buffer[..chunk_len], current, pattern) {
+                if addr < min_addr || addr + pattern.len() as u64 > end {
+                    continue;
+                }
+                let first_page = ((addr - current) / page_size) as usize;
+                let last_page = ((addr + pattern.len() as u64 - 1 - current) / page_size) as usize;
+                if (first_page..=last_page).all(|idx| page_status.is_page_success(idx)) {
+                    results.push(addr);
+                }
+            }
+        }
+
+        if chunk_end >= end {
+            break;
+        }
+        // 回退 overlap 字节后向下页对齐作为下一块的起点
+        min_addr = chunk_end - overlap;
+        current = min_addr & !(page_size - 1);
+    }
+
+    if log_enabled!(Level::Debug) {
+        debug!("AOB scan 0x{:X} - 0x{:X}: {} matches", start, end, results.len());
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: [u8; 12] = [0x90, 0x48, 0x8B, 0x05, 0x10, 0x90, 0x48, 0x8B, 0x0D, 0x20, 0x90, 0xC3];
+
+    #[test]
+    fn test_aob_scan_exact() {
+        assert_eq!(aob_scan(&CODE, 0x400000, "48 8B 05").unwrap(), vec![0x400001]);
+        assert_eq!(aob_scan(&CODE, 0x400000, "90 c3").unwrap(), vec![0x40000A]);
+    }
+
+    #[test]
+    fn test_aob_scan_wildcard() {
+        assert_eq!(aob_scan(&CODE, 0x400000, "48 8B ?? ?? 90").unwrap(), vec![0x400001, 0x400006]);
+        // 首字节为通配符时以后续字节为锚点
+        assert_eq!(aob_scan(&CODE, 0x1000, "? 48 8B").unwrap(), vec![0x1000, 0x1005]);
+    }
+
+    #[test]
+    fn test_aob_scan_no_match() {
+        assert!(aob_scan(&CODE, 0, "48 8B ?? ?? CC").unwrap().is_empty());
+        assert!(aob_scan(&CODE[..3], 0, "90 48 8B 05").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_aob_scan_malformed() {
+        assert!(aob_scan(&CODE, 0, "").is_err());
+        assert!(aob_scan(&CODE, 0, "?? ??").is_err());
+        assert!(aob_scan(&CODE, 0, "48 8G").is_err());
+        assert!(aob_scan(&CODE, 0, "488B").is_err());
+    }
+
+    /// 用内存中的 Vec 模拟目标进程，`bad_page` 对应的页读取失败
+    fn scan_mock(memory: &[u8], base: u64, pattern: &str, chunk_size: usize, bad_page: Option<u64>) -> Vec<u64> {
+        let pattern = parse_aob_pattern(pattern).unwrap();
+        let page_size = *PAGE_SIZE as u64;
+        scan_region_chunks(&pattern, base, base + memory.len() as u64, chunk_size, |addr, buf| {
+            let offset = (addr - base) as usize;
+            buf.copy_from_slice(&memory[offset..offset + buf.len()]);
+            let mut status = PageStatusBitmap::new(buf.len(), addr as usize);
+            let pages = (buf.len() as u64).div_ceil(page_size);
+            for idx in 0..pages {
+                if bad_page != Some(addr + idx * page_size) {
+                    status.mark_success(idx as usize);
+                }
+            }
+            Some(status)
+        })
+    }
+
+    #[test]
+    fn test_region_scan_across_chunks() {
+        let page_size = *PAGE_SIZE;
+        let base = 0x7000_0000u64;
+        let mut memory = vec![0u8; page_size * 4];
+        // 跨越第 1、2 页边界的匹配，以及每页偏移 8 处各一个匹配
+        memory[page_size * 2 - 2..page_size * 2 + 2].copy_from_slice(&[0x48, 0x8B, 0x05, 0x89]);
+        for page in 0..4 {
+            memory[page * page_size + 8..page * page_size + 12].copy_from_slice(&[0x48, 0x8B, 0x11, 0x89]);
+        }
+
+        let mut expected: Vec<u64> = (0..4).map(|p| base + (p * page_size + 8) as u64).collect();
+        expected.push(base + (page_size * 2 - 2) as u64);
+        expected.sort_unstable();
+
+        let mut found = scan_mock(&memory, base, "48 8B ?? 89", page_size, None);
+        found.sort_unstable();
+        assert_eq!(found, expected);
+
+        // 跨页匹配涉及的页读取失败时应被丢弃
+        let found = scan_mock(&memory, base, "48 8B ?? 89", page_size, Some(base + page_size as u64 * 2));
+        assert!(!found.contains(&(base + (page_size * 2 - 2) as u64)));
+        assert!(!found.contains(&(base + (page_size * 2 + 8) as u64)));
+        assert_eq!(found.len(), 3);
+    }
+}
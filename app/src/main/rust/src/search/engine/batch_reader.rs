@@ -69,7 +69,7 @@ pub fn cluster_addresses(items: &[FuzzySearchResultItem]) -> Vec<AddressBatch> {
 
     for (idx, item) in items.iter().enumerate() {
         let addr = item.address;
-        let size = item.value_type.size();
+        let size = item.value_size();
 
         match &mut current_batch {
             Some(batch) => {
@@ -121,7 +121,7 @@ pub fn cluster_addresses(items: &[FuzzySearchResultItem]) -> Vec<AddressBatch> {
 /// * `check_cancelled` - 取消检查闭包
 ///
 /// # 返回
-/// 返回成功读取的 (地址项, 当前值) 元组列表
+/// 返回成功读取的 (结果在 `items` 中的索引, 当前值) 元组列表
 pub fn parallel_batch_read<P, F>(
     batches: &[AddressBatch],
     items: &[FuzzySearchResultItem],
@@ -129,7 +129,7 @@ pub fn parallel_batch_read<P, F>(
     total_found_counter: Option<&Arc<AtomicUsize>>,
     update_progress: &P,
     check_cancelled: Option<&F>,
-) -> Result<Vec<(FuzzySearchResultItem, Vec<u8>)>>
+) -> Result<Vec<(usize, Vec<u8>)>>
 where
     P: Fn(usize, usize) + Sync,
     F: Fn() -> bool + Sync,
@@ -139,7 +139,7 @@ where
     let cancelled_clone = Arc::clone(&cancelled);
 
     // 并行处理批次
-    let results: Result<Vec<(FuzzySearchResultItem, Vec<u8>)>> = batches
+    let results: Result<Vec<(usize, Vec<u8>)>> = batches
         .par_iter()
         .enumerate()
         .take_any_while(|&(_idx, _batch)| {
@@ -157,7 +157,7 @@ where
         })
         .try_fold(
             || Vec::new(), // 线程本地累加器
-            |mut acc, (batch_idx, batch)| -> Result<Vec<(FuzzySearchResultItem, Vec<u8>)>> {
+            |mut acc, (batch_idx, batch)| -> Result<Vec<(usize, Vec<u8>)>> {
                 let driver_manager = DRIVER_MANAGER.read().map_err(|_| anyhow!("Failed to acquire DriverManager lock"))?;
 
                 // 分配批次缓冲区
@@ -173,8 +173,7 @@ where
                         // 从批次缓冲区提取各个地址的值
                         for item_ref in &batch.items {
                             let value_bytes = &buffer[item_ref.offset..item_ref.offset + item_ref.value_size];
-                            let original_item = &items[item_ref.item_index];
-                            acc.push((original_item.clone(), value_bytes.to_vec()));
+                            acc.push((item_ref.item_index, value_bytes.to_vec()));
                         }
                     },
                     Err(e) => {
@@ -191,7 +190,7 @@ where
                             let mut small_buffer = vec![0u8; item_ref.value_size];
 
                             if driver_manager.read_memory_unified(original_item.address, &mut small_buffer, None).is_ok() {
-                                acc.push((original_item.clone(), small_buffer));
+                                acc.push((item_ref.item_index, small_buffer));
                             }
                         }
                     },
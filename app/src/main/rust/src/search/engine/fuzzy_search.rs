@@ -1,5 +1,5 @@
-use super::super::result_manager::FuzzySearchResultItem;
-use super::super::types::{FuzzyCondition, ValueType};
+use super::super::result_manager::{FuzzyResultSet, FuzzySearchResultItem};
+use super::super::types::{FuzzyCompareOptions, FuzzyCondition, ValueType};
 use super::manager::{BPLUS_TREE_ORDER, PAGE_SIZE};
 use crate::core::DRIVER_MANAGER;
 use crate::wuwa::PageStatusBitmap;
@@ -230,32 +230,35 @@ fn scan_single_page(
 
 /// 模糊搜索细化
 /// 读取已有结果的当前值，并根据条件过滤
-/// 返回新的 BPlusTreeSet
+/// 返回新的结果集，历史信息随结果一起更新
 ///
 /// # 参数
-/// * `items` - 之前的搜索结果
+/// * `results` - 之前的搜索结果及其历史信息
 /// * `condition` - 模糊搜索条件
+/// * `options` - 比较选项（无符号、回绕差值、字节序）
 /// * `processed_counter` - 已处理计数器（可选）
 /// * `total_found_counter` - 找到总数计数器（可选）
 /// * `update_progress` - 进度更新回调
 /// * `check_cancelled` - 取消检查闭包（可选）
 ///
 /// # 返回
-/// 返回满足条件的结果项（包含新值，有序）
+/// 返回满足条件的结果项（包含新值，按地址有序）
 pub(crate) fn fuzzy_refine_search<P, F>(
-    items: &Vec<FuzzySearchResultItem>,
+    results: &FuzzyResultSet,
     condition: FuzzyCondition,
+    options: FuzzyCompareOptions,
     processed_counter: Option<&Arc<AtomicUsize>>,
     total_found_counter: Option<&Arc<AtomicUsize>>,
     update_progress: &P,
     check_cancelled: Option<&F>,
-) -> Result<BPlusTreeSet<FuzzySearchResultItem>>
+) -> Result<FuzzyResultSet>
 where
     P: Fn(usize, usize) + Sync,
     F: Fn() -> bool + Sync,
 {
+    let items = &results.items;
     if items.is_empty() {
-        return Ok(BPlusTreeSet::new(BPLUS_TREE_ORDER));
+        return Ok(FuzzyResultSet::default());
     }
 
     let total_items = items.len();
@@ -280,7 +283,7 @@ where
     let cancelled = Arc::new(AtomicBool::new(false));
     let cancelled_clone = Arc::clone(&cancelled);
 
-    let matched: Vec<FuzzySearchResultItem> = items_with_current_value
+    let mut matched: Vec<(usize, FuzzySearchResultItem)> = items_with_current_value
         .par_iter()
         .take_any_while(|_| {
             if cancelled_clone.load(Ordering::Relaxed) {
@@ -294,32 +297,43 @@ where
             }
             true
         })
-        .filter_map(|(old_item, current_value)| {
-            if old_item.matches_condition(current_value, condition) {
+        .filter_map(|(index, current_value)| {
+            let old_item = &items[*index];
+            if old_item.matches_condition_history(current_value, condition, options, results.history(*index)) {
                 if let Some(counter) = total_found_counter {
                     counter.fetch_add(1, Ordering::Relaxed);
                 }
-                Some(FuzzySearchResultItem::from_bytes(old_item.address, current_value, old_item.value_type))
+                Some((*index, old_item.with_new_value_ordered(current_value, options.byte_order)))
             } else {
                 None
             }
         })
         .collect();
 
-    let mut results = BPlusTreeSet::new(BPLUS_TREE_ORDER);
-    for item in matched {
-        results.insert(item);
-    }
+    // 批次并行读取，完成顺序不固定：按地址排序并去重
+    matched.sort_unstable_by_key(|(_, item)| item.address);
+    matched.dedup_by_key(|(_, item)| item.address);
+
+    let history = results.history.as_ref().map(|history| {
+        matched
+            .iter()
+            .map(|(index, item)| history[*index].advance(&items[*index], item))
+            .collect()
+    });
+    let refined = FuzzyResultSet {
+        items: matched.into_iter().map(|(_, item)| item).collect(),
+        history,
+    };
 
     if log_enabled!(Level::Debug) {
-        debug!("Fuzzy refine: checked {} items, found {} matches", items.len(), results.len());
+        debug!("Fuzzy refine: checked {} items, found {} matches", items.len(), refined.len());
     }
 
     // 最终更新进度到 100%
     if let Some(counter) = total_found_counter {
-        counter.store(results.len(), Ordering::Relaxed);
+        counter.store(refined.len(), Ordering::Relaxed);
     }
-    update_progress(total_items, results.len());
+    update_progress(total_items, refined.len());
 
-    Ok(results)
+    Ok(refined)
 }
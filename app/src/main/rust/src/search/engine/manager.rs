@@ -1,6 +1,7 @@
-use super::super::result_manager::{FuzzySearchResultItem, SearchResultManager, SearchResultMode};
-use super::super::types::{FuzzyCondition, SearchQuery, ValueType};
+use super::super::result_manager::{FuzzyItemHistory, FuzzyResultSet, FuzzySearchResultItem, FuzzySearchResultManager, ResultSnapshot, ResultUsageStats, SearchResultManager, SearchResultManagerConfig, SearchResultMode};
+use super::super::types::{ByteOrder, FuzzyCompareOptions, FuzzyCondition, SearchQuery, ValueType};
 use super::super::SearchResultItem;
+use super::aob;
 use super::filter::SearchFilter;
 use super::fuzzy_search;
 use super::group_search;
@@ -8,6 +9,7 @@ use super::shared_buffer::{SearchErrorCode, SearchStatus, SharedBuffer};
 use super::single_search;
 use crate::core::globals::TOKIO_RUNTIME;
 use crate::core::DRIVER_MANAGER;
+use crate::ext::jni::JniErrorCode;
 use crate::search::result_manager::ExactSearchResultItem;
 use anyhow::{anyhow, Result};
 use bplustree::BPlusTreeSet;
@@ -15,7 +17,10 @@ use lazy_static::lazy_static;
 use log::{debug, error, info, log_enabled, warn, Level};
 use rayon::prelude::*;
 use std::cmp::Ordering as CmpOrdering;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
@@ -76,6 +81,8 @@ pub trait SearchProgressCallback: Send + Sync {
 /// Search engine manager with async support.
 pub struct SearchEngineManager {
     result_manager: Option<SearchResultManager>,
+    /// 初始化时的结果管理器配置，合并快照时按它创建临时管理器
+    result_config: Option<SearchResultManagerConfig>,
     chunk_size: usize,
     filter: SearchFilter,
     shared_buffer: SharedBuffer,
@@ -83,18 +90,30 @@ pub struct SearchEngineManager {
     search_handle: Option<JoinHandle<()>>,
     /// 兼容模式：所有搜索结果都以模糊搜索格式存储，支持精确搜索和模糊搜索互相切换
     compatibility_mode: bool,
+    /// 模糊细化的比较选项（无符号、回绕差值、字节序）
+    compare_options: FuzzyCompareOptions,
+    /// 最近一次保存的结果快照，用于撤销一次细化
+    undo_snapshot: Option<ResultSnapshot>,
+    /// 分批读取结果的游标：句柄 -> 下一次读取的起始索引
+    result_cursors: HashMap<u64, usize>,
+    next_cursor_handle: u64,
 }
 
 impl SearchEngineManager {
     pub fn new() -> Self {
         Self {
             result_manager: None,
+            result_config: None,
             chunk_size: 512 * 1024,
             filter: SearchFilter::new(),
             shared_buffer: SharedBuffer::new(),
             cancel_token: None,
             search_handle: None,
             compatibility_mode: false,
+            compare_options: FuzzyCompareOptions::default(),
+            undo_snapshot: None,
+            result_cursors: HashMap::new(),
+            next_cursor_handle: 1,
         }
     }
 
@@ -110,6 +129,51 @@ impl SearchEngineManager {
         self.compatibility_mode
     }
 
+    /// Set unsigned comparison for fuzzy refine.
+    /// When enabled, integer values are compared as unsigned, so counters crossing the sign boundary
+    /// (e.g. a Dword going from 0x7FFFFFFF to 0x80000000) are treated as increased.
+    pub fn set_unsigned_compare(&mut self, enabled: bool) {
+        self.compare_options.unsigned = enabled;
+        self.sync_compare_options();
+    }
+
+    /// Get unsigned comparison for fuzzy refine
+    pub fn get_unsigned_compare(&self) -> bool {
+        self.compare_options.unsigned
+    }
+
+    /// Set wrap-aware difference for fuzzy refine.
+    /// When enabled, IncreasedBy/DecreasedBy and their tolerance and range variants compute the
+    /// difference at the value type's native width, so an 8-bit counter going from 255 to 0 matches "+1".
+    pub fn set_wrapping_compare(&mut self, enabled: bool) {
+        self.compare_options.wrapping = enabled;
+        self.sync_compare_options();
+    }
+
+    /// Get wrap-aware difference for fuzzy refine
+    pub fn get_wrapping_compare(&self) -> bool {
+        self.compare_options.wrapping
+    }
+
+    /// Set the target byte order for fuzzy search.
+    /// Values read by the initial scan, refine and result updates are decoded in this order.
+    pub fn set_byte_order(&mut self, order: ByteOrder) {
+        self.compare_options.byte_order = order;
+        self.sync_compare_options();
+    }
+
+    /// Get the target byte order for fuzzy search
+    pub fn get_byte_order(&self) -> ByteOrder {
+        self.compare_options.byte_order
+    }
+
+    /// 同步比较选项到结果管理器，供 refine_fuzzy_in_place 等同步细化路径使用
+    fn sync_compare_options(&mut self) {
+        if let Some(result_mgr) = self.result_manager.as_mut() {
+            result_mgr.set_compare_options(self.compare_options);
+        }
+    }
+
     /// Sets the shared buffer for progress communication.
     pub fn set_shared_buffer(&mut self, ptr: *mut u8, len: usize) -> bool {
         self.shared_buffer.set(ptr, len)
@@ -137,12 +201,24 @@ impl SearchEngineManager {
     }
 
     pub fn init(&mut self, memory_buffer_size: usize, cache_dir: String, chunk_size: usize) -> Result<()> {
+        self.init_with_config(SearchResultManagerConfig::new(memory_buffer_size, PathBuf::from(cache_dir)), chunk_size)
+    }
+
+    /// 按给定的内存与磁盘策略初始化，见 [`SearchResultManagerConfig`]
+    pub fn init_with_config(&mut self, config: SearchResultManagerConfig, chunk_size: usize) -> Result<()> {
         if self.result_manager.is_some() {
             warn!("SearchEngineManager already initialized, reinitializing...");
         }
 
-        let cache_path = PathBuf::from(cache_dir);
-        self.result_manager = Some(SearchResultManager::new(memory_buffer_size, cache_path));
+        match FuzzySearchResultManager::remove_stale_disk_files(&config.cache_dir) {
+            Ok(0) => {},
+            Ok(removed) => info!("Removed {} stale fuzzy disk files from {:?}", removed, config.cache_dir),
+            Err(e) => warn!("Failed to clean stale fuzzy disk files in {:?}: {:?}", config.cache_dir, e),
+        }
+        self.result_manager = Some(SearchResultManager::from_config(config.clone()));
+        self.result_config = Some(config);
+        self.sync_compare_options();
+        self.undo_snapshot = None;
         self.chunk_size = if chunk_size == 0 { 512 * 1024 } else { chunk_size };
 
         Ok(())
@@ -161,7 +237,7 @@ impl SearchEngineManager {
         if !self.is_initialized() {
             self.shared_buffer.write_status(SearchStatus::Error);
             self.shared_buffer.write_error_code(SearchErrorCode::NotInitialized);
-            return Err(anyhow!("SearchEngineManager not initialized"));
+            return Err(JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"));
         }
 
         if self.is_searching() {
@@ -377,13 +453,14 @@ impl SearchEngineManager {
                                     error!("Failed to set mode: {:?}", e);
                                 }
                                 if let Ok(driver_manager) = DRIVER_MANAGER.read() {
+                                    let byte_order = result_mgr.byte_order();
                                     let fuzzy_results: Vec<FuzzySearchResultItem> = all_results
                                         .into_iter() // todo 可以并行吗?
                                         .filter_map(|pair| {
                                             let size = pair.value_type.size();
                                             let mut buffer = vec![0u8; size];
                                             if driver_manager.read_memory_unified(pair.addr, &mut buffer, None).is_ok() {
-                                                Some(FuzzySearchResultItem::from_bytes(pair.addr, &buffer, pair.value_type))
+                                                Some(FuzzySearchResultItem::from_bytes_ordered(pair.addr, &buffer, pair.value_type, byte_order))
                                             } else {
                                                 None
                                             }
@@ -454,7 +531,7 @@ impl SearchEngineManager {
         if !self.is_initialized() {
             self.shared_buffer.write_status(SearchStatus::Error);
             self.shared_buffer.write_error_code(SearchErrorCode::NotInitialized);
-            return Err(anyhow!("SearchEngineManager not initialized"));
+            return Err(JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"));
         }
 
         if self.is_searching() {
@@ -616,13 +693,14 @@ impl SearchEngineManager {
                                         let _ = result_mgr.set_mode(SearchResultMode::Fuzzy);
                                         // Convert to FuzzySearchResultItem by reading current memory values
                                         if let Ok(driver_manager) = DRIVER_MANAGER.read() {
+                                            let byte_order = result_mgr.byte_order();
                                             let fuzzy_results: Vec<_> = refined_results
                                                 .into_iter() // todo 是否需要优化成并行的？
                                                 .filter_map(|pair| {
                                                     let size = pair.value_type.size();
                                                     let mut buffer = vec![0u8; size];
                                                     if driver_manager.read_memory_unified(pair.addr, &mut buffer, None).is_ok() {
-                                                        Some(FuzzySearchResultItem::from_bytes(pair.addr, &buffer, pair.value_type))
+                                                        Some(FuzzySearchResultItem::from_bytes_ordered(pair.addr, &buffer, pair.value_type, byte_order))
                                                     } else {
                                                         None
                                                     }
@@ -683,7 +761,7 @@ impl SearchEngineManager {
         if !self.is_initialized() {
             self.shared_buffer.write_status(SearchStatus::Error);
             self.shared_buffer.write_error_code(SearchErrorCode::NotInitialized);
-            return Err(anyhow!("SearchEngineManager not initialized"));
+            return Err(JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"));
         }
 
         if self.is_searching() {
@@ -705,13 +783,14 @@ impl SearchEngineManager {
                 // Convert exact to fuzzy: need to read current values
                 let driver_manager = DRIVER_MANAGER.read().map_err(|_| anyhow!("Failed to acquire DriverManager lock"))?;
 
+                let byte_order = self.compare_options.byte_order;
                 let mut fuzzy_results = Vec::with_capacity(exact_results.len());
                 for exact in exact_results {
                     let size = exact.typ.size();
                     let mut buffer = vec![0u8; size];
 
                     if driver_manager.read_memory_unified(exact.address, &mut buffer, None).is_ok() {
-                        let fuzzy = FuzzySearchResultItem::from_bytes(exact.address, &buffer, exact.typ);
+                        let fuzzy = FuzzySearchResultItem::from_bytes_ordered(exact.address, &buffer, exact.typ, byte_order);
                         fuzzy_results.push(fuzzy);
                     }
                 }
@@ -748,9 +827,10 @@ impl SearchEngineManager {
         self.cancel_token = Some(cancel_token.clone());
 
         let chunk_size = self.chunk_size;
+        let byte_order = self.compare_options.byte_order;
 
         let handle = TOKIO_RUNTIME.spawn(async move {
-            Self::run_fuzzy_initial_task(value_type, regions, chunk_size, cancel_token).await;
+            Self::run_fuzzy_initial_task(value_type, regions, chunk_size, byte_order, cancel_token).await;
         });
 
         self.search_handle = Some(handle);
@@ -758,7 +838,13 @@ impl SearchEngineManager {
     }
 
     /// Internal async fuzzy initial scan task.
-    async fn run_fuzzy_initial_task(value_type: ValueType, regions: Vec<(u64, u64)>, chunk_size: usize, cancel_token: CancellationToken) {
+    async fn run_fuzzy_initial_task(
+        value_type: ValueType,
+        regions: Vec<(u64, u64)>,
+        chunk_size: usize,
+        byte_order: ByteOrder,
+        cancel_token: CancellationToken,
+    ) {
         let start_time = Instant::now();
         let total_regions = regions.len();
 
@@ -859,8 +945,8 @@ impl SearchEngineManager {
                         if let Some(ref mut result_mgr) = manager.result_manager {
                             for region_results in all_results {
                                 if !region_results.is_empty() {
-                                    // Convert BPlusTreeSet to Vec for storage
-                                    let items: Vec<_> = region_results.iter().cloned().collect();
+                                    // Convert BPlusTreeSet to Vec for storage, decoding raw bytes in the target byte order
+                                    let items: Vec<_> = region_results.iter().map(|item| item.reinterpret_order(byte_order)).collect();
                                     if let Err(e) = result_mgr.add_fuzzy_results_batch(items) {
                                         error!("Failed to add fuzzy results: {:?}", e);
                                     }
@@ -910,7 +996,7 @@ impl SearchEngineManager {
         if !self.is_initialized() {
             self.shared_buffer.write_status(SearchStatus::Error);
             self.shared_buffer.write_error_code(SearchErrorCode::NotInitialized);
-            return Err(anyhow!("SearchEngineManager not initialized"));
+            return Err(JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"));
         }
 
         if self.is_searching() {
@@ -924,7 +1010,7 @@ impl SearchEngineManager {
             return Err(anyhow!("Not in fuzzy mode"));
         }
 
-        let current_results = result_mgr.get_all_fuzzy_results()?;
+        let current_results = result_mgr.get_fuzzy_result_set()?;
         if current_results.is_empty() {
             warn!("No fuzzy results to refine");
             self.shared_buffer.write_status(SearchStatus::Completed);
@@ -940,8 +1026,10 @@ impl SearchEngineManager {
         let cancel_token = CancellationToken::new();
         self.cancel_token = Some(cancel_token.clone());
 
+        let options = self.compare_options;
+
         let handle = TOKIO_RUNTIME.spawn(async move {
-            Self::run_fuzzy_refine_task(current_results, condition, cancel_token).await;
+            Self::run_fuzzy_refine_task(current_results, condition, options, cancel_token).await;
         });
 
         self.search_handle = Some(handle);
@@ -949,11 +1037,19 @@ impl SearchEngineManager {
     }
 
     /// Internal async fuzzy refine task.
-    async fn run_fuzzy_refine_task(current_results: Vec<FuzzySearchResultItem>, condition: FuzzyCondition, cancel_token: CancellationToken) {
+    async fn run_fuzzy_refine_task(
+        current_results: FuzzyResultSet,
+        condition: FuzzyCondition,
+        options: FuzzyCompareOptions,
+        cancel_token: CancellationToken,
+    ) {
         let start_time = Instant::now();
         let total_items = current_results.len();
 
-        debug!("Starting fuzzy refine: condition={:?}, existing results={}", condition, total_items);
+        debug!(
+            "Starting fuzzy refine: condition={:?}, options={:?}, existing results={}",
+            condition, options, total_items
+        );
 
         let processed_counter = Arc::new(AtomicUsize::new(0));
         let total_found_counter = Arc::new(AtomicUsize::new(0));
@@ -967,13 +1063,13 @@ impl SearchEngineManager {
         let refine_result = tokio::task::spawn_blocking(move || {
             // Check cancellation.
             if cancel_token_clone.is_cancelled() || cancelled_clone.load(AtomicOrdering::Relaxed) {
-                return BPlusTreeSet::new(BPLUS_TREE_ORDER);
+                return FuzzyResultSet::default();
             }
 
             if let Ok(manager) = SEARCH_ENGINE_MANAGER.read() {
                 if manager.shared_buffer.is_cancel_requested() {
                     cancelled_clone.store(true, AtomicOrdering::Relaxed);
-                    return BPlusTreeSet::new(BPLUS_TREE_ORDER);
+                    return FuzzyResultSet::default();
                 }
             }
 
@@ -1003,6 +1099,7 @@ impl SearchEngineManager {
             fuzzy_search::fuzzy_refine_search(
                 &current_results,
                 condition,
+                options,
                 Some(&processed_clone),
                 Some(&found_clone),
                 &update_progress,
@@ -1010,7 +1107,7 @@ impl SearchEngineManager {
             )
             .unwrap_or_else(|e| {
                 error!("Fuzzy refine failed: {:?}", e);
-                BPlusTreeSet::new(BPLUS_TREE_ORDER)
+                FuzzyResultSet::default()
             })
         })
         .await;
@@ -1025,14 +1122,11 @@ impl SearchEngineManager {
 
         // Process results.
         let success = match refine_result {
-            Ok(refined_tree) => {
+            Ok(refined) => {
                 match SEARCH_ENGINE_MANAGER.write() {
                     Ok(mut manager) => {
                         if let Some(ref mut result_mgr) = manager.result_manager {
-                            // Convert tree to vec and replace all results.
-                            let refined_vec: Vec<_> = refined_tree.iter().cloned().collect();
-
-                            if let Err(e) = result_mgr.replace_all_fuzzy_results(refined_vec) {
+                            if let Err(e) = result_mgr.replace_all_fuzzy_results(refined) {
                                 error!("Failed to replace fuzzy results: {:?}", e);
                                 false
                             } else {
@@ -1083,7 +1177,7 @@ impl SearchEngineManager {
         use_deep_search: bool,
         callback: Option<Arc<dyn SearchProgressCallback>>,
     ) -> Result<usize> {
-        let result_mgr = self.result_manager.as_mut().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         result_mgr.clear()?;
         result_mgr.set_mode(SearchResultMode::Exact)?;
@@ -1175,49 +1269,333 @@ impl SearchEngineManager {
     }
 
     pub fn get_results(&self, start: usize, size: usize) -> Result<Vec<SearchResultItem>> {
-        let result_mgr = self.result_manager.as_ref().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         result_mgr.get_results(start, size)
     }
 
+    /// 打开一个从首个结果开始的游标，返回非 0 句柄
+    /// 游标只记录读取位置，期间结果被修改时从原索引继续读取
+    pub fn open_result_cursor(&mut self) -> Result<u64> {
+        if self.result_manager.is_none() {
+            return Err(JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"));
+        }
+
+        let handle = self.next_cursor_handle;
+        self.next_cursor_handle += 1;
+        self.result_cursors.insert(handle, 0);
+        Ok(handle)
+    }
+
+    /// 从游标位置读取至多 `batch_size` 个结果并前移游标，附带每项的全局索引；读完后返回空
+    pub fn cursor_next(&mut self, handle: u64, batch_size: usize) -> Result<Vec<(usize, SearchResultItem)>> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+        let position = self
+            .result_cursors
+            .get_mut(&handle)
+            .ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid result cursor: {}", handle)))?;
+
+        let start = *position;
+        let items = result_mgr.get_results(start, batch_size)?;
+        *position += items.len();
+        Ok(items.into_iter().enumerate().map(|(offset, item)| (start + offset, item)).collect())
+    }
+
+    /// 关闭游标，句柄无效时返回 false
+    pub fn close_result_cursor(&mut self, handle: u64) -> bool {
+        self.result_cursors.remove(&handle).is_some()
+    }
+
+    /// 按当前值排序后取 [start, start + size) 的结果，附带每项在地址顺序中的索引
+    pub fn get_results_sorted_by_value(&self, descending: bool, start: usize, size: usize) -> Result<Vec<(usize, SearchResultItem)>> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        Ok(result_mgr
+            .sorted_by_value(descending)?
+            .into_iter()
+            .skip(start)
+            .take(size)
+            .map(|(index, item)| (index, SearchResultItem::Fuzzy(item)))
+            .collect())
+    }
+
+    /// 按页获取结果，页码超出范围时取最后一页，返回 (结果, 总页数)
+    pub fn get_page(&self, page: usize, page_size: usize) -> Result<(Vec<SearchResultItem>, usize)> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.get_page(page, page_size)
+    }
+
     pub fn get_total_count(&self) -> Result<usize> {
-        let result_mgr = self.result_manager.as_ref().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         Ok(result_mgr.total_count())
     }
 
+    /// 将当前结果导出到文件，`as_json` 为 false 时导出 CSV
+    pub fn export_results(&self, path: &Path, as_json: bool) -> Result<()> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        let writer = BufWriter::new(File::create(path)?);
+        if as_json {
+            result_mgr.export_json(writer)
+        } else {
+            result_mgr.export_csv(writer)
+        }
+    }
+
+    pub fn get_usage_stats(&self) -> Result<ResultUsageStats> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        Ok(result_mgr.usage_stats())
+    }
+
     pub fn clear_results(&mut self) -> Result<()> {
-        let result_mgr = self.result_manager.as_mut().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         result_mgr.clear()
     }
 
     pub fn remove_result(&mut self, index: usize) -> Result<()> {
-        let result_mgr = self.result_manager.as_mut().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         result_mgr.remove_result(index)
     }
 
     pub fn remove_results_batch(&mut self, indices: Vec<usize>) -> Result<()> {
-        let result_mgr = self.result_manager.as_mut().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         result_mgr.remove_results_batch(indices)
     }
 
     pub fn keep_only_results(&mut self, keep_indices: Vec<usize>) -> Result<()> {
-        let result_mgr = self.result_manager.as_mut().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         result_mgr.keep_only_results(keep_indices)
     }
 
+    pub fn find_near(&self, address: u64, window: u64) -> Result<Vec<(usize, SearchResultItem)>> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.find_near(address, window)
+    }
+
+    pub fn contiguous_runs(&self, stride: usize) -> Result<Vec<(u64, usize)>> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.contiguous_runs(stride)
+    }
+
+    pub fn get_addresses(&self) -> Result<Vec<u64>> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.addresses()
+    }
+
+    pub fn filter_address_range(&mut self, min: u64, max: u64) -> Result<()> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.filter_address_range(min, max)
+    }
+
+    /// 与另一组地址求交，`addresses` 须升序排列，返回剩余结果数量
+    pub fn intersect_results(&mut self, addresses: &[u64]) -> Result<usize> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.intersect_with(addresses)?;
+        Ok(result_mgr.total_count())
+    }
+
+    /// 用户修改内存后同步单个模糊结果的值
+    pub fn update_fuzzy_result(&mut self, index: usize, new_bytes: &[u8]) -> Result<()> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.update_fuzzy_value(index, new_bytes)
+    }
+
+    /// 用调用方读取的新值同步细化模糊结果，返回剩余数量
+    pub fn refine_fuzzy_in_place(&mut self, fresh_values: &[u8], condition: FuzzyCondition) -> Result<usize> {
+        if self.is_searching() {
+            return Err(anyhow!("Search already in progress"));
+        }
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.refine_fuzzy_in_place(fresh_values, condition)
+    }
+
+    /// 特征码（AOB）搜索，同步执行
+    ///
+    /// 清空现有结果并切换为精确模式，匹配地址以 `ValueType::Bytes` 存入精确结果。
+    pub fn signature_search(&mut self, pattern: &str, regions: &[(u64, u64)]) -> Result<usize> {
+        if self.is_searching() {
+            return Err(anyhow!("Search already in progress"));
+        }
+        let pattern = aob::parse_aob_pattern(pattern)?;
+        let chunk_size = self.chunk_size;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        let start_time = Instant::now();
+        let mut addresses = regions
+            .par_iter()
+            .map(|&(start, end)| aob::search_region_aob(&pattern, start, end, chunk_size))
+            .collect::<Result<Vec<_>>>()?
+            .concat();
+        addresses.par_sort_unstable();
+        addresses.dedup();
+
+        result_mgr.clear()?;
+        result_mgr.set_mode(SearchResultMode::Exact)?;
+        let count = addresses.len();
+        result_mgr.add_results_batch(addresses.into_iter().map(|addr| SearchResultItem::new_exact(addr, ValueType::Bytes)).collect())?;
+
+        info!("Signature search completed: {} matches in {:?}", count, start_time.elapsed());
+        Ok(count)
+    }
+
+    /// 保存当前结果的快照（替换之前的快照），返回快照中的结果数量
+    ///
+    /// 在细化前调用，之后可通过 [`Self::restore_result_snapshot`] 撤销该次细化
+    pub fn save_result_snapshot(&mut self) -> Result<usize> {
+        if self.is_searching() {
+            return Err(anyhow!("Search already in progress"));
+        }
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        let snapshot = result_mgr.snapshot()?;
+        let count = snapshot.len();
+        self.undo_snapshot = Some(snapshot);
+        Ok(count)
+    }
+
+    /// 恢复并丢弃最近保存的快照，没有快照时返回 false
+    pub fn restore_result_snapshot(&mut self) -> Result<bool> {
+        if self.is_searching() {
+            return Err(anyhow!("Search already in progress"));
+        }
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        let Some(snapshot) = self.undo_snapshot.take() else {
+            return Ok(false);
+        };
+        result_mgr.restore(snapshot)?;
+        Ok(true)
+    }
+
+    /// Appends the saved snapshot's results to the current results and discards the snapshot.
+    /// Lets two scans be combined: save a snapshot after the first scan, run the second, then merge.
+    /// Nothing is deduplicated, so the merged count is the sum of both.
+    /// Returns the merged result count, or None when no snapshot was saved.
+    pub fn merge_result_snapshot(&mut self) -> Result<Option<usize>> {
+        if self.is_searching() {
+            return Err(anyhow!("Search already in progress"));
+        }
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+        let config = self.result_config.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        let Some(snapshot) = self.undo_snapshot.take_if(|snapshot| snapshot.mode() == result_mgr.get_mode()) else {
+            if self.undo_snapshot.is_some() {
+                return Err(anyhow!("Cannot merge a snapshot of another mode into {:?} results", result_mgr.get_mode()));
+            }
+            return Ok(None);
+        };
+
+        // 临时管理器使用单独的目录，避免与当前结果的精确磁盘文件同名
+        let merge_dir = config.cache_dir.join("merge");
+        std::fs::create_dir_all(&merge_dir)?;
+        let mut other = SearchResultManager::from_config(SearchResultManagerConfig { cache_dir: merge_dir, ..config.clone() });
+        other.restore(snapshot)?;
+        result_mgr.merge(other)?;
+        Ok(Some(result_mgr.total_count()))
+    }
+
+    /// Flushes all fuzzy results to their disk file so they survive the process being killed.
+    /// Returns the file path to pass to [`Self::restore_results`] after a restart, or None when there are no results.
+    /// History is not persisted; restored results start tracking from their current values.
+    pub fn persist_results(&mut self) -> Result<Option<PathBuf>> {
+        if self.is_searching() {
+            return Err(anyhow!("Search already in progress"));
+        }
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.persist_fuzzy_results()
+    }
+
+    /// Replaces the current results with fuzzy results persisted by [`Self::persist_results`].
+    /// Returns the restored result count.
+    pub fn restore_results(&mut self, path: &Path) -> Result<usize> {
+        if self.is_searching() {
+            return Err(anyhow!("Search already in progress"));
+        }
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.restore_fuzzy_results(path)
+    }
+
+    pub fn has_result_snapshot(&self) -> bool {
+        self.undo_snapshot.is_some()
+    }
+
+    /// 设置之后扫描得到的模糊结果的分组标签，非 0 标签会开启历史跟踪
+    pub fn set_fuzzy_tag(&mut self, tag: u16) -> Result<()> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.set_fuzzy_tag(tag);
+        Ok(())
+    }
+
+    /// Enables or disables per-result history (previous value, min/max, change count, tag) for fuzzy results.
+    /// History is stored in a side table only while enabled; disabling frees it.
+    pub fn set_history_tracking(&mut self, enabled: bool) -> Result<()> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.set_history_tracking(enabled);
+        Ok(())
+    }
+
+    /// History of the fuzzy result at `index`, or None when history tracking is off.
+    pub fn fuzzy_history(&self, index: usize) -> Option<FuzzyItemHistory> {
+        self.result_manager.as_ref().and_then(|result_mgr| result_mgr.fuzzy_history(index))
+    }
+
+    /// 设置之后新增结果要求的地址对齐，None 表示不限制
+    pub fn set_required_alignment(&mut self, alignment: Option<usize>) -> Result<()> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.set_required_alignment(alignment)
+    }
+
+    pub fn keep_only_tag(&mut self, tag: u16) -> Result<usize> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.keep_only_tag(tag)
+    }
+
+    pub fn remove_tag(&mut self, tag: u16) -> Result<usize> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.remove_tag(tag)
+    }
+
+    pub fn value_histogram(&self, buckets: usize) -> Result<Vec<(f64, f64, usize)>> {
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.value_histogram(buckets)
+    }
+
+    pub fn shrink_results(&mut self) -> Result<()> {
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
+
+        result_mgr.shrink_to_fit()
+    }
+
     pub fn set_result_mode(&mut self, mode: SearchResultMode) -> Result<()> {
-        let result_mgr = self.result_manager.as_mut().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         result_mgr.set_mode(mode)
     }
 
     pub fn add_results_batch(&mut self, results: Vec<SearchResultItem>) -> Result<()> {
-        let result_mgr = self.result_manager.as_mut().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         result_mgr.add_results_batch(results)
     }
@@ -1250,7 +1628,7 @@ impl SearchEngineManager {
     }
 
     pub fn get_current_mode(&self) -> Result<SearchResultMode> {
-        let result_mgr = self.result_manager.as_ref().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_ref().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         Ok(result_mgr.get_mode())
     }
@@ -1258,7 +1636,7 @@ impl SearchEngineManager {
     /// Legacy synchronous refine search method.
     #[deprecated]
     pub fn refine_search(&mut self, query: &SearchQuery, callback: Option<Arc<dyn SearchProgressCallback>>) -> Result<usize> {
-        let result_mgr = self.result_manager.as_mut().ok_or_else(|| anyhow!("SearchEngineManager not initialized"))?;
+        let result_mgr = self.result_manager.as_mut().ok_or_else(|| JniErrorCode::NotInitialized.error("SearchEngineManager not initialized"))?;
 
         let current_results: Vec<_> = match result_mgr.get_mode() {
             SearchResultMode::Exact => result_mgr
@@ -6,11 +6,16 @@ pub trait MemchrExt {
 
 impl MemchrExt for [u8] {
     fn find_aligned(&self, needle: &[u8], align: usize) -> Vec<usize> {
-        find_aligned_internal(self, needle, align)
+        scan_bytes(self, needle, align)
     }
 }
 
-fn find_aligned_internal(haystack: &[u8], needle: &[u8], align: usize) -> Vec<usize> {
+/// 在 haystack 中查找 needle 的所有出现位置（允许重叠）
+/// 使用 memchr 定位首字节作为候选，仅保留 stride 对齐的位置（stride 为 0 视为 1）
+///
+/// # 返回
+/// 返回所有匹配的起始偏移（升序）
+pub fn scan_bytes(haystack: &[u8], needle: &[u8], stride: usize) -> Vec<usize> {
     if needle.is_empty() {
         return vec![];
     }
@@ -18,20 +23,48 @@ fn find_aligned_internal(haystack: &[u8], needle: &[u8], align: usize) -> Vec<us
         return vec![];
     }
 
+    let stride = stride.max(1);
     let first = needle[0];
+    let last_start = haystack.len() - needle.len();
     let mut out = Vec::new();
 
-    for pos in memchr_iter(first, haystack) {
-        if pos % align != 0 {
+    for pos in memchr_iter(first, &haystack[..=last_start]) {
+        if pos % stride != 0 {
             continue;
         }
-        let end = pos + needle.len();
-        if end > haystack.len() {
-            continue;
-        }
-        if &haystack[pos..end] == needle {
+        if &haystack[pos..pos + needle.len()] == needle {
             out.push(pos);
         }
     }
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_bytes_overlapping() {
+        let haystack = b"aaaaa";
+        assert_eq!(scan_bytes(haystack, b"aa", 1), vec![0, 1, 2, 3]);
+        assert_eq!(scan_bytes(haystack, b"aaa", 0), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_scan_bytes_strided() {
+        // 0x11223344 出现在偏移 1, 4, 8，只有 4 与 8 按 4 字节对齐
+        let haystack = [0x00, 0x44, 0x33, 0x22, 0x44, 0x33, 0x22, 0x11, 0x44, 0x33, 0x22, 0x11];
+        let needle = [0x44, 0x33, 0x22];
+        assert_eq!(scan_bytes(&haystack, &needle, 1), vec![1, 4, 8]);
+        assert_eq!(scan_bytes(&haystack, &needle, 4), vec![4, 8]);
+        assert_eq!(haystack.find_aligned(&needle, 2), vec![4, 8]);
+    }
+
+    #[test]
+    fn test_scan_bytes_edge_cases() {
+        assert!(scan_bytes(b"abc", b"", 1).is_empty());
+        assert!(scan_bytes(b"ab", b"abc", 1).is_empty());
+        assert_eq!(scan_bytes(b"abc", b"abc", 1), vec![0]);
+        assert_eq!(scan_bytes(b"xxabc", b"bc", 1), vec![3]);
+    }
+}
@@ -1,5 +1,6 @@
 //! Search engine implementation modules.
 
+pub mod aob;
 mod batch_reader;
 pub mod filter;
 pub mod fuzzy_search;
@@ -9,6 +10,8 @@ pub mod shared_buffer;
 pub mod single_search;
 mod memchr_ext;
 
+pub use aob::aob_scan;
 pub use filter::SearchFilter;
+pub use memchr_ext::scan_bytes;
 pub use manager::{SearchEngineManager, SearchProgressCallback, ValuePair, BPLUS_TREE_ORDER, PAGE_MASK, PAGE_SIZE, SEARCH_ENGINE_MANAGER};
 pub use shared_buffer::{SearchErrorCode, SearchStatus, SharedBuffer, SHARED_BUFFER_SIZE};
@@ -10,6 +10,7 @@ pub enum Token<'a> {
     DoubleColon,
     Tilde,
     DoubleTilde,
+    Ampersand,
 }
 
 pub struct Lexer<'a> {
@@ -157,6 +158,10 @@ impl<'a> Lexer<'a> {
                         Ok(Some(Token::Tilde))
                     }
                 }
+                b'&' => {
+                    self.advance();
+                    Ok(Some(Token::Ampersand))
+                }
                 b'0'..=b'9' => self.read_number().map(Some),
                 b'A'..=b'Z' | b'a'..=b'z' => {
                     let start_pos = self.pos;
@@ -64,6 +64,10 @@ impl<'a> Parser<'a> {
 
                 self.parse_range(num_token, exclude)
             }
+            Some(Token::Ampersand) => {
+                self.advance();
+                self.parse_mask(num_token, None)
+            }
             Some(Token::Type(value_type)) => {
                 let value_type = *value_type;
                 self.advance();
@@ -72,6 +76,9 @@ impl<'a> Parser<'a> {
                     let exclude = matches!(self.peek(), Some(Token::DoubleTilde));
                     self.advance();
                     self.parse_range_with_type(num_token, value_type, exclude)
+                } else if matches!(self.peek(), Some(Token::Ampersand)) {
+                    self.advance();
+                    self.parse_mask(num_token, Some(value_type))
                 } else {
                     self.create_fixed_value(num_token, value_type)
                 }
@@ -126,6 +133,69 @@ impl<'a> Parser<'a> {
         self.create_range_value(start_token, end_token, value_type, exclude)
     }
 
+    /// 解析 `值&掩码` 中的掩码部分，类型可以写在值后或掩码后
+    fn parse_mask(&mut self, value_token: (&'a str, bool), value_type: Option<ValueType>) -> Result<SearchValue, String> {
+        let mask_token = match self.advance() {
+            Some(Token::Number(s, is_hex)) => (*s, *is_hex),
+            Some(token) => return Err(format!("Expected mask after '&', got {:?}", token)),
+            None => return Err("Expected mask after '&', got EOF".to_string()),
+        };
+
+        let value_type = match (value_type, self.peek()) {
+            (None, Some(Token::Type(vt))) => {
+                let vt = *vt;
+                self.advance();
+                vt
+            }
+            (Some(value_type), Some(Token::Type(mask_type))) => {
+                if *mask_type != value_type {
+                    return Err(format!("Mask type mismatch: value is {}, mask is {}", value_type, mask_type));
+                }
+                self.advance();
+                value_type
+            }
+            (value_type, _) => value_type.unwrap_or(self.default_type),
+        };
+
+        self.create_masked_value(value_token, mask_token, value_type)
+    }
+
+    fn create_masked_value(
+        &self,
+        value_token: (&'a str, bool),
+        mask_token: (&'a str, bool),
+        value_type: ValueType,
+    ) -> Result<SearchValue, String> {
+        if value_type.is_variable_length() {
+            return Err(format!("Masked search is not supported for {}", value_type));
+        }
+        let bits = value_type.size() * 8;
+        let fits = |v: i128| bits >= 128 || (v >= 0 && v >> bits == 0);
+
+        let mask = parse_number(mask_token.0, mask_token.1)?;
+        if mask == 0 {
+            return Err("Mask must not be zero".to_string());
+        }
+        if !fits(mask) {
+            return Err(format!("Mask 0x{:X} is wider than {}", mask, value_type));
+        }
+
+        // 浮点值按其原始位参与掩码比较
+        let value = match value_type {
+            ValueType::Float => (parse_float(value_token.0, value_token.1)? as f32).to_bits() as i128,
+            ValueType::Double => parse_float(value_token.0, value_token.1)?.to_bits() as i128,
+            _ => {
+                let value = parse_number(value_token.0, value_token.1)?;
+                if value > u64::MAX as i128 || value < i64::MIN as i128 {
+                    return Err(format!("Value {} is out of range for masked search", value));
+                }
+                value
+            }
+        };
+
+        Ok(SearchValue::masked(value, mask, value_type))
+    }
+
     fn create_fixed_value(&self, num_token: (&'a str, bool), value_type: ValueType) -> Result<SearchValue, String> {
         let (num_str, is_hex) = num_token;
 
@@ -331,6 +401,26 @@ mod tests {
         assert!(matches!(query.values[0], SearchValue::RangeFloat { .. }));
     }
 
+    #[test]
+    fn test_parse_masked_value() {
+        let query = parse_search_query("12340000h&FFFF0000hD", ValueType::Dword).unwrap();
+        let value = &query.values[0];
+        assert!(matches!(value, SearchValue::Masked { .. }));
+        assert!(value.matched(&0x1234ABCDu32.to_le_bytes()).unwrap());
+        assert!(!value.matched(&0x1235ABCDu32.to_le_bytes()).unwrap());
+
+        // 浮点值按原始位比较，只匹配高 16 位
+        let query = parse_search_query("1.5F&FFFF0000h", ValueType::Dword).unwrap();
+        assert_eq!(query.values[0].value_type(), ValueType::Float);
+        let near = f32::from_bits(1.5f32.to_bits() | 0x1234);
+        assert!(query.values[0].matched(&near.to_le_bytes()).unwrap());
+        assert!(!query.values[0].matched(&2.5f32.to_le_bytes()).unwrap());
+
+        assert!(parse_search_query("5&0", ValueType::Dword).is_err());
+        assert!(parse_search_query("5&1FFFFh", ValueType::Word).is_err());
+        assert!(parse_search_query("5D&FFhW", ValueType::Dword).is_err());
+    }
+
     #[test]
     fn test_parse_float_with_comma_separator() {
         let query = parse_search_query("1,234.56F", ValueType::Float).unwrap();
@@ -1,13 +1,18 @@
 mod exact;
+mod export;
 mod fuzzy;
+mod packed;
 
-use super::types::ValueType;
+use super::types::{ByteOrder, FuzzyCompareOptions, FuzzyCondition, ValueType};
+use crate::ext::jni::JniErrorCode;
 pub use crate::search::result_manager::exact::ExactSearchResultItem;
 use crate::search::result_manager::exact::ExactSearchResultManager;
-pub use crate::search::result_manager::fuzzy::{FuzzySearchResultItem, FuzzySearchResultManager};
+pub use crate::search::result_manager::fuzzy::{
+    DiskSpillConfig, FuzzyItemHistory, FuzzyResultSet, FuzzySearchResultItem, FuzzySearchResultManager, SessionDiff,
+};
 use anyhow::{Result, anyhow};
-use log::{debug, error, info};
-use std::path::PathBuf;
+use log::{debug, error};
+use std::path::{Path, PathBuf};
 use crate::search::engine::ValuePair;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -47,18 +52,110 @@ impl From<&ValuePair> for SearchResultItem {
     }
 }
 
+/// 当前结果集的内存/磁盘占用统计（只计算存活结果，不含预分配空间）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResultUsageStats {
+    pub mode: SearchResultMode,
+    pub total_count: usize,
+    pub memory_count: usize,
+    pub disk_count: usize,
+    pub memory_bytes: usize,
+    pub disk_bytes: usize,
+}
+
+impl ResultUsageStats {
+    pub fn total_bytes(&self) -> usize {
+        self.memory_bytes + self.disk_bytes
+    }
+}
+
+/// 结果集快照：按存储顺序拷贝的全部结果，用于撤销一次细化
+#[derive(Debug, Clone)]
+pub enum ResultSnapshot {
+    Exact(Vec<ExactSearchResultItem>),
+    Fuzzy(FuzzyResultSet),
+}
+
+impl ResultSnapshot {
+    pub fn len(&self) -> usize {
+        match self {
+            ResultSnapshot::Exact(items) => items.len(),
+            ResultSnapshot::Fuzzy(results) => results.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn mode(&self) -> SearchResultMode {
+        match self {
+            ResultSnapshot::Exact(_) => SearchResultMode::Exact,
+            ResultSnapshot::Fuzzy(_) => SearchResultMode::Fuzzy,
+        }
+    }
+}
+
+/// 结果管理器的内存与磁盘策略，按设备调整时统一在这里配置
+#[derive(Debug, Clone)]
+pub struct SearchResultManagerConfig {
+    /// 精确结果的内存缓冲区大小（字节），0 表示直接写入磁盘
+    pub exact_memory_budget: usize,
+    /// 模糊结果的内存缓冲区大小（字节），0 表示直接写入磁盘
+    pub fuzzy_memory_budget: usize,
+    /// 磁盘文件的初始大小与每次扩展的大小（字节）
+    pub disk_growth_increment: usize,
+    pub cache_dir: PathBuf,
+}
+
+impl SearchResultManagerConfig {
+    /// 精确与模糊结果使用相同的内存预算，磁盘按默认的 128 MB 扩展
+    pub fn new(memory_buffer_size: usize, cache_dir: PathBuf) -> Self {
+        Self {
+            exact_memory_budget: memory_buffer_size,
+            fuzzy_memory_budget: memory_buffer_size,
+            disk_growth_increment: DiskSpillConfig::default().growth_increment,
+            cache_dir,
+        }
+    }
+
+    /// 不使用内存缓冲区，所有结果直接写入磁盘
+    pub fn direct_disk(mut self) -> Self {
+        self.exact_memory_budget = 0;
+        self.fuzzy_memory_budget = 0;
+        self
+    }
+}
+
 pub(crate) struct SearchResultManager {
     current_mode: SearchResultMode,
     exact: ExactSearchResultManager,
     fuzzy: FuzzySearchResultManager,
+    /// 新增模糊结果的分组标签
+    fuzzy_tag: u16,
+    /// 新增结果要求的地址对齐上限，见 set_required_alignment
+    required_alignment: Option<usize>,
 }
 
 impl SearchResultManager {
+    #[cfg(test)]
     pub fn new(memory_buffer_size: usize, cache_dir: PathBuf) -> Self {
+        Self::from_config(SearchResultManagerConfig::new(memory_buffer_size, cache_dir))
+    }
+
+    pub fn from_config(config: SearchResultManagerConfig) -> Self {
+        let disk_config = DiskSpillConfig {
+            initial_size: config.disk_growth_increment,
+            growth_increment: config.disk_growth_increment,
+            geometric: false,
+        };
         Self {
             current_mode: SearchResultMode::Exact,
-            exact: ExactSearchResultManager::new(memory_buffer_size, cache_dir.clone()),
-            fuzzy: FuzzySearchResultManager::new(memory_buffer_size, cache_dir),
+            exact: ExactSearchResultManager::new(config.exact_memory_budget, config.cache_dir.clone())
+                .with_growth_increment(config.disk_growth_increment),
+            fuzzy: FuzzySearchResultManager::new(config.fuzzy_memory_budget, config.cache_dir).with_disk_config(disk_config),
+            fuzzy_tag: 0,
+            required_alignment: None,
         }
     }
 
@@ -91,18 +188,53 @@ impl SearchResultManager {
         Ok(())
     }
 
+    /// 未对齐的结果会被跳过，见 set_required_alignment
     pub fn add_result(&mut self, item: SearchResultItem) -> Result<()> {
         match (self.current_mode, item) {
             (SearchResultMode::Exact, SearchResultItem::Exact(exact_item)) => {
+                if !self.is_aligned(exact_item.address, exact_item.typ) {
+                    return Ok(());
+                }
                 self.exact.add_result(exact_item)
             },
             (SearchResultMode::Fuzzy, SearchResultItem::Fuzzy(fuzzy_item)) => {
+                if !self.is_aligned(fuzzy_item.address, fuzzy_item.value_type) {
+                    return Ok(());
+                }
                 self.fuzzy.add_result(fuzzy_item)
             },
             _ => Err(anyhow!("Mismatched SearchResultMode and SearchResultItem type")),
         }
     }
 
+    /// 将另一个管理器的结果追加到当前结果之后，两者必须处于相同模式
+    /// 不做去重，合并后的结果数量等于两者之和
+    pub fn merge(&mut self, other: SearchResultManager) -> Result<()> {
+        if self.current_mode != other.current_mode {
+            return Err(anyhow!(
+                "Cannot merge result managers in different modes: {:?} vs {:?}",
+                self.current_mode,
+                other.current_mode
+            ));
+        }
+
+        match self.current_mode {
+            SearchResultMode::Exact => {
+                for item in other.exact.iter() {
+                    self.exact.add_result(item)?;
+                }
+            },
+            SearchResultMode::Fuzzy => {
+                for item in other.fuzzy.iter() {
+                    self.fuzzy.add_result(item)?;
+                }
+            },
+        }
+
+        debug!("Merged {} results, total {}", other.total_count(), self.total_count());
+        Ok(())
+    }
+
     pub fn add_results_batch(&mut self, results: Vec<SearchResultItem>) -> Result<()> {
         for result in results {
             self.add_result(result)?;
@@ -110,25 +242,134 @@ impl SearchResultManager {
         Ok(())
     }
 
-    /// 添加模糊搜索结果（直接使用 FuzzySearchResultItem）
+    /// 设置之后新增的模糊结果所属的分组标签，已有结果不受影响
+    /// 标签保存在历史信息中，设置非 0 标签时自动开启历史跟踪
+    pub fn set_fuzzy_tag(&mut self, tag: u16) {
+        self.fuzzy_tag = tag;
+        if tag != 0 {
+            self.fuzzy.set_history_tracking(true);
+        }
+    }
+
+    /// 开启或关闭模糊结果的历史跟踪，见 [`FuzzySearchResultManager::set_history_tracking`]
+    pub fn set_history_tracking(&mut self, enabled: bool) {
+        self.fuzzy.set_history_tracking(enabled);
+    }
+
+    /// 第 `index` 项模糊结果的历史信息，未开启历史跟踪时返回 None
+    pub fn fuzzy_history(&self, index: usize) -> Option<FuzzyItemHistory> {
+        self.fuzzy.history(index)
+    }
+
+    /// 设置模糊细化的比较选项，见 [`FuzzySearchResultManager::set_compare_options`]
+    pub fn set_compare_options(&mut self, options: FuzzyCompareOptions) {
+        self.fuzzy.set_compare_options(options);
+    }
+
+    pub fn byte_order(&self) -> ByteOrder {
+        self.fuzzy.byte_order()
+    }
+
+    /// 设置新增结果要求的地址对齐：每项地址须按 min(alignment, 值类型的自然对齐) 对齐，
+    /// 否则 add_result 等新增入口直接跳过该项；None 表示不限制。已有结果、快照恢复与合并不受影响
+    /// alignment 须为 2 的幂
+    pub fn set_required_alignment(&mut self, alignment: Option<usize>) -> Result<()> {
+        if let Some(alignment) = alignment && !alignment.is_power_of_two() {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Alignment must be a power of two: {}", alignment)));
+        }
+        self.required_alignment = alignment;
+        Ok(())
+    }
+
+    fn is_aligned(&self, address: u64, value_type: ValueType) -> bool {
+        self.required_alignment
+            .is_none_or(|alignment| address.is_multiple_of(alignment.min(value_type.alignment()) as u64))
+    }
+
+    /// 添加模糊搜索结果（直接使用 FuzzySearchResultItem），标记为当前分组标签
     pub fn add_fuzzy_result(&mut self, item: FuzzySearchResultItem) -> Result<()> {
         if self.current_mode != SearchResultMode::Fuzzy {
             return Err(anyhow!("Not in fuzzy mode"));
         }
-        self.fuzzy.add_result(item)
+        if !self.is_aligned(item.address, item.value_type) {
+            return Ok(());
+        }
+        self.fuzzy.add_result_with_history(item, FuzzyItemHistory::new(&item).with_tag(self.fuzzy_tag))
     }
 
-    /// 批量添加模糊搜索结果
+    /// 批量添加模糊搜索结果，标记为当前分组标签
     pub fn add_fuzzy_results_batch(&mut self, results: Vec<FuzzySearchResultItem>) -> Result<()> {
         if self.current_mode != SearchResultMode::Fuzzy {
             return Err(anyhow!("Not in fuzzy mode"));
         }
         for item in results {
-            self.fuzzy.add_result(item)?;
+            if self.is_aligned(item.address, item.value_type) {
+                self.fuzzy.add_result_with_history(item, FuzzyItemHistory::new(&item).with_tag(self.fuzzy_tag))?;
+            }
         }
         Ok(())
     }
 
+    /// 用新读取的原始字节更新指定索引的模糊结果，开启历史跟踪时当前值移入上一次的值
+    pub fn update_fuzzy_value(&mut self, index: usize, new_bytes: &[u8]) -> Result<()> {
+        if self.current_mode != SearchResultMode::Fuzzy {
+            return Err(anyhow!("Not in fuzzy mode"));
+        }
+        let Some(item) = self.fuzzy.get_results(index, 1)?.pop() else {
+            return Err(JniErrorCode::OutOfBounds.error(format!("Index out of bounds: {} >= {}", index, self.fuzzy.total_count())));
+        };
+        let updated = item.with_new_value_ordered(new_bytes, self.fuzzy.byte_order());
+        self.fuzzy.update_result(index, updated)
+    }
+
+    /// 用按结果顺序排列的新值原地细化模糊结果，见 [`FuzzySearchResultManager::refine_in_place`]
+    pub fn refine_fuzzy_in_place(&mut self, fresh_values: &[u8], condition: FuzzyCondition) -> Result<usize> {
+        match self.current_mode {
+            SearchResultMode::Exact => Err(anyhow!("Not in fuzzy mode")),
+            SearchResultMode::Fuzzy => self.fuzzy.refine_in_place(fresh_values, condition),
+        }
+    }
+
+    /// 仅保留指定分组标签的模糊结果，返回保留数量
+    pub fn keep_only_tag(&mut self, tag: u16) -> Result<usize> {
+        match self.current_mode {
+            SearchResultMode::Exact => Err(anyhow!("Result tags are only supported in fuzzy mode")),
+            SearchResultMode::Fuzzy => self.fuzzy.keep_only_tag(tag),
+        }
+    }
+
+    /// 模糊结果的值分布直方图，见 [`FuzzySearchResultManager::value_histogram`]
+    pub fn value_histogram(&self, buckets: usize) -> Result<Vec<(f64, f64, usize)>> {
+        match self.current_mode {
+            SearchResultMode::Exact => Err(anyhow!("Value histograms are only supported in fuzzy mode")),
+            SearchResultMode::Fuzzy => self.fuzzy.value_histogram(buckets),
+        }
+    }
+
+    /// 按当前值排序的模糊结果副本及其地址顺序索引，见 [`FuzzySearchResultManager::sorted_by_value`]
+    pub fn sorted_by_value(&self, descending: bool) -> Result<Vec<(usize, FuzzySearchResultItem)>> {
+        match self.current_mode {
+            SearchResultMode::Exact => Err(anyhow!("Sorting by value is only supported in fuzzy mode")),
+            SearchResultMode::Fuzzy => self.fuzzy.sorted_by_value_indexed(descending),
+        }
+    }
+
+    /// 模糊结果的连续地址区段，见 [`FuzzySearchResultManager::contiguous_runs`]
+    pub fn contiguous_runs(&self, stride: usize) -> Result<Vec<(u64, usize)>> {
+        match self.current_mode {
+            SearchResultMode::Exact => Err(anyhow!("Contiguous runs are only supported in fuzzy mode")),
+            SearchResultMode::Fuzzy => self.fuzzy.contiguous_runs(stride),
+        }
+    }
+
+    /// 移除指定分组标签的模糊结果，返回移除数量
+    pub fn remove_tag(&mut self, tag: u16) -> Result<usize> {
+        match self.current_mode {
+            SearchResultMode::Exact => Err(anyhow!("Result tags are only supported in fuzzy mode")),
+            SearchResultMode::Fuzzy => self.fuzzy.remove_tag(tag),
+        }
+    }
+
     pub fn get_results(&self, start: usize, size: usize) -> Result<Vec<SearchResultItem>> {
         match self.current_mode {
             SearchResultMode::Exact => {
@@ -142,6 +383,22 @@ impl SearchResultManager {
         }
     }
 
+    /// 按页获取结果，返回该页结果与总页数
+    /// 页码超出范围时取最后一页，而不是返回空结果；没有结果时返回空结果与 0 页
+    pub fn get_page(&self, page: usize, page_size: usize) -> Result<(Vec<SearchResultItem>, usize)> {
+        if page_size == 0 {
+            return Err(JniErrorCode::InvalidArgument.error("Page size must be greater than 0"));
+        }
+
+        let total_pages = self.total_count().div_ceil(page_size);
+        if total_pages == 0 {
+            return Ok((Vec::new(), 0));
+        }
+
+        let page = page.min(total_pages - 1);
+        Ok((self.get_results(page * page_size, page_size)?, total_pages))
+    }
+
     pub fn total_count(&self) -> usize {
         match self.current_mode {
             SearchResultMode::Exact => self.exact.total_count(),
@@ -149,6 +406,61 @@ impl SearchResultManager {
         }
     }
 
+    /// 汇总当前模式下结果集的内存与磁盘占用
+    pub fn usage_stats(&self) -> ResultUsageStats {
+        let (total_count, memory_count, disk_count, memory_bytes, disk_bytes) = match self.current_mode {
+            SearchResultMode::Exact => (
+                self.exact.total_count(),
+                self.exact.memory_count(),
+                self.exact.disk_count(),
+                self.exact.memory_bytes(),
+                self.exact.disk_bytes(),
+            ),
+            SearchResultMode::Fuzzy => (
+                self.fuzzy.total_count(),
+                self.fuzzy.memory_count(),
+                self.fuzzy.disk_count(),
+                self.fuzzy.memory_bytes(),
+                self.fuzzy.disk_bytes(),
+            ),
+        };
+        ResultUsageStats {
+            mode: self.current_mode,
+            total_count,
+            memory_count,
+            disk_count,
+            memory_bytes,
+            disk_bytes,
+        }
+    }
+
+    /// 拷贝当前模式下的全部结果
+    pub fn snapshot(&self) -> Result<ResultSnapshot> {
+        Ok(match self.current_mode {
+            SearchResultMode::Exact => ResultSnapshot::Exact(self.exact.iter().collect()),
+            SearchResultMode::Fuzzy => ResultSnapshot::Fuzzy(self.fuzzy.result_set()),
+        })
+    }
+
+    /// 用快照替换当前结果，必要时切换到快照所属的模式
+    pub fn restore(&mut self, snapshot: ResultSnapshot) -> Result<()> {
+        match snapshot {
+            ResultSnapshot::Exact(items) => {
+                self.set_mode(SearchResultMode::Exact)?;
+                self.exact.clear()?;
+                for item in items {
+                    self.exact.add_result(item)?;
+                }
+            },
+            ResultSnapshot::Fuzzy(results) => {
+                self.set_mode(SearchResultMode::Fuzzy)?;
+                self.fuzzy.replace_all(results)?;
+            },
+        }
+        debug!("Restored {} results from snapshot", self.total_count());
+        Ok(())
+    }
+
     pub fn remove_result(&mut self, index: usize) -> Result<()> {
         match self.current_mode {
             SearchResultMode::Exact => self.exact.remove_result(index),
@@ -170,6 +482,57 @@ impl SearchResultManager {
         }
     }
 
+    /// 释放当前模式下结果集缩小后多余的内存和磁盘空间，通常在大量删除结果之后调用
+    pub fn shrink_to_fit(&mut self) -> Result<()> {
+        match self.current_mode {
+            SearchResultMode::Exact => self.exact.shrink_to_fit(),
+            SearchResultMode::Fuzzy => self.fuzzy.shrink_to_fit(),
+        }
+    }
+
+    /// 返回地址与 `address` 相距不超过 `window` 字节的结果及其索引，用于查看相邻的结构体字段
+    pub fn find_near(&self, address: u64, window: u64) -> Result<Vec<(usize, SearchResultItem)>> {
+        let min = address.saturating_sub(window);
+        let max = address.saturating_add(window);
+
+        Ok(match self.current_mode {
+            SearchResultMode::Exact => self
+                .exact
+                .find_in_range(min, max)
+                .into_iter()
+                .map(|(index, item)| (index, SearchResultItem::Exact(item)))
+                .collect(),
+            SearchResultMode::Fuzzy => self
+                .fuzzy
+                .find_in_range(min, max)
+                .into_iter()
+                .map(|(index, item)| (index, SearchResultItem::Fuzzy(item)))
+                .collect(),
+        })
+    }
+
+    /// 当前模式下所有结果的地址，顺序与结果索引一致
+    pub fn addresses(&self) -> Result<Vec<u64>> {
+        match self.current_mode {
+            SearchResultMode::Exact => self.exact.addresses(),
+            SearchResultMode::Fuzzy => self.fuzzy.addresses(),
+        }
+    }
+
+    pub fn filter_address_range(&mut self, min: u64, max: u64) -> Result<()> {
+        match self.current_mode {
+            SearchResultMode::Exact => self.exact.filter_address_range(min, max),
+            SearchResultMode::Fuzzy => self.fuzzy.filter_address_range(min, max),
+        }
+    }
+
+    pub fn intersect_with(&mut self, other_addresses: &[u64]) -> Result<()> {
+        match self.current_mode {
+            SearchResultMode::Exact => self.exact.intersect_with(other_addresses),
+            SearchResultMode::Fuzzy => self.fuzzy.intersect_with(other_addresses),
+        }
+    }
+
     pub fn get_mode(&self) -> SearchResultMode {
         self.current_mode
     }
@@ -189,11 +552,34 @@ impl SearchResultManager {
         }
     }
 
+    /// 获取所有模糊搜索结果及其历史信息
+    pub fn get_fuzzy_result_set(&self) -> Result<FuzzyResultSet> {
+        match self.current_mode {
+            SearchResultMode::Exact => Err(anyhow!("Cannot get fuzzy results in exact mode")),
+            SearchResultMode::Fuzzy => Ok(self.fuzzy.result_set()),
+        }
+    }
+
     /// 批量替换所有模糊搜索结果（用于细化搜索后）
-    pub fn replace_all_fuzzy_results(&mut self, results: Vec<FuzzySearchResultItem>) -> Result<()> {
+    pub fn replace_all_fuzzy_results(&mut self, results: FuzzyResultSet) -> Result<()> {
         if self.current_mode != SearchResultMode::Fuzzy {
             return Err(anyhow!("Not in fuzzy mode"));
         }
         self.fuzzy.replace_all(results)
     }
+
+    /// 将模糊结果全部落盘，返回磁盘文件路径；没有任何结果时返回 None
+    pub fn persist_fuzzy_results(&mut self) -> Result<Option<PathBuf>> {
+        if self.current_mode != SearchResultMode::Fuzzy {
+            return Err(anyhow!("Not in fuzzy mode"));
+        }
+        self.fuzzy.flush_disk_file()?;
+        Ok(self.fuzzy.disk_file_path().map(Path::to_path_buf))
+    }
+
+    /// 切换到模糊模式并从落盘的磁盘文件恢复结果，返回恢复的结果数量
+    pub fn restore_fuzzy_results(&mut self, path: &Path) -> Result<usize> {
+        self.set_mode(SearchResultMode::Fuzzy)?;
+        self.fuzzy.reopen_disk_file(path)
+    }
 }
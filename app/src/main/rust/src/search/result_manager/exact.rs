@@ -1,10 +1,14 @@
-use crate::search::{SearchResultItem, ValueType};
+use crate::ext::jni::JniErrorCode;
+use crate::search::{PAGE_SIZE, SearchResultItem, ValueType};
 use crate::search::result_manager::SearchResultManager;
+use anyhow::{Context, anyhow};
 use log::{debug, info};
 use memmap2::MmapMut;
 use std::fs::{File, OpenOptions};
 use std::path::PathBuf;
 
+/// 精确搜索结果项，只记录地址和类型
+/// 变长类型（String/Bytes）的长度即搜索内容的长度，同一次搜索的结果长度相同，因此不单独存储
 #[repr(packed)]
 #[derive(Debug, Clone, Copy)]
 pub struct ExactSearchResultItem {
@@ -16,6 +20,11 @@ impl ExactSearchResultItem {
     pub fn new(address: u64, typ: ValueType) -> Self {
         ExactSearchResultItem { address, typ }
     }
+
+    /// 按结果类型格式化从 `address` 读取的字节（精确结果本身不保存值）
+    pub fn format_value(&self, bytes: &[u8]) -> String {
+        self.typ.format_bytes(bytes)
+    }
 }
 
 impl From<(u64, ValueType)> for ExactSearchResultItem {
@@ -33,9 +42,13 @@ pub struct ExactSearchResultManager {
     mmap: Option<MmapMut>,
     disk_count: usize,
     total_count: usize,
+    /// 磁盘文件的初始大小与每次扩展的大小
+    growth_increment: usize,
 }
 
 impl ExactSearchResultManager {
+    const DEFAULT_GROWTH_INCREMENT: usize = 128 * 1024 * 1024;
+
     pub fn new(memory_buffer_size: usize, cache_dir: PathBuf) -> Self {
         let capacity = if memory_buffer_size == 0 {
             0
@@ -66,9 +79,16 @@ impl ExactSearchResultManager {
             mmap: None,
             disk_count: 0,
             total_count: 0,
+            growth_increment: Self::DEFAULT_GROWTH_INCREMENT,
         }
     }
 
+    /// 设置磁盘文件的初始大小与每次扩展的大小，下次创建或扩展磁盘文件时生效
+    pub fn with_growth_increment(mut self, growth_increment: usize) -> Self {
+        self.growth_increment = growth_increment.max(size_of::<ExactSearchResultItem>());
+        self
+    }
+
     pub fn clear(&mut self) -> anyhow::Result<()> {
         self.memory_buffer.clear();
         self.total_count = 0;
@@ -115,37 +135,53 @@ impl ExactSearchResultManager {
             self.init_disk_file()?;
         }
 
-        if let Some(ref mut mmap) = self.mmap {
-            let offset = self.disk_count * size_of::<ExactSearchResultItem>();
-            let mmap_size = mmap.len();
-
-            if offset + size_of::<ExactSearchResultItem>() > mmap_size {
-                drop(self.mmap.take());
-                let new_size = mmap_size + 128 * 1024 * 1024;
-                if let Some(ref file) = self.disk_file {
-                    file.set_len(new_size as u64)?;
-                }
-                self.mmap = Some(unsafe { MmapMut::map_mut(self.disk_file.as_ref().unwrap())? });
-            }
-
-            let mmap = self.mmap.as_mut().unwrap();
-            unsafe {
-                let ptr = mmap.as_mut_ptr().add(offset) as *mut ExactSearchResultItem;
-                ptr.write(*item);
-            }
+        let offset = self.disk_count * size_of::<ExactSearchResultItem>();
+        let mmap_size = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+        if offset + size_of::<ExactSearchResultItem>() > mmap_size {
+            self.grow_disk_file(mmap_size + self.growth_increment)?;
+        }
 
-            self.disk_count += 1;
+        // 磁盘文件存在但没有映射时不能静默丢弃结果，否则 total_count 与实际数据不一致
+        let mmap = self.mmap.as_mut().ok_or_else(|| anyhow!("Disk file is not mapped"))?;
+        unsafe {
+            let ptr = mmap.as_mut_ptr().add(offset) as *mut ExactSearchResultItem;
+            ptr.write(*item);
         }
 
+        self.disk_count += 1;
         Ok(())
     }
 
+    /// 扩展磁盘文件并重新映射
+    /// 新映射成功之前保留旧映射，失败时回滚文件长度，已写入的结果仍可正常读取
+    fn grow_disk_file(&mut self, new_size: usize) -> anyhow::Result<()> {
+        let file = self.disk_file.as_ref().ok_or_else(|| anyhow!("Disk file is not initialized"))?;
+        let old_size = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+
+        file.set_len(new_size as u64)
+            .with_context(|| format!("Failed to grow disk file to {} MB", new_size / 1024 / 1024))?;
+
+        match unsafe { MmapMut::map_mut(file) } {
+            Ok(mmap) => {
+                self.mmap = Some(mmap);
+                debug!("Disk file grown: {} MB -> {} MB", old_size / 1024 / 1024, new_size / 1024 / 1024);
+                Ok(())
+            },
+            Err(e) => {
+                if old_size > 0 {
+                    let _ = file.set_len(old_size as u64);
+                }
+                Err(anyhow!(e).context(format!("Failed to remap disk file at {} MB", new_size / 1024 / 1024)))
+            },
+        }
+    }
+
     fn init_disk_file(&mut self) -> anyhow::Result<()> {
         let file_path = self.cache_dir.join("mamu_search_results.bin");
 
         debug!("Creating disk file: {:?}", file_path);
 
-        let initial_size = 128 * 1024 * 1024;
+        let initial_size = self.growth_increment;
         let file = OpenOptions::new()
             .read(true)
             .write(true)
@@ -221,9 +257,90 @@ impl ExactSearchResultManager {
         self.disk_count
     }
 
+    /// 内存缓冲区中实际存活结果占用的字节数
+    pub fn memory_bytes(&self) -> usize {
+        self.memory_buffer.len() * size_of::<ExactSearchResultItem>()
+    }
+
+    /// 磁盘上实际存活结果占用的字节数（不含 mmap 预分配的空间）
+    pub fn disk_bytes(&self) -> usize {
+        self.disk_count * size_of::<ExactSearchResultItem>()
+    }
+
+    /// 按顺序遍历所有结果（先内存缓冲区，后磁盘 mmap），不分配内存
+    pub fn iter(&self) -> impl Iterator<Item = ExactSearchResultItem> + '_ {
+        let disk: &[ExactSearchResultItem] = match self.mmap {
+            Some(ref mmap) if self.disk_count > 0 => unsafe {
+                std::slice::from_raw_parts(mmap.as_ptr() as *const ExactSearchResultItem, self.disk_count)
+            },
+            _ => &[],
+        };
+        self.memory_buffer.iter().chain(disk.iter()).copied()
+    }
+
+    /// 按结果顺序返回所有地址，只拷贝地址字段，用于指针扫描
+    pub fn addresses(&self) -> anyhow::Result<Vec<u64>> {
+        let mut addresses = Vec::with_capacity(self.total_count);
+        addresses.extend(self.iter().map(|item| item.address));
+        Ok(addresses)
+    }
+
+    /// 返回地址位于 [min, max] 内的结果及其索引
+    pub fn find_in_range(&self, min: u64, max: u64) -> Vec<(usize, ExactSearchResultItem)> {
+        self.iter()
+            .enumerate()
+            .filter(|(_, item)| (min..=max).contains(&{ item.address }))
+            .collect()
+    }
+
+    /// 仅保留地址位于 [min, max] 内的项
+    pub fn filter_address_range(&mut self, min: u64, max: u64) -> anyhow::Result<()> {
+        if min > max {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid address range: 0x{:x} > 0x{:x}", min, max)));
+        }
+
+        let keep_indices: Vec<usize> = self
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| (min..=max).contains(&{ item.address }))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        self.keep_only_results(keep_indices)?;
+        debug!("Address range filter [0x{:x}, 0x{:x}] kept {} results", min, max, self.total_count);
+        Ok(())
+    }
+
+    /// 仅保留地址出现在 `other_addresses` 中的项，`other_addresses` 须升序排列
+    /// 结果本身按地址有序，归并遍历一次即可完成求交，允许 `other_addresses` 含重复地址
+    pub fn intersect_with(&mut self, other_addresses: &[u64]) -> anyhow::Result<()> {
+        if !other_addresses.is_sorted() {
+            return Err(JniErrorCode::InvalidArgument.error("Addresses to intersect with must be sorted"));
+        }
+
+        let mut other = other_addresses.iter().peekable();
+        let keep_indices: Vec<usize> = self
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                let address = item.address;
+                while other.next_if(|&&other_address| other_address < address).is_some() {}
+                other.peek().is_some_and(|&&other_address| other_address == address)
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        self.keep_only_results(keep_indices)?;
+        debug!("Intersection with {} addresses kept {} results", other_addresses.len(), self.total_count);
+        Ok(())
+    }
+
+    /// 删除指定索引的结果，其后的结果依次前移
+    /// 只移动同一存储区（内存缓冲区或磁盘）中位于其后的结果：删除末尾附近的结果几乎不移动数据，
+    /// 删除磁盘区靠前的结果则要移动其后全部磁盘结果，一次删除多项时应使用 remove_results_batch
     pub fn remove_result(&mut self, index: usize) -> anyhow::Result<()> {
         if index >= self.total_count {
-            return Err(anyhow::anyhow!("Index out of bounds: {} >= {}", index, self.total_count));
+            return Err(JniErrorCode::OutOfBounds.error(format!("Index out of bounds: {} >= {}", index, self.total_count)));
         }
 
         if index < self.memory_buffer.len() {
@@ -404,6 +521,36 @@ impl ExactSearchResultManager {
         Ok(())
     }
 
+    /// 释放结果集缩小后多余的空间：内存缓冲区归还给分配器，磁盘文件截断到存活结果的大小（按页向上取整）
+    /// 磁盘上已没有结果时直接删除磁盘文件；之后再写入会按需重新扩展
+    pub fn shrink_to_fit(&mut self) -> anyhow::Result<()> {
+        self.memory_buffer.shrink_to_fit();
+
+        let Some(ref file) = self.disk_file else {
+            return Ok(());
+        };
+        if self.disk_count == 0 {
+            return self.clear_disk();
+        }
+
+        let page_size = *PAGE_SIZE;
+        let new_size = (self.disk_count * size_of::<ExactSearchResultItem>()).div_ceil(page_size) * page_size;
+        let old_size = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+        if new_size >= old_size {
+            return Ok(());
+        }
+
+        if let Some(ref mmap) = self.mmap {
+            mmap.flush()?;
+        }
+        drop(self.mmap.take());
+        file.set_len(new_size as u64)?;
+        self.mmap = Some(unsafe { MmapMut::map_mut(file)? });
+
+        info!("Exact disk file shrunk: {} KB -> {} KB", old_size / 1024, new_size / 1024);
+        Ok(())
+    }
+
     /// Keep only the specified results, remove all others
     /// Optimized: when keep_count < remove_count, rebuild instead of batch delete
     pub fn keep_only_results(&mut self, mut keep_indices: Vec<usize>) -> anyhow::Result<()> {
@@ -0,0 +1,105 @@
+//! 搜索结果导出（CSV / JSON），逐项写入 writer，避免为大结果集拼接整个字符串
+
+use super::{SearchResultManager, SearchResultMode};
+use super::exact::ExactSearchResultItem;
+use super::fuzzy::FuzzySearchResultItem;
+use anyhow::Result;
+use serde_json::json;
+use std::io::Write;
+
+const CSV_HEADER: &str = "address,type,raw,int,float";
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 模糊结果的原始字节只取值类型实际占用的部分，变长类型导出完整编码
+fn fuzzy_raw(item: &FuzzySearchResultItem) -> String {
+    let value = item.value;
+    if item.value_type.is_variable_length() {
+        return hex_bytes(&value);
+    }
+    let size = item.value_type.size().min(value.len());
+    hex_bytes(&value[..size])
+}
+
+impl SearchResultManager {
+    /// 以 CSV 导出当前结果：地址（十六进制）、值类型、原始字节、整数值、浮点值
+    /// 精确搜索结果不保存值，后三列留空
+    pub fn export_csv(&self, writer: impl Write) -> Result<()> {
+        let mut writer = writer;
+        writeln!(writer, "{}", CSV_HEADER)?;
+
+        match self.current_mode {
+            SearchResultMode::Exact => {
+                for item in self.exact.iter() {
+                    let ExactSearchResultItem { address, typ } = item;
+                    writeln!(writer, "0x{:x},{:?},,,", address, typ)?;
+                }
+            },
+            SearchResultMode::Fuzzy => {
+                for item in self.fuzzy.iter() {
+                    let address = item.address;
+                    let value_type = item.value_type;
+                    writeln!(
+                        writer,
+                        "0x{:x},{:?},{},{},{}",
+                        address,
+                        value_type,
+                        fuzzy_raw(&item),
+                        item.as_i64(),
+                        item.as_f64()
+                    )?;
+                }
+            },
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// 以 JSON 数组导出当前结果，字段与 CSV 相同；精确搜索结果只包含地址与类型
+    pub fn export_json(&self, writer: impl Write) -> Result<()> {
+        let mut writer = writer;
+        writer.write_all(b"[")?;
+
+        let mut first = true;
+        let mut write_entry = |writer: &mut dyn Write, entry: serde_json::Value| -> Result<()> {
+            if !first {
+                writer.write_all(b",")?;
+            }
+            first = false;
+            serde_json::to_writer(&mut *writer, &entry)?;
+            Ok(())
+        };
+
+        match self.current_mode {
+            SearchResultMode::Exact => {
+                for item in self.exact.iter() {
+                    let ExactSearchResultItem { address, typ } = item;
+                    write_entry(&mut writer, json!({
+                        "address": format!("0x{:x}", address),
+                        "type": format!("{:?}", typ),
+                    }))?;
+                }
+            },
+            SearchResultMode::Fuzzy => {
+                for item in self.fuzzy.iter() {
+                    let address = item.address;
+                    let value_type = item.value_type;
+                    write_entry(&mut writer, json!({
+                        "address": format!("0x{:x}", address),
+                        "type": format!("{:?}", value_type),
+                        "raw": fuzzy_raw(&item),
+                        "int": item.as_i64(),
+                        "float": item.as_f64(),
+                    }))?;
+                }
+            },
+        }
+
+        writer.write_all(b"]")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
@@ -1,15 +1,38 @@
-use crate::search::FuzzyCondition;
-use crate::search::types::ValueType;
-use anyhow::{Result, anyhow};
-use log::{debug, info};
-use memmap2::MmapMut;
+use crate::ext::jni::JniErrorCode;
+use crate::search::{FuzzyCondition, PAGE_SIZE, scan_bytes};
+use crate::search::types::{ByteOrder, FuzzyCompareOptions, ValueType};
+use super::packed::{PackedReader, PackedWriter, read_packed};
+use anyhow::{Context, Result, anyhow};
+use log::{debug, info, warn};
+use memmap2::{MmapMut, MmapOptions};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::mem::size_of;
-use std::path::PathBuf;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+/// 磁盘文件名前缀，完整文件名为 `{前缀}_{pid}_{序号}.bin`，同一缓存目录下的多个管理器或进程互不覆盖
+const DISK_FILE_PREFIX: &str = "mamu_fuzzy_results";
+
+/// 本进程内磁盘文件的序号
+static DISK_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 浮点值分桶精度，差值小于该精度的浮点数视为相同值
+const FLOAT_BUCKET_EPSILON: f64 = 1e-6;
+
+/// 变长类型（String/Bytes）在 8 字节值中的编码：
+/// [0..2] 长度（u16 LE，超过 u16::MAX 时饱和）
+/// [2..8] 长度不超过 6 时为原始字节（不足补 0），否则为 FNV-1a 哈希的低 6 字节
+/// 短文本可以直接还原，长文本通过地址 + 长度重新读取，哈希用于判断内容是否变化
+const VARIABLE_INLINE_MAX: usize = 6;
 
 /// 模糊搜索结果项 - 存储地址和当前值
 /// 使用 [u8; 8] 存储值（最大类型 Qword/Double 刚好 8 字节）
+/// 数值统一以小端存储，大端目标的值在构造时按 [`ByteOrder`] 转换，比较与解码无需关心字节序
+/// 上一次的值、最小/最大值、变化次数与分组标签不在结果项内，开启历史跟踪后见 [`FuzzyItemHistory`]
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy)]
 pub struct FuzzySearchResultItem {
@@ -54,342 +77,2133 @@ impl FuzzySearchResultItem {
         FuzzySearchResultItem { address, value, value_type }
     }
 
-    /// 从字节切片创建结果项
+    /// 从字节切片创建结果项，变长类型按 [`VARIABLE_INLINE_MAX`] 的规则编码
     #[inline]
     pub fn from_bytes(address: u64, bytes: &[u8], value_type: ValueType) -> Self {
-        let mut value = [0u8; 8];
-        let len = bytes.len().min(8);
-        value[..len].copy_from_slice(&bytes[..len]);
-        FuzzySearchResultItem { address, value, value_type }
+        Self::from_bytes_ordered(address, bytes, value_type, ByteOrder::Little)
+    }
+
+    /// 按目标字节序从字节切片创建结果项，数值类型转换为小端存储；变长类型没有字节序
+    #[inline]
+    pub fn from_bytes_ordered(address: u64, bytes: &[u8], value_type: ValueType, order: ByteOrder) -> Self {
+        let value = if value_type.is_variable_length() {
+            Self::encode_variable(bytes)
+        } else {
+            let mut value = [0u8; 8];
+            let len = bytes.len().min(8);
+            value[..len].copy_from_slice(&bytes[..len]);
+            match order {
+                ByteOrder::Little => value,
+                ByteOrder::Big => Self::big_to_little(value, value_type),
+            }
+        };
+        FuzzySearchResultItem::new(address, value, value_type)
+    }
+
+    /// 把按小端读入的原始字节改按 `order` 重新解码；扫描路径先按原始字节构造结果项，入库前再转换
+    #[inline]
+    pub fn reinterpret_order(&self, order: ByteOrder) -> Self {
+        if order == ByteOrder::Little || self.value_type.is_variable_length() {
+            return *self;
+        }
+        let value = self.value;
+        FuzzySearchResultItem::from_bytes_ordered(self.address, &value[..self.value_size()], self.value_type, order)
     }
 
-    /// 获取值的有效字节数
+    fn big_to_little(value: [u8; 8], value_type: ValueType) -> [u8; 8] {
+        let mut out = [0u8; 8];
+        match value_type.size() {
+            2 => out[..2].copy_from_slice(&u16::from_be_bytes(value[..2].try_into().unwrap()).to_le_bytes()),
+            4 => out[..4].copy_from_slice(&u32::from_be_bytes(value[..4].try_into().unwrap()).to_le_bytes()),
+            8 => out = u64::from_be_bytes(value).to_le_bytes(),
+            _ => out = value,
+        }
+        out
+    }
+
+    /// 获取值的有效字节数（变长类型返回记录的长度）
     #[inline]
     pub fn value_size(&self) -> usize {
-        self.value_type.size()
+        if self.value_type.is_variable_length() {
+            let value = self.value;
+            u16::from_le_bytes([value[0], value[1]]) as usize
+        } else {
+            self.value_type.size()
+        }
+    }
+
+    /// 变长类型的原始内容，仅当长度不超过 6 字节（内联存储）时可还原
+    pub fn variable_bytes(&self) -> Option<Vec<u8>> {
+        Self::decode_variable(self.value, self.value_type)
+    }
+
+    fn encode_variable(bytes: &[u8]) -> [u8; 8] {
+        let mut value = [0u8; 8];
+        let len = bytes.len().min(u16::MAX as usize) as u16;
+        value[..2].copy_from_slice(&len.to_le_bytes());
+        if bytes.len() <= VARIABLE_INLINE_MAX {
+            value[2..2 + bytes.len()].copy_from_slice(bytes);
+        } else {
+            // FNV-1a 64
+            let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, &b| (hash ^ b as u64).wrapping_mul(0x100000001b3));
+            value[2..].copy_from_slice(&hash.to_le_bytes()[..VARIABLE_INLINE_MAX]);
+        }
+        value
+    }
+
+    /// 按值类型格式化当前值，规则见 [`ValueType::format_bytes`]
+    /// 超过内联长度的变长值只保存了哈希，返回 "<长度> bytes"，需要按地址重新读取才能显示内容
+    pub fn format_value(&self) -> String {
+        Self::format_encoded(self.value, self.value_type)
+    }
+
+    fn format_encoded(value: [u8; 8], value_type: ValueType) -> String {
+        if !value_type.is_variable_length() {
+            return value_type.format_bytes(&value);
+        }
+        match Self::decode_variable(value, value_type) {
+            Some(bytes) => value_type.format_bytes(&bytes),
+            None => format!("{} bytes", u16::from_le_bytes([value[0], value[1]])),
+        }
+    }
+
+    fn decode_variable(value: [u8; 8], value_type: ValueType) -> Option<Vec<u8>> {
+        if !value_type.is_variable_length() {
+            return None;
+        }
+        let len = u16::from_le_bytes([value[0], value[1]]) as usize;
+        (len <= VARIABLE_INLINE_MAX).then(|| value[2..2 + len].to_vec())
     }
 
     /// 读取为 i64 值（用于整数比较）
     #[inline]
     pub fn as_i64(&self) -> i64 {
+        Self::decode_i64(self.value, self.value_type)
+    }
+
+    /// 读取为 u64 值：整数类型按各自宽度零扩展（如 Dword 0xFFFFFFFF 为 4294967295 而不是 -1）
+    #[inline]
+    pub fn as_u64(&self) -> u64 {
+        Self::decode_u64(self.value, self.value_type)
+    }
+
+    /// 读取为 f64 值（用于浮点数比较）
+    #[inline]
+    pub fn as_f64(&self) -> f64 {
+        Self::decode_f64(self.value, self.value_type)
+    }
+
+    fn decode_i64(value: [u8; 8], value_type: ValueType) -> i64 {
+        match value_type {
+            ValueType::Byte => value[0] as i8 as i64,
+            ValueType::Word => i16::from_le_bytes(value[..2].try_into().unwrap()) as i64,
+            ValueType::Dword | ValueType::Auto | ValueType::Xor => i32::from_le_bytes(value[..4].try_into().unwrap()) as i64,
+            ValueType::Qword => i64::from_le_bytes(value),
+            ValueType::Float => f32::from_le_bytes(value[..4].try_into().unwrap()) as i64,
+            ValueType::Double => f64::from_le_bytes(value) as i64,
+            // 变长类型没有数值含义，返回编码本身，仅用于判断是否变化
+            ValueType::String | ValueType::Bytes => i64::from_le_bytes(value),
+        }
+    }
+
+    fn decode_u64(value: [u8; 8], value_type: ValueType) -> u64 {
+        match value_type {
+            ValueType::Byte => value[0] as u64,
+            ValueType::Word => u16::from_le_bytes(value[..2].try_into().unwrap()) as u64,
+            ValueType::Dword | ValueType::Auto | ValueType::Xor => u32::from_le_bytes(value[..4].try_into().unwrap()) as u64,
+            ValueType::Qword => u64::from_le_bytes(value),
+            // 浮点数没有无符号形式，负数饱和为 0
+            ValueType::Float => f32::from_le_bytes(value[..4].try_into().unwrap()) as u64,
+            ValueType::Double => f64::from_le_bytes(value) as u64,
+            ValueType::String | ValueType::Bytes => u64::from_le_bytes(value),
+        }
+    }
+
+    fn decode_f64(value: [u8; 8], value_type: ValueType) -> f64 {
+        match value_type {
+            ValueType::Byte => value[0] as i8 as f64,
+            ValueType::Word => i16::from_le_bytes(value[..2].try_into().unwrap()) as f64,
+            ValueType::Dword | ValueType::Auto | ValueType::Xor => i32::from_le_bytes(value[..4].try_into().unwrap()) as f64,
+            ValueType::Qword => i64::from_le_bytes(value) as f64,
+            ValueType::Float => f32::from_le_bytes(value[..4].try_into().unwrap()) as f64,
+            ValueType::Double => f64::from_le_bytes(value),
+            ValueType::String | ValueType::Bytes => i64::from_le_bytes(value) as f64,
+        }
+    }
+
+    /// 检查新值是否满足模糊搜索条件（整数按有符号比较，小端解释）
+    #[inline]
+    pub fn matches_condition(&self, new_bytes: &[u8], condition: FuzzyCondition) -> bool {
+        self.matches_condition_opts(new_bytes, condition, FuzzyCompareOptions::default())
+    }
+
+    /// 同 [`Self::matches_condition_history`]，没有历史信息
+    #[inline]
+    pub fn matches_condition_opts(&self, new_bytes: &[u8], condition: FuzzyCondition, options: FuzzyCompareOptions) -> bool {
+        self.matches_condition_history(new_bytes, condition, options, None)
+    }
+
+    /// 按 `options` 检查新值是否满足模糊搜索条件：
+    /// - `unsigned` 为 true 时整数类型按无符号比较，浮点类型忽略该选项
+    /// - `wrapping` 为 true 时差值类条件（增加/减少指定量、容差、范围）按值类型的原生宽度回绕计算：
+    ///   差值取 `新值 - 旧值` 在该宽度下回绕后的有符号结果，例如 Byte 从 255 变为 0 视为增加 1，
+    ///   Dword 从 0x7FFFFFFF 变为 0x80000000 同样视为增加 1。
+    ///   回绕差值的范围受宽度限制（Byte 为 -128..=127），超出范围的增减量不会匹配；
+    ///   其余条件、浮点类型与变长类型不受影响
+    /// - `new_bytes` 按 `byte_order` 字节序解释
+    ///
+    /// "至少变化 N 次" 的计数来自 `history`；没有历史信息时只计入本次变化
+    pub fn matches_condition_history(
+        &self,
+        new_bytes: &[u8],
+        condition: FuzzyCondition,
+        options: FuzzyCompareOptions,
+        history: Option<&FuzzyItemHistory>,
+    ) -> bool {
+        let new_item = FuzzySearchResultItem::from_bytes_ordered(self.address, new_bytes, self.value_type, options.byte_order);
+        let changes = FuzzyItemHistory::count_change(history.map_or(0, |history| history.change_count), self, &new_item);
+
+        if options.wrapping
+            && let Some(matched) = self.matches_wrapping_diff(&new_item, condition)
+        {
+            return matched;
+        }
+
+        if self.value_type.is_float_type() {
+            self.matches_condition_float(&new_item, condition, changes)
+        } else if options.unsigned {
+            self.matches_condition_uint(&new_item, condition, changes)
+        } else {
+            self.matches_condition_int(&new_item, condition, changes)
+        }
+    }
+
+    /// 按回绕差值判断差值类条件，非整数类型或其余条件返回 None
+    fn matches_wrapping_diff(&self, new_item: &FuzzySearchResultItem, condition: FuzzyCondition) -> Option<bool> {
+        let diff = self.wrapping_diff(new_item)? as i128;
+
+        match condition {
+            FuzzyCondition::IncreasedBy(amount) => Some(diff == amount as i128),
+            FuzzyCondition::DecreasedBy(amount) => Some(diff == -(amount as i128)),
+            FuzzyCondition::IncreasedByTolerance(amount, tolerance) => Some((diff - amount as i128).abs() <= tolerance as i128),
+            FuzzyCondition::DecreasedByTolerance(amount, tolerance) => Some((diff + amount as i128).abs() <= tolerance as i128),
+            FuzzyCondition::IncreasedByRange(min, max) => Some(diff >= min as i128 && diff <= max as i128),
+            FuzzyCondition::DecreasedByRange(min, max) => Some(-diff >= min as i128 && -diff <= max as i128),
+            _ => None,
+        }
+    }
+
+    /// 按值类型原生宽度计算回绕差值，非整数类型返回 None
+    fn wrapping_diff(&self, new_item: &FuzzySearchResultItem) -> Option<i64> {
+        let old_val = self.as_u64();
+        let new_val = new_item.as_u64();
         match self.value_type {
-            ValueType::Byte => self.value[0] as i8 as i64,
-            ValueType::Word => i16::from_le_bytes(self.value[..2].try_into().unwrap()) as i64,
-            ValueType::Dword | ValueType::Auto | ValueType::Xor => i32::from_le_bytes(self.value[..4].try_into().unwrap()) as i64,
-            ValueType::Qword => i64::from_le_bytes(self.value),
-            ValueType::Float => f32::from_le_bytes(self.value[..4].try_into().unwrap()) as i64,
-            ValueType::Double => f64::from_le_bytes(self.value) as i64,
+            ValueType::Byte => Some((new_val as u8).wrapping_sub(old_val as u8) as i8 as i64),
+            ValueType::Word => Some((new_val as u16).wrapping_sub(old_val as u16) as i16 as i64),
+            ValueType::Dword | ValueType::Auto | ValueType::Xor => Some((new_val as u32).wrapping_sub(old_val as u32) as i32 as i64),
+            ValueType::Qword => Some(new_val.wrapping_sub(old_val) as i64),
+            ValueType::Float | ValueType::Double | ValueType::String | ValueType::Bytes => None,
+        }
+    }
+
+    fn matches_condition_int(&self, new_item: &FuzzySearchResultItem, condition: FuzzyCondition, changes: u32) -> bool {
+        let old_val = self.as_i64();
+        let new_val = new_item.as_i64();
+        let diff = new_val.wrapping_sub(old_val);
+
+        match condition {
+            FuzzyCondition::Initial => true,
+            FuzzyCondition::Unchanged => old_val == new_val,
+            FuzzyCondition::Changed => old_val != new_val,
+            FuzzyCondition::Increased => new_val > old_val,
+            FuzzyCondition::Decreased => new_val < old_val,
+            FuzzyCondition::IncreasedBy(amount) => diff == amount,
+            FuzzyCondition::DecreasedBy(amount) => diff == -amount,
+            // 在 i128 中计算偏差，数量或容差接近 i64 边界时不会溢出
+            FuzzyCondition::IncreasedByTolerance(amount, tolerance) => {
+                (diff as i128 - amount as i128).abs() <= tolerance as i128
+            },
+            FuzzyCondition::DecreasedByTolerance(amount, tolerance) => {
+                (diff as i128 + amount as i128).abs() <= tolerance as i128
+            },
+            FuzzyCondition::IncreasedByRange(min, max) => diff >= min && diff <= max,
+            FuzzyCondition::DecreasedByRange(min, max) => {
+                let neg_diff = -diff;
+                neg_diff >= min && neg_diff <= max
+            },
+            // 百分比按旧值的绝对值计算，负数旧值时 "增加 10%" 表示向正方向移动 |旧值| * 10%
+            // 变化量截断为整数，正数旧值时与原先 (旧值 * (1 + 百分比)) 截断的结果一致
+            // 阈值在 i128 中计算：旧值接近 i64 边界时阈值可能超出 i64，饱和到边界会让边界值误匹配
+            FuzzyCondition::IncreasedByPercent(percent) => {
+                if old_val == 0 {
+                    new_val > 0
+                } else {
+                    let delta = (old_val.unsigned_abs() as f64 * percent as f64) as i128;
+                    new_val as i128 >= old_val as i128 + delta
+                }
+            },
+            FuzzyCondition::DecreasedByPercent(percent) => {
+                if old_val == 0 {
+                    new_val < 0
+                } else {
+                    let delta = (old_val.unsigned_abs() as f64 * percent as f64) as i128;
+                    new_val as i128 <= old_val as i128 - delta
+                }
+            },
+            FuzzyCondition::Between(min, max) => new_val >= min && new_val <= max,
+            FuzzyCondition::BetweenFloat(min, max) => {
+                let new_val = new_val as f64;
+                new_val >= min && new_val <= max
+            },
+            FuzzyCondition::ChangedAtLeast(times) => changes >= times,
+            FuzzyCondition::EqualsNow(value) => new_val == value,
+            FuzzyCondition::EqualsNowFloat(value) => new_val as f64 == value,
+        }
+    }
+
+    /// 无符号比较：在 i128 中计算差值，Qword 全范围内也不会溢出
+    fn matches_condition_uint(&self, new_item: &FuzzySearchResultItem, condition: FuzzyCondition, changes: u32) -> bool {
+        let old_val = self.as_u64() as i128;
+        let new_val = new_item.as_u64() as i128;
+        let diff = new_val - old_val;
+
+        match condition {
+            FuzzyCondition::Initial => true,
+            FuzzyCondition::Unchanged => old_val == new_val,
+            FuzzyCondition::Changed => old_val != new_val,
+            FuzzyCondition::Increased => new_val > old_val,
+            FuzzyCondition::Decreased => new_val < old_val,
+            FuzzyCondition::IncreasedBy(amount) => diff == amount as i128,
+            FuzzyCondition::DecreasedBy(amount) => diff == -(amount as i128),
+            FuzzyCondition::IncreasedByTolerance(amount, tolerance) => (diff - amount as i128).abs() <= tolerance as i128,
+            FuzzyCondition::DecreasedByTolerance(amount, tolerance) => (diff + amount as i128).abs() <= tolerance as i128,
+            FuzzyCondition::IncreasedByRange(min, max) => diff >= min as i128 && diff <= max as i128,
+            FuzzyCondition::DecreasedByRange(min, max) => -diff >= min as i128 && -diff <= max as i128,
+            FuzzyCondition::IncreasedByPercent(percent) => {
+                if old_val == 0 {
+                    new_val > 0
+                } else {
+                    new_val >= old_val + (old_val as f64 * percent as f64) as i128
+                }
+            },
+            // 无符号值不会小于 0，旧值为 0 时不可能减少
+            FuzzyCondition::DecreasedByPercent(percent) => {
+                old_val != 0 && new_val <= old_val - (old_val as f64 * percent as f64) as i128
+            },
+            FuzzyCondition::Between(min, max) => new_val >= min as i128 && new_val <= max as i128,
+            FuzzyCondition::BetweenFloat(min, max) => {
+                let new_val = new_val as f64;
+                new_val >= min && new_val <= max
+            },
+            FuzzyCondition::ChangedAtLeast(times) => changes >= times,
+            FuzzyCondition::EqualsNow(value) => new_val == value as i128,
+            FuzzyCondition::EqualsNowFloat(value) => new_val as f64 == value,
+        }
+    }
+
+    fn matches_condition_float(&self, new_item: &FuzzySearchResultItem, condition: FuzzyCondition, changes: u32) -> bool {
+        let old_val = self.as_f64();
+        let new_val = new_item.as_f64();
+
+        // NaN 与任何值比较都为 false，需要单独处理：变为 NaN 或从 NaN 恢复算作变化，
+        // 两次都是 NaN 算作未变化；NaN 没有大小关系，增减、差值与范围条件一律不满足
+        if old_val.is_nan() || new_val.is_nan() {
+            let both_nan = old_val.is_nan() && new_val.is_nan();
+            return match condition {
+                FuzzyCondition::Initial => true,
+                FuzzyCondition::Unchanged => both_nan,
+                FuzzyCondition::Changed => !both_nan,
+                FuzzyCondition::ChangedAtLeast(times) => changes >= times,
+                _ => false,
+            };
+        }
+
+        let diff = new_val - old_val;
+        let epsilon = 1e-9;
+
+        match condition {
+            FuzzyCondition::Initial => true,
+            // 同号无穷大相减为 NaN，先按相等判断
+            FuzzyCondition::Unchanged => old_val == new_val || (old_val - new_val).abs() < epsilon,
+            FuzzyCondition::Changed => old_val != new_val && (old_val - new_val).abs() >= epsilon,
+            FuzzyCondition::Increased => new_val > old_val + epsilon,
+            FuzzyCondition::Decreased => new_val < old_val - epsilon,
+            FuzzyCondition::IncreasedBy(amount) => (diff - amount as f64).abs() < epsilon,
+            FuzzyCondition::DecreasedBy(amount) => (diff + amount as f64).abs() < epsilon,
+            FuzzyCondition::IncreasedByTolerance(amount, tolerance) => (diff - amount as f64).abs() <= tolerance as f64 + epsilon,
+            FuzzyCondition::DecreasedByTolerance(amount, tolerance) => (diff + amount as f64).abs() <= tolerance as f64 + epsilon,
+            FuzzyCondition::IncreasedByRange(min, max) => diff >= min as f64 && diff <= max as f64,
+            FuzzyCondition::DecreasedByRange(min, max) => {
+                let neg_diff = -diff;
+                neg_diff >= min as f64 && neg_diff <= max as f64
+            },
+            // 百分比按旧值的绝对值计算；百分比本身是 f32，按其精度放宽阈值
+            FuzzyCondition::IncreasedByPercent(percent) => {
+                if old_val.abs() < epsilon {
+                    new_val > epsilon
+                } else {
+                    let tolerance = old_val.abs() * f32::EPSILON as f64;
+                    let threshold = old_val + old_val.abs() * percent as f64;
+                    new_val >= threshold - tolerance
+                }
+            },
+            FuzzyCondition::DecreasedByPercent(percent) => {
+                if old_val.abs() < epsilon {
+                    new_val < -epsilon
+                } else {
+                    let tolerance = old_val.abs() * f32::EPSILON as f64;
+                    let threshold = old_val - old_val.abs() * percent as f64;
+                    new_val <= threshold + tolerance
+                }
+            },
+            FuzzyCondition::Between(min, max) => new_val >= min as f64 && new_val <= max as f64,
+            FuzzyCondition::BetweenFloat(min, max) => new_val >= min && new_val <= max,
+            FuzzyCondition::ChangedAtLeast(times) => changes >= times,
+            FuzzyCondition::EqualsNow(value) => (new_val - value as f64).abs() < epsilon,
+            // Float 存储的值与输入的小数（如 0.1）通常不能精确相等，按 f32 精度放宽
+            FuzzyCondition::EqualsNowFloat(value) => {
+                let tolerance = match self.value_type {
+                    ValueType::Float => value.abs() * f32::EPSILON as f64,
+                    _ => 0.0,
+                };
+                (new_val - value).abs() <= tolerance.max(epsilon)
+            },
+        }
+    }
+
+    /// 值的分桶键：整数直接使用值本身，浮点数按 FLOAT_BUCKET_EPSILON 取整后使用其位模式
+    #[inline]
+    pub fn value_key(&self) -> i64 {
+        if self.value_type.is_float_type() {
+            let bucketed = (self.as_f64() / FLOAT_BUCKET_EPSILON).round() * FLOAT_BUCKET_EPSILON;
+            // + 0.0 将 -0.0 归一为 0.0
+            (bucketed + 0.0).to_bits() as i64
+        } else {
+            self.as_i64()
+        }
+    }
+
+    /// 更新值（用于细化搜索后保存新值），历史信息由 [`FuzzyItemHistory::advance`] 单独维护
+    pub fn with_new_value(&self, new_bytes: &[u8]) -> Self {
+        self.with_new_value_ordered(new_bytes, ByteOrder::Little)
+    }
+
+    /// 同 [`Self::with_new_value`]，`new_bytes` 按 `order` 字节序解释
+    pub fn with_new_value_ordered(&self, new_bytes: &[u8], order: ByteOrder) -> Self {
+        FuzzySearchResultItem::from_bytes_ordered(self.address, new_bytes, self.value_type, order)
+    }
+}
+
+/// 结果项的历史信息，只在开启历史跟踪时按结果索引分配，结果项本身保持 17 字节
+/// 上一次的值用于界面显示变化（如 "100 → 95"），首次记录时与当前值相同
+/// 最小/最大值用于区分来回波动的值（如当前血量）与只增不减的值（如分数）
+/// 变化次数用于区分频繁变化的值（如坐标）与基本不变的值
+/// 标签用于在同一结果列表中区分多组扫描结果，0 表示未分组
+/// 值的编码与 [`FuzzySearchResultItem::value`] 相同，解码时需要结果项的值类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyItemHistory {
+    pub prev_value: [u8; 8],
+    pub min_value: [u8; 8],
+    pub max_value: [u8; 8],
+    pub change_count: u32,
+    pub tag: u16,
+}
+
+impl FuzzyItemHistory {
+    /// 首次记录：上一次的值与最小/最大值都是当前值
+    #[inline]
+    pub fn new(item: &FuzzySearchResultItem) -> Self {
+        let value = item.value;
+        FuzzyItemHistory { prev_value: value, min_value: value, max_value: value, change_count: 0, tag: 0 }
+    }
+
+    /// 设置分组标签
+    #[inline]
+    pub fn with_tag(mut self, tag: u16) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// 计入新值后的变化次数：编码后的值与旧值不同即算一次变化，达到 u32::MAX 后饱和
+    #[inline]
+    fn count_change(change_count: u32, old: &FuzzySearchResultItem, new: &FuzzySearchResultItem) -> u32 {
+        if new.value != old.value { change_count.saturating_add(1) } else { change_count }
+    }
+
+    /// 结果项从 `old` 更新为 `new` 后的历史：旧值移入上一次的值，并扩展最小/最大值范围
+    pub fn advance(&self, old: &FuzzySearchResultItem, new: &FuzzySearchResultItem) -> Self {
+        let mut history = *self;
+        history.change_count = Self::count_change(self.change_count, old, new);
+        history.prev_value = old.value;
+        history.track_range(new);
+        history
+    }
+
+    /// 值类型重新解释后旧的范围没有意义，以当前值重新开始；上一次的值、变化次数与标签保留
+    pub fn reset_range(&mut self, item: &FuzzySearchResultItem) {
+        self.min_value = item.value;
+        self.max_value = item.value;
+    }
+
+    /// 用当前值扩展最小/最大值范围；变长类型没有大小关系，范围始终为当前值
+    fn track_range(&mut self, item: &FuzzySearchResultItem) {
+        let value_type = item.value_type;
+        if value_type.is_variable_length() {
+            self.reset_range(item);
+        } else if value_type.is_float_type() {
+            let current = item.as_f64();
+            if current < self.min_as_f64(value_type) {
+                self.min_value = item.value;
+            }
+            if current > self.max_as_f64(value_type) {
+                self.max_value = item.value;
+            }
+        } else {
+            let current = item.as_i64();
+            if current < self.min_as_i64(value_type) {
+                self.min_value = item.value;
+            }
+            if current > self.max_as_i64(value_type) {
+                self.max_value = item.value;
+            }
+        }
+    }
+
+    /// 上一次的值（整数形式）
+    #[inline]
+    pub fn prev_as_i64(&self, value_type: ValueType) -> i64 {
+        FuzzySearchResultItem::decode_i64(self.prev_value, value_type)
+    }
+
+    /// 上一次的值（无符号整数形式）
+    #[inline]
+    pub fn prev_as_u64(&self, value_type: ValueType) -> u64 {
+        FuzzySearchResultItem::decode_u64(self.prev_value, value_type)
+    }
+
+    /// 上一次的值（浮点形式）
+    #[inline]
+    pub fn prev_as_f64(&self, value_type: ValueType) -> f64 {
+        FuzzySearchResultItem::decode_f64(self.prev_value, value_type)
+    }
+
+    /// 会话内的最小值（整数形式）
+    #[inline]
+    pub fn min_as_i64(&self, value_type: ValueType) -> i64 {
+        FuzzySearchResultItem::decode_i64(self.min_value, value_type)
+    }
+
+    /// 会话内的最大值（整数形式）
+    #[inline]
+    pub fn max_as_i64(&self, value_type: ValueType) -> i64 {
+        FuzzySearchResultItem::decode_i64(self.max_value, value_type)
+    }
+
+    /// 会话内的最小值（浮点形式）
+    #[inline]
+    pub fn min_as_f64(&self, value_type: ValueType) -> f64 {
+        FuzzySearchResultItem::decode_f64(self.min_value, value_type)
+    }
+
+    /// 会话内的最大值（浮点形式）
+    #[inline]
+    pub fn max_as_f64(&self, value_type: ValueType) -> f64 {
+        FuzzySearchResultItem::decode_f64(self.max_value, value_type)
+    }
+
+    /// 按值类型格式化上一次的值
+    pub fn format_prev_value(&self, value_type: ValueType) -> String {
+        FuzzySearchResultItem::format_encoded(self.prev_value, value_type)
+    }
+
+    /// 上一次的变长内容
+    pub fn variable_bytes_prev(&self, value_type: ValueType) -> Option<Vec<u8>> {
+        FuzzySearchResultItem::decode_variable(self.prev_value, value_type)
+    }
+}
+
+/// 按存储顺序排列的全部结果及其历史信息，用于快照与异步细化；未开启历史跟踪时 `history` 为 None
+#[derive(Debug, Clone, Default)]
+pub struct FuzzyResultSet {
+    pub items: Vec<FuzzySearchResultItem>,
+    pub history: Option<Vec<FuzzyItemHistory>>,
+}
+
+impl FuzzyResultSet {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// 第 `index` 项的历史信息
+    #[inline]
+    pub fn history(&self, index: usize) -> Option<&FuzzyItemHistory> {
+        self.history.as_ref().and_then(|history| history.get(index))
+    }
+}
+
+/// 可移植导出格式魔数
+const PORTABLE_MAGIC: [u8; 4] = *b"MMFR";
+/// 可移植导出格式版本
+const PORTABLE_VERSION: u16 = 1;
+/// 紧凑编码的可移植导出格式版本，见 [`super::packed`]
+const PORTABLE_VERSION_PACKED: u16 = 2;
+
+/// 会话文件魔数
+const SESSION_MAGIC: [u8; 4] = *b"MMFS";
+/// 会话文件头的字节数：魔数 + u16 版本 + 2 字节类型信息 + u64 结果数量
+const SESSION_HEADER_LEN: usize = 16;
+/// 逐项记录的会话文件版本，结果包含多种值类型时使用
+const SESSION_VERSION: u16 = 1;
+/// 紧凑编码的会话文件版本，所有结果为同一值类型时使用，见 [`FuzzySearchResultManager::save_session`]
+const SESSION_VERSION_PACKED: u16 = 2;
+/// 紧凑编码的文件头标记：结果区之前带有历史信息
+const SESSION_FLAG_HISTORY: u8 = 1;
+/// 每项历史信息的字节数：上一次的值 + 最小值 + 最大值 + u32 变化次数 + u16 分组标签
+const SESSION_HISTORY_LEN: usize = 3 * size_of::<[u8; 8]>() + size_of::<u32>() + size_of::<u16>();
+/// 逐项记录的字节数：u64 地址 + 当前值 + 历史信息 + u8 值类型 id
+const SESSION_RECORD_LEN: usize = size_of::<u64>() + size_of::<[u8; 8]>() + SESSION_HISTORY_LEN + size_of::<u8>();
+
+impl FuzzyItemHistory {
+    /// 按会话文件的格式编码
+    fn to_session_bytes(self) -> [u8; SESSION_HISTORY_LEN] {
+        let mut bytes = [0u8; SESSION_HISTORY_LEN];
+        bytes[0..8].copy_from_slice(&self.prev_value);
+        bytes[8..16].copy_from_slice(&self.min_value);
+        bytes[16..24].copy_from_slice(&self.max_value);
+        bytes[24..28].copy_from_slice(&self.change_count.to_le_bytes());
+        bytes[28..30].copy_from_slice(&self.tag.to_le_bytes());
+        bytes
+    }
+
+    /// 从会话文件的格式解码
+    fn from_session_bytes(bytes: &[u8; SESSION_HISTORY_LEN]) -> Result<Self> {
+        Ok(FuzzyItemHistory {
+            prev_value: bytes[0..8].try_into()?,
+            min_value: bytes[8..16].try_into()?,
+            max_value: bytes[16..24].try_into()?,
+            change_count: u32::from_le_bytes(bytes[24..28].try_into()?),
+            tag: u16::from_le_bytes(bytes[28..30].try_into()?),
+        })
+    }
+}
+
+/// 逐项读取会话文件中的结果，打开时校验文件头
+struct SessionReader {
+    /// 逐项记录；紧凑编码时为历史信息区
+    reader: BufReader<File>,
+    remaining: usize,
+    /// 紧凑编码的结果区及所有结果共同的值类型
+    packed: Option<(PackedReader<BufReader<File>>, ValueType)>,
+    /// 紧凑编码时结果区之前是否带有历史信息
+    packed_history: bool,
+}
+
+impl SessionReader {
+    fn open(path: &Path) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut header = [0u8; SESSION_HEADER_LEN];
+        reader.read_exact(&mut header)?;
+        if header[0..4] != SESSION_MAGIC {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid fuzzy session file: {:?}", path)));
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        let remaining = u64::from_le_bytes(header[8..16].try_into()?) as usize;
+        match version {
+            SESSION_VERSION => Ok(Self { reader, remaining, packed: None, packed_history: false }),
+            SESSION_VERSION_PACKED => {
+                let value_type = ValueType::from_id(header[6] as i32)
+                    .ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type id in session: {}", header[6])))?;
+                let packed_history = header[7] & SESSION_FLAG_HISTORY != 0;
+                // 历史信息区长度固定，结果区用另一个句柄从其后开始读取，两个区域同步逐项推进
+                let history_len = if packed_history { SESSION_HISTORY_LEN } else { 0 };
+                let items_offset = remaining
+                    .checked_mul(history_len)
+                    .and_then(|len| len.checked_add(SESSION_HEADER_LEN))
+                    .ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid fuzzy session length: {} results", remaining)))?;
+                let mut items = BufReader::new(File::open(path)?);
+                items.seek(SeekFrom::Start(items_offset as u64))?;
+                let packed = Some((PackedReader::new(items, value_type, remaining), value_type));
+                Ok(Self { reader, remaining, packed, packed_history })
+            },
+            _ => Err(JniErrorCode::InvalidArgument.error(format!("Unsupported fuzzy session version: {}", version))),
+        }
+    }
+
+    fn read_item(&mut self) -> Result<(FuzzySearchResultItem, FuzzyItemHistory)> {
+        if let Some((items, value_type)) = self.packed.as_mut() {
+            let (address, value) = items.next_item()?;
+            let item = FuzzySearchResultItem::new(address, value, *value_type);
+            if !self.packed_history {
+                return Ok((item, FuzzyItemHistory::new(&item)));
+            }
+            let mut record = [0u8; SESSION_HISTORY_LEN];
+            self.reader.read_exact(&mut record)?;
+            return Ok((item, FuzzyItemHistory::from_session_bytes(&record)?));
         }
+
+        let mut record = [0u8; SESSION_RECORD_LEN];
+        self.reader.read_exact(&mut record)?;
+
+        let address = u64::from_le_bytes(record[0..8].try_into()?);
+        let value: [u8; 8] = record[8..16].try_into()?;
+        let history = FuzzyItemHistory::from_session_bytes(record[16..16 + SESSION_HISTORY_LEN].try_into()?)?;
+        let type_id = record[SESSION_RECORD_LEN - 1];
+        let value_type = ValueType::from_id(type_id as i32)
+            .ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type id in session: {}", type_id)))?;
+        Ok((FuzzySearchResultItem::new(address, value, value_type), history))
+    }
+}
+
+impl Iterator for SessionReader {
+    type Item = Result<(FuzzySearchResultItem, FuzzyItemHistory)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.read_item())
+    }
+}
+
+/// 两个会话之间的差异，三组地址均按升序排列
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SessionDiff {
+    /// 只出现在新会话中的地址
+    pub appeared: Vec<u64>,
+    /// 只出现在旧会话中的地址
+    pub disappeared: Vec<u64>,
+    /// 两个会话中都有、但值类型或当前值不同的地址
+    pub changed: Vec<u64>,
+}
+
+/// 磁盘溢出文件头魔数（Mamu Fuzzy Disk）
+/// 文件开头为文件头，结果区从文件头记录的数据偏移开始映射，因此结果偏移不受文件头影响
+const DISK_MAGIC: [u8; 4] = *b"MMFD";
+/// 磁盘溢出文件头版本（v2 结果项增加了变化次数，v3 增加了分组标签，v4 历史信息移出结果项，v5 记录落盘标记与数据偏移）
+const DISK_VERSION: u16 = 5;
+/// 文件头实际使用的字节数：魔数 + u16 版本 + u16 单项大小 + u64 结果数量 + u32 CRC32 + u32 标记 + u64 数据偏移
+const DISK_HEADER_LEN: usize = 32;
+/// 文件头标记：结果已通过 [`FuzzySearchResultManager::flush_disk_file`] 完整落盘，可以重新打开
+const DISK_FLAG_FLUSHED: u32 = 1;
+/// 结果区的最小数据偏移。64 KiB 是 4K/16K/64K 页大小的公倍数，
+/// 文件在页大小不同的设备之间迁移后，记录的偏移仍满足 mmap 的对齐要求
+const DISK_DATA_ALIGN: usize = 64 * 1024;
+
+/// 磁盘溢出文件的初始大小与扩展策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpillConfig {
+    /// 首次创建磁盘文件时分配的大小
+    pub initial_size: usize,
+    /// 每次扩展的大小；几何增长时为单次扩展的上限
+    pub growth_increment: usize,
+    /// 为 true 时每次扩展为当前大小的一倍（不超过 `growth_increment`）
+    pub geometric: bool,
+}
+
+impl Default for DiskSpillConfig {
+    fn default() -> Self {
+        Self {
+            initial_size: 128 * 1024 * 1024,
+            growth_increment: 128 * 1024 * 1024,
+            geometric: false,
+        }
+    }
+}
+
+impl DiskSpillConfig {
+    /// 从较小的初始大小开始按倍数增长，单次扩展不超过 `max_increment`
+    pub fn geometric(initial_size: usize, max_increment: usize) -> Self {
+        Self {
+            initial_size,
+            growth_increment: max_increment,
+            geometric: true,
+        }
+    }
+
+    /// 按页对齐后的初始大小，至少一页
+    fn aligned_initial_size(&self) -> usize {
+        let page_size = *PAGE_SIZE;
+        self.initial_size.div_ceil(page_size).max(1) * page_size
+    }
+
+    /// 当前映射大小为 `current_size` 时扩展后的大小（按页对齐）
+    fn next_size(&self, current_size: usize) -> usize {
+        let page_size = *PAGE_SIZE;
+        let step = if self.geometric {
+            current_size.min(self.growth_increment)
+        } else {
+            self.growth_increment
+        };
+        (current_size + step.max(page_size)).div_ceil(page_size) * page_size
+    }
+}
+
+/// 模糊搜索结果管理器 - 内存 + 磁盘混合存储
+pub struct FuzzySearchResultManager {
+    memory_buffer: Vec<FuzzySearchResultItem>,
+    memory_buffer_capacity: usize,
+    cache_dir: PathBuf,
+    disk_file_path: Option<PathBuf>,
+    disk_file: Option<File>,
+    mmap: Option<MmapMut>,
+    disk_count: usize,
+    total_count: usize,
+    disk_config: DiskSpillConfig,
+    /// 仅计数模式：add_result 只增加 total_count，不保存结果
+    discarding: bool,
+    /// 细化比较选项，其中的字节序也用于从原始内存构造结果项
+    compare_options: FuzzyCompareOptions,
+    /// 与结果索引一一对应的历史信息，仅开启历史跟踪时分配
+    history: Option<Vec<FuzzyItemHistory>>,
+    /// 当前磁盘文件中结果区的偏移，新建文件时取 [`Self::disk_data_offset`]，重新打开时取文件头记录的值
+    disk_data_offset: usize,
+    /// 文件头带有落盘标记，之后第一次修改磁盘结果前需要清除
+    disk_flushed: bool,
+}
+
+impl FuzzySearchResultManager {
+    const ITEM_SIZE: usize = size_of::<FuzzySearchResultItem>();
+    const VALUE_TYPE_OFFSET: usize = std::mem::offset_of!(FuzzySearchResultItem, value_type);
+
+    pub fn new(memory_buffer_size: usize, cache_dir: PathBuf) -> Self {
+        let capacity = if memory_buffer_size == 0 { 0 } else { memory_buffer_size / Self::ITEM_SIZE };
+
+        if memory_buffer_size == 0 {
+            info!(
+                "Initializing FuzzySearchResultManager: memory_buffer_capacity=0 (direct disk write mode), cache_dir={:?}",
+                cache_dir
+            );
+        } else {
+            info!(
+                "Initializing FuzzySearchResultManager: memory_buffer_capacity={} items ({} MB), cache_dir={:?}",
+                capacity,
+                memory_buffer_size / 1024 / 1024,
+                cache_dir
+            );
+        }
+
+        FuzzySearchResultManager {
+            memory_buffer: Vec::with_capacity(capacity),
+            memory_buffer_capacity: capacity,
+            cache_dir,
+            disk_file_path: None,
+            disk_file: None,
+            mmap: None,
+            disk_count: 0,
+            total_count: 0,
+            disk_config: DiskSpillConfig::default(),
+            discarding: false,
+            compare_options: FuzzyCompareOptions::default(),
+            history: None,
+            disk_data_offset: Self::disk_data_offset(),
+            disk_flushed: false,
+        }
+    }
+
+    /// 设置目标进程的字节序，add_regions / refine 读取的原始字节按该字节序解释
+    pub fn with_byte_order(mut self, order: ByteOrder) -> Self {
+        self.compare_options.byte_order = order;
+        self
+    }
+
+    pub fn byte_order(&self) -> ByteOrder {
+        self.compare_options.byte_order
+    }
+
+    /// 设置 refine / refine_in_place 使用的比较选项（无符号、回绕差值、字节序）
+    pub fn set_compare_options(&mut self, options: FuzzyCompareOptions) {
+        self.compare_options = options;
+    }
+
+    pub fn compare_options(&self) -> FuzzyCompareOptions {
+        self.compare_options
+    }
+
+    /// 开启或关闭历史跟踪（上一次的值、最小/最大值、变化次数与分组标签）
+    /// 开启时为已有结果按当前值初始化历史信息，关闭时释放历史表
+    pub fn set_history_tracking(&mut self, enabled: bool) {
+        match (enabled, self.history.is_some()) {
+            (true, false) => self.history = Some(self.iter().map(|item| FuzzyItemHistory::new(&item)).collect()),
+            (false, true) => self.history = None,
+            _ => {},
+        }
+    }
+
+    pub fn is_tracking_history(&self) -> bool {
+        self.history.is_some()
+    }
+
+    /// 第 `index` 项的历史信息，未开启历史跟踪时返回 None
+    pub fn history(&self, index: usize) -> Option<FuzzyItemHistory> {
+        self.history.as_ref().and_then(|history| history.get(index).copied())
+    }
+
+    /// 第 `index` 项的分组标签，未开启历史跟踪时所有结果都未分组
+    fn tag_of(&self, index: usize) -> u16 {
+        self.history(index).map_or(0, |history| history.tag)
+    }
+
+    /// 设置仅计数模式（DiscardingMode），用于在保存结果前先快速得到匹配数量
+    /// 该模式下 add_result 只增加 total_count，既不占用内存缓冲区也不创建磁盘文件；
+    /// 读取结果时返回空，需要实际结果时关闭该模式重新扫描
+    pub fn with_discarding(mut self, discarding: bool) -> Self {
+        self.discarding = discarding;
+        self
+    }
+
+    /// 是否为仅计数模式
+    pub fn is_discarding(&self) -> bool {
+        self.discarding
+    }
+
+    /// 设置磁盘溢出文件的初始大小与扩展策略，下次创建或扩展磁盘文件时生效
+    pub fn with_disk_config(mut self, config: DiskSpillConfig) -> Self {
+        self.disk_config = config;
+        self
+    }
+
+    /// 当前磁盘文件的映射大小（包含预分配空间）
+    pub fn mapped_disk_size(&self) -> usize {
+        self.mmap.as_ref().map_or(0, |mmap| mmap.len())
+    }
+
+    pub fn clear(&mut self) -> Result<()> {
+        self.mark_disk_dirty()?;
+        self.memory_buffer.clear();
+        self.total_count = 0;
+        self.disk_count = 0;
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
+        debug!("Fuzzy search results cleared");
+        Ok(())
+    }
+
+    pub fn clear_disk(&mut self) -> Result<()> {
+        drop(self.mmap.take());
+        drop(self.disk_file.take());
+
+        if let Some(ref path) = self.disk_file_path {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+                debug!("Removed fuzzy disk file: {:?}", path);
+            }
+        }
+
+        self.disk_file_path = None;
+        self.disk_count = 0;
+        self.disk_flushed = false;
+        info!("Fuzzy disk resources cleared");
+        Ok(())
+    }
+
+    pub fn destroy(&mut self) -> Result<()> {
+        self.memory_buffer.clear();
+        self.total_count = 0;
+        self.disk_count = 0;
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+            history.shrink_to_fit();
+        }
+
+        if let Some(ref path) = self.disk_file_path {
+            drop(self.mmap.take());
+            drop(self.disk_file.take());
+            if path.exists() {
+                std::fs::remove_file(path)?;
+                debug!("Removed fuzzy disk file: {:?}", path);
+            }
+        }
+
+        self.disk_file_path = None;
+        self.disk_flushed = false;
+        info!("FuzzySearchResultManager destroyed");
+        Ok(())
+    }
+
+    pub fn add_result(&mut self, item: FuzzySearchResultItem) -> Result<()> {
+        self.add_result_with_history(item, FuzzyItemHistory::new(&item))
+    }
+
+    /// 追加结果并记录其历史信息，未开启历史跟踪时忽略 `history`
+    pub fn add_result_with_history(&mut self, item: FuzzySearchResultItem, history: FuzzyItemHistory) -> Result<()> {
+        if self.discarding {
+            // 仅计数
+        } else if self.memory_room() > 0 {
+            self.memory_buffer.push(item);
+        } else {
+            self.write_to_disk(&item)?;
+        }
+
+        if !self.discarding
+            && let Some(table) = self.history.as_mut()
+        {
+            table.push(history);
+        }
+        self.total_count += 1;
+        Ok(())
+    }
+
+    /// 内存缓冲区还能追加的结果数量
+    /// 磁盘上已有结果（溢出或重新打开之后）时为 0：新结果必须追加在磁盘结果之后，才能保持索引顺序
+    fn memory_room(&self) -> usize {
+        if self.disk_count > 0 {
+            return 0;
+        }
+        self.memory_buffer_capacity.saturating_sub(self.memory_buffer.len())
+    }
+
+    /// 预先扩展磁盘文件，使之后 `additional` 个新结果写入时无需逐项扩展与重新映射
+    /// 内存缓冲区剩余空间能容纳的部分不占用磁盘，不计入扩展
+    pub fn reserve(&mut self, additional: usize) -> Result<()> {
+        let disk_additional = additional.saturating_sub(self.memory_room());
+        if self.discarding || disk_additional == 0 {
+            return Ok(());
+        }
+
+        if self.disk_file.is_none() {
+            self.init_disk_file()?;
+        }
+
+        let needed = (self.disk_count + disk_additional) * Self::ITEM_SIZE;
+        let mmap_size = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+        if needed > mmap_size {
+            let page_size = *PAGE_SIZE;
+            self.grow_disk_file(needed.div_ceil(page_size) * page_size)?;
+        }
+        Ok(())
+    }
+
+    /// 批量追加结果，效果与逐项 add_result 相同：先填满内存缓冲区，
+    /// 其余结果先按总数一次性扩展磁盘文件，再整段复制到映射中
+    pub fn add_results_bulk(&mut self, items: &[FuzzySearchResultItem]) -> Result<()> {
+        if self.discarding {
+            self.total_count += items.len();
+            return Ok(());
+        }
+
+        // 先预留磁盘空间，失败时结果与历史信息都保持不变
+        let (to_memory, to_disk) = items.split_at(self.memory_room().min(items.len()));
+        if !to_disk.is_empty() {
+            self.reserve(items.len())?;
+            self.mark_disk_dirty()?;
+        }
+
+        if let Some(history) = self.history.as_mut() {
+            history.extend(items.iter().map(FuzzyItemHistory::new));
+        }
+        self.memory_buffer.extend_from_slice(to_memory);
+        self.total_count += to_memory.len();
+
+        if !to_disk.is_empty() {
+            let offset = self.disk_count * Self::ITEM_SIZE;
+            let mmap = self.mmap.as_mut().ok_or_else(|| anyhow!("Disk file is not mapped"))?;
+            unsafe {
+                let ptr = mmap.as_mut_ptr().add(offset) as *mut FuzzySearchResultItem;
+                std::ptr::copy_nonoverlapping(to_disk.as_ptr(), ptr, to_disk.len());
+            }
+            self.disk_count += to_disk.len();
+            self.total_count += to_disk.len();
+        }
+        Ok(())
+    }
+
+    fn write_to_disk(&mut self, item: &FuzzySearchResultItem) -> Result<()> {
+        if self.disk_file.is_none() {
+            self.init_disk_file()?;
+        }
+        self.mark_disk_dirty()?;
+
+        let offset = self.disk_count * Self::ITEM_SIZE;
+        let mmap_size = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+        if offset + Self::ITEM_SIZE > mmap_size {
+            self.grow_disk_file(self.disk_config.next_size(mmap_size))?;
+        }
+
+        // 磁盘文件存在但没有映射时不能静默丢弃结果，否则 total_count 与实际数据不一致
+        let mmap = self.mmap.as_mut().ok_or_else(|| anyhow!("Disk file is not mapped"))?;
+        unsafe {
+            let ptr = mmap.as_mut_ptr().add(offset) as *mut FuzzySearchResultItem;
+            ptr.write(*item);
+        }
+
+        self.disk_count += 1;
+        Ok(())
+    }
+
+    /// 扩展磁盘文件并重新映射
+    /// 新映射成功之前保留旧映射，失败时回滚文件长度，已写入的结果仍可正常读取
+    fn grow_disk_file(&mut self, new_size: usize) -> Result<()> {
+        let file = self.disk_file.as_ref().ok_or_else(|| anyhow!("Disk file is not initialized"))?;
+        let old_size = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+
+        let header_size = self.disk_data_offset;
+        file.set_len((header_size + new_size) as u64)
+            .with_context(|| format!("Failed to grow disk file to {} MB", new_size / 1024 / 1024))?;
+
+        match Self::map_items(file, header_size, new_size) {
+            Ok(mmap) => {
+                self.mmap = Some(mmap);
+                debug!("Disk file grown: {} MB -> {} MB", old_size / 1024 / 1024, new_size / 1024 / 1024);
+                Ok(())
+            },
+            Err(e) => {
+                if old_size > 0 {
+                    let _ = file.set_len((header_size + old_size) as u64);
+                }
+                Err(anyhow!(e).context(format!("Failed to remap disk file at {} MB", new_size / 1024 / 1024)))
+            },
+        }
+    }
+
+    /// 收缩磁盘文件到 `disk_count * ITEM_SIZE`（按页向上取整）并重新映射
+    /// 用于大量删除后释放存储空间；没有磁盘文件时直接返回
+    pub fn compact(&mut self) -> Result<()> {
+        if self.disk_file.is_none() {
+            return Ok(());
+        }
+
+        let page_size = *PAGE_SIZE;
+        let used = self.disk_count * Self::ITEM_SIZE;
+        // 保留至少一页，空文件无法映射
+        let new_size = used.div_ceil(page_size).max(1) * page_size;
+        let old_size = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+        if new_size >= old_size {
+            return Ok(());
+        }
+
+        self.mark_disk_dirty()?;
+        let Some(ref file) = self.disk_file else {
+            return Ok(());
+        };
+        if let Some(ref mmap) = self.mmap {
+            mmap.flush()?;
+        }
+        drop(self.mmap.take());
+        file.set_len((self.disk_data_offset + new_size) as u64)?;
+        self.mmap = Some(Self::map_items(file, self.disk_data_offset, new_size)?);
+
+        info!("Fuzzy disk file compacted: {} KB -> {} KB", old_size / 1024, new_size / 1024);
+        Ok(())
+    }
+
+    /// 释放多余空间：内存缓冲区归还给分配器，磁盘文件按 [`Self::compact`] 收缩
+    pub fn shrink_to_fit(&mut self) -> Result<()> {
+        self.memory_buffer.shrink_to_fit();
+        self.compact()
+    }
+
+    fn init_disk_file(&mut self) -> Result<()> {
+        let file_path = self.cache_dir.join(format!(
+            "{}_{}_{}.bin",
+            DISK_FILE_PREFIX,
+            std::process::id(),
+            DISK_FILE_COUNTER.fetch_add(1, AtomicOrdering::Relaxed)
+        ));
+
+        debug!("Creating fuzzy disk file: {:?}", file_path);
+
+        let initial_size = self.disk_config.aligned_initial_size();
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&file_path)?;
+
+        let data_offset = Self::disk_data_offset();
+        file.set_len((data_offset + initial_size) as u64)?;
+        // 新文件没有落盘标记，重新打开时会被拒绝
+        file.write_all_at(&Self::encode_disk_header(0, 0, 0, data_offset), 0)?;
+
+        let mmap = Self::map_items(&file, data_offset, initial_size)?;
+
+        self.disk_file_path = Some(file_path);
+        self.disk_file = Some(file);
+        self.mmap = Some(mmap);
+        self.disk_data_offset = data_offset;
+        self.disk_flushed = false;
+
+        info!("Fuzzy disk file initialized with size {} KB", initial_size / 1024);
+        Ok(())
+    }
+
+    /// 新建磁盘文件时结果区的偏移：不小于 [`DISK_DATA_ALIGN`] 且按当前页大小对齐
+    fn disk_data_offset() -> usize {
+        DISK_DATA_ALIGN.div_ceil(*PAGE_SIZE) * *PAGE_SIZE
+    }
+
+    /// 映射文件头之后的结果区
+    fn map_items(file: &File, data_offset: usize, len: usize) -> std::io::Result<MmapMut> {
+        unsafe { MmapOptions::new().offset(data_offset as u64).len(len).map_mut(file) }
+    }
+
+    fn encode_disk_header(count: usize, crc: u32, flags: u32, data_offset: usize) -> [u8; DISK_HEADER_LEN] {
+        let mut header = [0u8; DISK_HEADER_LEN];
+        header[0..4].copy_from_slice(&DISK_MAGIC);
+        header[4..6].copy_from_slice(&DISK_VERSION.to_le_bytes());
+        header[6..8].copy_from_slice(&(Self::ITEM_SIZE as u16).to_le_bytes());
+        header[8..16].copy_from_slice(&(count as u64).to_le_bytes());
+        header[16..20].copy_from_slice(&crc.to_le_bytes());
+        header[20..24].copy_from_slice(&flags.to_le_bytes());
+        header[24..32].copy_from_slice(&(data_offset as u64).to_le_bytes());
+        header
+    }
+
+    /// 文件落盘之后第一次修改磁盘结果前清除文件头的落盘标记，
+    /// 修改中途进程退出时文件不会被当作有效结果重新打开
+    fn mark_disk_dirty(&mut self) -> Result<()> {
+        if !self.disk_flushed {
+            return Ok(());
+        }
+        if let Some(ref file) = self.disk_file {
+            file.write_all_at(&Self::encode_disk_header(0, 0, 0, self.disk_data_offset), 0)?;
+        }
+        self.disk_flushed = false;
+        Ok(())
+    }
+
+    /// 把内存缓冲区中的结果移到磁盘文件开头，使磁盘文件包含全部结果，索引顺序不变
+    fn spill_memory_to_disk(&mut self) -> Result<()> {
+        let memory_len = self.memory_buffer.len();
+        if memory_len == 0 {
+            return Ok(());
+        }
+
+        if self.disk_file.is_none() {
+            self.init_disk_file()?;
+        }
+        self.mark_disk_dirty()?;
+
+        let needed = (self.disk_count + memory_len) * Self::ITEM_SIZE;
+        let mmap_size = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+        if needed > mmap_size {
+            let page_size = *PAGE_SIZE;
+            self.grow_disk_file(needed.div_ceil(page_size) * page_size)?;
+        }
+
+        let mmap = self.mmap.as_mut().ok_or_else(|| anyhow!("Disk file is not mapped"))?;
+        unsafe {
+            let base = mmap.as_mut_ptr();
+            std::ptr::copy(base, base.add(memory_len * Self::ITEM_SIZE), self.disk_count * Self::ITEM_SIZE);
+            std::ptr::copy_nonoverlapping(self.memory_buffer.as_ptr() as *const u8, base, memory_len * Self::ITEM_SIZE);
+        }
+        self.disk_count += memory_len;
+        self.memory_buffer.clear();
+        Ok(())
+    }
+
+    /// 将所有结果落盘：内存缓冲区中的结果先移入磁盘文件，再在文件头写入结果数量、结果区的 CRC32 和落盘标记
+    /// 之后可通过 [`Self::reopen_disk_file`] 在进程重启后恢复；没有任何结果且没有磁盘文件时直接返回
+    /// 历史信息不写入磁盘文件，重新打开后从当前值重新开始
+    pub fn flush_disk_file(&mut self) -> Result<()> {
+        self.spill_memory_to_disk()?;
+        let Some(ref file) = self.disk_file else {
+            return Ok(());
+        };
+        let Some(ref mmap) = self.mmap else {
+            return Err(anyhow!("Disk file is not mapped"));
+        };
+
+        let used = self.disk_count * Self::ITEM_SIZE;
+        mmap.flush_range(0, used)?;
+        let crc = crc32fast::hash(&mmap[..used]);
+        file.write_all_at(&Self::encode_disk_header(self.disk_count, crc, DISK_FLAG_FLUSHED, self.disk_data_offset), 0)?;
+        file.sync_data()?;
+        self.disk_flushed = true;
+
+        debug!("Fuzzy disk file flushed: {} items, crc32={:08x}", self.disk_count, crc);
+        Ok(())
+    }
+
+    /// 当前磁盘文件的路径，尚未溢出到磁盘时为 None；重启后需用该路径调用 [`Self::reopen_disk_file`]
+    pub fn disk_file_path(&self) -> Option<&Path> {
+        self.disk_file_path.as_deref()
+    }
+
+    /// 删除缓存目录中已退出进程遗留的磁盘文件，返回删除的文件数量
+    ///
+    /// 处理 `{前缀}_{pid}_{序号}.bin` 形式的文件，pid 对应的进程仍存在时保留；
+    /// 带有落盘标记的文件留给 [`Self::reopen_disk_file`] 恢复，不会删除。
+    /// 旧版本使用的固定文件名 `{前缀}.bin` 无法恢复，直接删除。单个文件删除失败时记录日志并继续
+    pub fn remove_stale_disk_files(cache_dir: &Path) -> Result<usize> {
+        let legacy_name = format!("{}.bin", DISK_FILE_PREFIX);
+        let mut removed = 0;
+        for entry in std::fs::read_dir(cache_dir)? {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    warn!("Failed to read entry in {:?}: {:?}", cache_dir, e);
+                    continue;
+                },
+            };
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if name != legacy_name {
+                let Some(pid) = name
+                    .strip_prefix(DISK_FILE_PREFIX)
+                    .and_then(|rest| rest.strip_prefix('_'))
+                    .and_then(|rest| rest.strip_suffix(".bin"))
+                    .and_then(|rest| rest.split_once('_'))
+                    .and_then(|(pid, _)| pid.parse::<u32>().ok())
+                else {
+                    continue;
+                };
+                if pid == std::process::id() || Path::new(&format!("/proc/{}", pid)).exists() || Self::is_flushed_disk_file(&path) {
+                    continue;
+                }
+            }
+            match std::fs::remove_file(&path) {
+                Ok(()) => {
+                    debug!("Removed stale fuzzy disk file: {:?}", path);
+                    removed += 1;
+                },
+                Err(e) => warn!("Failed to remove stale fuzzy disk file {:?}: {:?}", path, e),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// 文件头是当前版本且带有落盘标记，即可以通过 [`Self::reopen_disk_file`] 恢复
+    fn is_flushed_disk_file(path: &Path) -> bool {
+        let mut header = [0u8; DISK_HEADER_LEN];
+        let Ok(()) = File::open(path).and_then(|file| file.read_exact_at(&mut header, 0)) else {
+            return false;
+        };
+        let flags = u32::from_le_bytes([header[20], header[21], header[22], header[23]]);
+        header[0..4] == DISK_MAGIC && header[4..6] == DISK_VERSION.to_le_bytes() && flags & DISK_FLAG_FLUSHED != 0
+    }
+
+    /// 重新打开已有的磁盘文件，替换当前所有结果，返回恢复的结果数量
+    ///
+    /// 之后该文件归当前管理器所有，clear_disk / destroy 时删除。映射之前先校验文件头的魔数、版本、单项大小、文件长度、结果区的 CRC32 以及每项的值类型字节，
+    /// 文件被截断或损坏时返回错误，不会把未初始化或过期的数据当作结果读取
+    pub fn reopen_disk_file(&mut self, file_path: &Path) -> Result<usize> {
+        let file_path = file_path.to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&file_path)
+            .with_context(|| format!("Failed to open fuzzy disk file {:?}", file_path))?;
+
+        let invalid = |reason: &str| JniErrorCode::InvalidArgument.error(format!("Invalid fuzzy disk file {:?}: {}", file_path, reason));
+
+        let file_len = file.metadata()?.len() as usize;
+        if file_len < DISK_HEADER_LEN {
+            return Err(invalid("truncated header"));
+        }
+
+        let mut header = [0u8; DISK_HEADER_LEN];
+        file.read_exact_at(&mut header, 0)?;
+        if header[0..4] != DISK_MAGIC {
+            return Err(invalid("bad magic"));
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != DISK_VERSION {
+            return Err(invalid(&format!("unsupported version {}", version)));
+        }
+        let item_size = u16::from_le_bytes([header[6], header[7]]) as usize;
+        if item_size != Self::ITEM_SIZE {
+            return Err(invalid(&format!("item size {} != {}", item_size, Self::ITEM_SIZE)));
+        }
+        let count = u64::from_le_bytes(header[8..16].try_into()?) as usize;
+        let expected_crc = u32::from_le_bytes(header[16..20].try_into()?);
+        let flags = u32::from_le_bytes(header[20..24].try_into()?);
+        if flags & DISK_FLAG_FLUSHED == 0 {
+            return Err(invalid("results were not flushed"));
+        }
+        // 数据偏移按写入设备的页大小对齐，当前设备的页大小更大时无法映射
+        let header_size = u64::from_le_bytes(header[24..32].try_into()?) as usize;
+        if header_size < DISK_HEADER_LEN || header_size > file_len || !header_size.is_multiple_of(*PAGE_SIZE) {
+            return Err(invalid(&format!("data offset {} is not page aligned within the file", header_size)));
+        }
+
+        let used = count
+            .checked_mul(Self::ITEM_SIZE)
+            .filter(|used| header_size + used <= file_len)
+            .ok_or_else(|| invalid(&format!("{} items exceed file length {}", count, file_len)))?;
+
+        // 通过普通读取计算 CRC 并检查值类型判别值，校验通过之前不建立映射
+        // 块大小取单项大小的整数倍，保证每项完整落在同一块内
+        let mut hasher = crc32fast::Hasher::new();
+        let chunk_items = (1024 * 1024 / Self::ITEM_SIZE).min(count).max(1);
+        let mut chunk = vec![0u8; chunk_items * Self::ITEM_SIZE];
+        let mut offset = 0;
+        while offset < used {
+            let len = chunk.len().min(used - offset);
+            file.read_exact_at(&mut chunk[..len], (header_size + offset) as u64)?;
+            hasher.update(&chunk[..len]);
+            for (i, raw) in chunk[..len].chunks_exact(Self::ITEM_SIZE).enumerate() {
+                let type_byte = raw[Self::VALUE_TYPE_OFFSET];
+                if ValueType::from_repr(type_byte).is_none() {
+                    let index = offset / Self::ITEM_SIZE + i;
+                    return Err(invalid(&format!("invalid value type byte {:#04x} in item {}", type_byte, index)));
+                }
+            }
+            offset += len;
+        }
+        let crc = hasher.finalize();
+        if crc != expected_crc {
+            return Err(invalid(&format!("crc32 mismatch (expected {:08x}, got {:08x})", expected_crc, crc)));
+        }
+
+        // 结果区至少映射一页，空区域无法映射
+        let page_size = *PAGE_SIZE;
+        let mapped = (file_len - header_size).max(1).div_ceil(page_size) * page_size;
+        if header_size + mapped > file_len {
+            file.set_len((header_size + mapped) as u64)?;
+        }
+        let mmap = Self::map_items(&file, header_size, mapped)?;
+
+        self.memory_buffer.clear();
+        drop(self.mmap.take());
+        drop(self.disk_file.take());
+        // 当前管理器自己的旧文件不再使用
+        if let Some(old_path) = self.disk_file_path.take()
+            && old_path != file_path
+            && let Err(e) = std::fs::remove_file(&old_path)
+        {
+            debug!("Failed to remove previous fuzzy disk file {:?}: {:?}", old_path, e);
+        }
+        self.disk_file_path = Some(file_path);
+        self.disk_file = Some(file);
+        self.mmap = Some(mmap);
+        self.disk_count = count;
+        self.total_count = count;
+        self.disk_data_offset = header_size;
+        self.disk_flushed = true;
+        // 磁盘文件只保存结果项，历史信息从当前值重新开始
+        if self.history.is_some() {
+            self.history = Some(self.iter().map(|item| FuzzyItemHistory::new(&item)).collect());
+        }
+
+        info!("Reopened fuzzy disk file: {} results", count);
+        Ok(count)
+    }
+
+    pub fn get_results(&self, start: usize, size: usize) -> Result<Vec<FuzzySearchResultItem>> {
+        let end = std::cmp::min(start + size, self.total_count);
+        if start >= self.total_count {
+            return Ok(Vec::new());
+        }
+
+        let mut results = Vec::with_capacity(end - start);
+
+        for i in start..end {
+            if i < self.memory_buffer.len() {
+                results.push(self.memory_buffer[i]);
+            } else {
+                let disk_index = i - self.memory_buffer.len();
+                if let Some(ref mmap) = self.mmap {
+                    let offset = disk_index * Self::ITEM_SIZE;
+                    unsafe {
+                        let ptr = mmap.as_ptr().add(offset) as *const FuzzySearchResultItem;
+                        results.push(*ptr);
+                    }
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    pub fn get_all_results(&self) -> Result<Vec<FuzzySearchResultItem>> {
+        self.get_results(0, self.total_count)
+    }
+
+    pub fn total_count(&self) -> usize {
+        self.total_count
+    }
+
+    pub fn memory_count(&self) -> usize {
+        self.memory_buffer.len()
+    }
+
+    pub fn disk_count(&self) -> usize {
+        self.disk_count
+    }
+
+    /// 内存缓冲区中实际存活结果占用的字节数，开启历史跟踪时包含历史表
+    pub fn memory_bytes(&self) -> usize {
+        let history_bytes = self.history.as_ref().map_or(0, |history| history.len() * size_of::<FuzzyItemHistory>());
+        self.memory_buffer.len() * Self::ITEM_SIZE + history_bytes
+    }
+
+    /// 磁盘上实际存活结果占用的字节数（不含 mmap 预分配的空间）
+    pub fn disk_bytes(&self) -> usize {
+        self.disk_count * Self::ITEM_SIZE
+    }
+
+    /// 按顺序遍历所有结果（先内存缓冲区，后磁盘 mmap），按需拷贝单项，不分配内存
+    pub fn iter(&self) -> impl Iterator<Item = FuzzySearchResultItem> + '_ {
+        self.memory_buffer.iter().chain(self.disk_items().iter()).copied()
+    }
+
+    /// 遍历 [start, end) 范围内的结果，超出总数的部分会被截断
+    pub fn iter_range(&self, start: usize, end: usize) -> impl Iterator<Item = FuzzySearchResultItem> + '_ {
+        let end = end.min(self.total_count);
+        let start = start.min(end);
+        let memory_len = self.memory_buffer.len();
+
+        let memory = &self.memory_buffer[start.min(memory_len)..end.min(memory_len)];
+        let disk = &self.disk_items()[start.saturating_sub(memory_len)..end.saturating_sub(memory_len)];
+        memory.iter().chain(disk.iter()).copied()
+    }
+
+    /// 按结果顺序返回所有地址，只拷贝地址字段，用于指针扫描
+    pub fn addresses(&self) -> Result<Vec<u64>> {
+        let mut addresses = Vec::with_capacity(self.total_count);
+        addresses.extend(self.iter().map(|item| item.address));
+        Ok(addresses)
+    }
+
+    /// 把地址间隔恰好为 `stride` 字节的相邻结果合并为连续区段，返回 `(起始地址, 数量)`，用于整块冻结
+    ///
+    /// `stride` 为 0 时使用每项自身的值大小。结果按地址排序，单次遍历即可；不相邻的结果各自成为数量为 1 的区段
+    pub fn contiguous_runs(&self, stride: usize) -> Result<Vec<(u64, usize)>> {
+        let mut runs: Vec<(u64, usize)> = Vec::new();
+        let mut next_address = None;
+        for item in self.iter() {
+            let address = item.address;
+            match runs.last_mut() {
+                Some((_, count)) if next_address == Some(address) => *count += 1,
+                _ => runs.push((address, 1)),
+            }
+            let step = if stride == 0 { item.value_size() } else { stride };
+            next_address = address.checked_add(step as u64);
+        }
+        Ok(runs)
+    }
+
+    /// 统计满足条件的结果数量，单次遍历，不分配内存
+    pub fn count_where(&self, pred: impl Fn(&FuzzySearchResultItem) -> bool) -> usize {
+        self.iter().filter(|item| pred(item)).count()
+    }
+
+    /// 按 `as_f64` 统计值分布，返回 `(bucket_min, bucket_max, count)`，桶按值从小到大排列
+    ///
+    /// 先遍历一次求最小/最大值，再遍历一次计数，均不分配结果内存。NaN 与无穷值不计入。
+    /// 没有有效值时返回空列表；所有值相等时只返回一个桶。
+    pub fn value_histogram(&self, buckets: usize) -> Result<Vec<(f64, f64, usize)>> {
+        if buckets == 0 {
+            return Err(JniErrorCode::InvalidArgument.error("Histogram bucket count must be greater than 0"));
+        }
+
+        let (min, max) = self
+            .iter()
+            .map(|item| item.as_f64())
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+        if min > max {
+            return Ok(Vec::new());
+        }
+        if min == max {
+            let count = self.count_where(|item| item.as_f64() == min);
+            return Ok(vec![(min, max, count)]);
+        }
+
+        let width = (max - min) / buckets as f64;
+        let mut counts = vec![0usize; buckets];
+        for value in self.iter().map(|item| item.as_f64()).filter(|value| value.is_finite()) {
+            let index = (((value - min) / width) as usize).min(buckets - 1);
+            counts[index] += 1;
+        }
+
+        Ok(counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let bucket_min = min + width * i as f64;
+                let bucket_max = if i == buckets - 1 { max } else { min + width * (i + 1) as f64 };
+                (bucket_min, bucket_max, count)
+            })
+            .collect())
+    }
+
+    /// 返回按当前值排序的结果副本，存储本身仍按地址排序
+    ///
+    /// 浮点类型按 `as_f64` 比较（total_cmp，NaN 排在最大端），其余按 `as_i64` 比较；值相同时保持地址顺序
+    pub fn sorted_by_value(&self, descending: bool) -> Result<Vec<FuzzySearchResultItem>> {
+        Ok(self.sorted_by_value_indexed(descending)?.into_iter().map(|(_, item)| item).collect())
+    }
+
+    /// 同 [`Self::sorted_by_value`]，同时返回每项在地址顺序中的索引
+    pub fn sorted_by_value_indexed(&self, descending: bool) -> Result<Vec<(usize, FuzzySearchResultItem)>> {
+        let mut items: Vec<(usize, FuzzySearchResultItem)> = Vec::with_capacity(self.total_count);
+        items.extend(self.iter().enumerate());
+
+        items.sort_by(|(_, a), (_, b)| {
+            let ordering = if a.value_type.is_float_type() || b.value_type.is_float_type() {
+                a.as_f64().total_cmp(&b.as_f64())
+            } else {
+                a.as_i64().cmp(&b.as_i64())
+            };
+            if descending { ordering.reverse() } else { ordering }
+        });
+        Ok(items)
+    }
+
+    /// 仅保留值在结果集中唯一的项（用于查找不重复的标识值），返回保留数量
+    pub fn keep_unique_values(&mut self) -> Result<usize> {
+        let mut value_counts: HashMap<i64, usize> = HashMap::new();
+        for item in self.memory_buffer.iter().chain(self.disk_items()) {
+            *value_counts.entry(item.value_key()).or_insert(0) += 1;
+        }
+
+        let keep_indices: Vec<usize> = self
+            .memory_buffer
+            .iter()
+            .chain(self.disk_items())
+            .enumerate()
+            .filter(|(_, item)| value_counts[&item.value_key()] == 1)
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let kept = keep_indices.len();
+        self.keep_only_results(keep_indices)?;
+        debug!("Kept {} fuzzy results with unique values", kept);
+        Ok(kept)
+    }
+
+    /// 将所有项的值类型改为 `new_type`，保留地址与原始字节，之后的比较按新类型解释（无需重新扫描）
+    ///
+    /// 只改标签不改字节：改为更窄的类型时读取原字节的低位部分，改为更宽的类型时高位为扫描时补的 0。
+    /// 最小/最大值在不同类型下的大小关系不同，因此重置为当前值。变长类型（文本/字节序列）的编码不同，不能互相转换
+    pub fn reinterpret_value_type(&mut self, new_type: ValueType) -> Result<()> {
+        if new_type.is_variable_length() || self.iter().any(|item| item.value_type.is_variable_length()) {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Cannot reinterpret variable-length results as {}", new_type)));
+        }
+
+        self.mark_disk_dirty()?;
+        let relabel = |item: &mut FuzzySearchResultItem| item.value_type = new_type;
+        self.memory_buffer.iter_mut().for_each(relabel);
+        self.disk_items_mut().iter_mut().for_each(relabel);
+        if let Some(mut history) = self.history.take() {
+            history.iter_mut().zip(self.iter()).for_each(|(entry, item)| entry.reset_range(&item));
+            self.history = Some(history);
+        }
+
+        debug!("Reinterpreted {} fuzzy results as {}", self.total_count, new_type);
+        Ok(())
+    }
+
+    /// 仅保留地址位于 [min, max] 内的项
+    pub fn filter_address_range(&mut self, min: u64, max: u64) -> Result<()> {
+        if min > max {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Invalid address range: 0x{:x} > 0x{:x}", min, max)));
+        }
+
+        let keep_indices: Vec<usize> = self
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| (min..=max).contains(&{ item.address }))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        self.keep_only_results(keep_indices)?;
+        debug!("Address range filter [0x{:x}, 0x{:x}] kept {} fuzzy results", min, max, self.total_count);
+        Ok(())
+    }
+
+    /// 仅保留地址出现在 `other_addresses` 中的项，`other_addresses` 须升序排列
+    /// 结果本身按地址有序，归并遍历一次即可完成求交，允许 `other_addresses` 含重复地址
+    pub fn intersect_with(&mut self, other_addresses: &[u64]) -> Result<()> {
+        if !other_addresses.is_sorted() {
+            return Err(JniErrorCode::InvalidArgument.error("Addresses to intersect with must be sorted"));
+        }
+
+        let mut other = other_addresses.iter().peekable();
+        let keep_indices: Vec<usize> = self
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                let address = item.address;
+                while other.next_if(|&&other_address| other_address < address).is_some() {}
+                other.peek().is_some_and(|&&other_address| other_address == address)
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        self.keep_only_results(keep_indices)?;
+        debug!("Intersection with {} addresses kept {} fuzzy results", other_addresses.len(), self.total_count);
+        Ok(())
+    }
+
+    /// 仅保留指定标签的项，返回保留数量
+    pub fn keep_only_tag(&mut self, tag: u16) -> Result<usize> {
+        let keep_indices: Vec<usize> = (0..self.total_count).filter(|&idx| self.tag_of(idx) == tag).collect();
+
+        let kept = keep_indices.len();
+        self.keep_only_results(keep_indices)?;
+        debug!("Kept {} fuzzy results tagged {}", kept, tag);
+        Ok(kept)
+    }
+
+    /// 移除指定标签的项，返回移除数量
+    pub fn remove_tag(&mut self, tag: u16) -> Result<usize> {
+        let keep_indices: Vec<usize> = (0..self.total_count).filter(|&idx| self.tag_of(idx) != tag).collect();
+
+        let removed = self.total_count - keep_indices.len();
+        self.keep_only_results(keep_indices)?;
+        debug!("Removed {} fuzzy results tagged {}", removed, tag);
+        Ok(removed)
     }
 
-    /// 读取为 f64 值（用于浮点数比较）
-    #[inline]
-    pub fn as_f64(&self) -> f64 {
-        match self.value_type {
-            ValueType::Byte => self.value[0] as i8 as f64,
-            ValueType::Word => i16::from_le_bytes(self.value[..2].try_into().unwrap()) as f64,
-            ValueType::Dword | ValueType::Auto | ValueType::Xor => i32::from_le_bytes(self.value[..4].try_into().unwrap()) as f64,
-            ValueType::Qword => i64::from_le_bytes(self.value) as f64,
-            ValueType::Float => f32::from_le_bytes(self.value[..4].try_into().unwrap()) as f64,
-            ValueType::Double => f64::from_le_bytes(self.value),
+    /// 返回地址位于 [min, max] 内的结果及其索引
+    /// 模糊结果按地址有序存储，内存缓冲区与磁盘区分别二分查找
+    pub fn find_in_range(&self, min: u64, max: u64) -> Vec<(usize, FuzzySearchResultItem)> {
+        let memory_len = self.memory_buffer.len();
+        let mut found = Vec::new();
+
+        for (base, items) in [(0, self.memory_buffer.as_slice()), (memory_len, self.disk_items())] {
+            let start = items.partition_point(|item| { item.address } < min);
+            let end = items.partition_point(|item| { item.address } <= max);
+            found.extend(items[start..end.max(start)].iter().enumerate().map(|(i, item)| (base + start + i, *item)));
         }
+
+        found
     }
 
-    /// 检查新值是否满足模糊搜索条件
-    #[inline]
-    pub fn matches_condition(&self, new_bytes: &[u8], condition: FuzzyCondition) -> bool {
-        let new_item = FuzzySearchResultItem::from_bytes(self.address, new_bytes, self.value_type);
+    /// 按地址查找结果，返回其索引与结果项，索引可直接用于 update_result / remove_result
+    /// 与 find_in_range 相同，内存缓冲区与磁盘区分别二分查找
+    pub fn find_by_address(&self, address: u64) -> Option<(usize, FuzzySearchResultItem)> {
+        let memory_len = self.memory_buffer.len();
 
-        if self.value_type.is_float_type() {
-            self.matches_condition_float(&new_item, condition)
-        } else {
-            self.matches_condition_int(&new_item, condition)
-        }
+        [(0, self.memory_buffer.as_slice()), (memory_len, self.disk_items())]
+            .into_iter()
+            .find_map(|(base, items)| {
+                let index = items.binary_search_by_key(&address, |item| item.address).ok()?;
+                Some((base + index, items[index]))
+            })
     }
 
-    fn matches_condition_int(&self, new_item: &FuzzySearchResultItem, condition: FuzzyCondition) -> bool {
-        let old_val = self.as_i64();
-        let new_val = new_item.as_i64();
-        let diff = new_val.wrapping_sub(old_val);
+    /// 在磁盘文件已使用区域内按字节查找 pattern，返回匹配处相对文件起始的字节偏移
+    pub fn find_byte_pattern(&self, pattern: &[u8]) -> Vec<usize> {
+        let used = self.disk_count * Self::ITEM_SIZE;
+        match self.mmap {
+            Some(ref mmap) if used > 0 => scan_bytes(&mmap[..used], pattern, 1),
+            _ => Vec::new(),
+        }
+    }
 
-        match condition {
-            FuzzyCondition::Initial => true,
-            FuzzyCondition::Unchanged => old_val == new_val,
-            FuzzyCondition::Changed => old_val != new_val,
-            FuzzyCondition::Increased => new_val > old_val,
-            FuzzyCondition::Decreased => new_val < old_val,
-            FuzzyCondition::IncreasedBy(amount) => diff == amount,
-            FuzzyCondition::DecreasedBy(amount) => diff == -amount,
-            FuzzyCondition::IncreasedByRange(min, max) => diff >= min && diff <= max,
-            FuzzyCondition::DecreasedByRange(min, max) => {
-                let neg_diff = -diff;
-                neg_diff >= min && neg_diff <= max
-            },
-            FuzzyCondition::IncreasedByPercent(percent) => {
-                if old_val == 0 {
-                    new_val > 0
-                } else {
-                    let threshold = (old_val as f64 * (1.0 + percent as f64)) as i64;
-                    new_val >= threshold
-                }
-            },
-            FuzzyCondition::DecreasedByPercent(percent) => {
-                if old_val == 0 {
-                    new_val < 0
-                } else {
-                    let threshold = (old_val as f64 * (1.0 - percent as f64)) as i64;
-                    new_val <= threshold
-                }
+    /// 以切片形式访问磁盘中的结果项（packed 结构体对齐为 1，可直接映射）
+    fn disk_items(&self) -> &[FuzzySearchResultItem] {
+        match self.mmap {
+            Some(ref mmap) if self.disk_count > 0 => unsafe {
+                std::slice::from_raw_parts(mmap.as_ptr() as *const FuzzySearchResultItem, self.disk_count)
             },
+            _ => &[],
         }
     }
 
-    fn matches_condition_float(&self, new_item: &FuzzySearchResultItem, condition: FuzzyCondition) -> bool {
-        let old_val = self.as_f64();
-        let new_val = new_item.as_f64();
-        let diff = new_val - old_val;
-        let epsilon = 1e-9;
-
-        match condition {
-            FuzzyCondition::Initial => true,
-            FuzzyCondition::Unchanged => (old_val - new_val).abs() < epsilon,
-            FuzzyCondition::Changed => (old_val - new_val).abs() >= epsilon,
-            FuzzyCondition::Increased => new_val > old_val + epsilon,
-            FuzzyCondition::Decreased => new_val < old_val - epsilon,
-            FuzzyCondition::IncreasedBy(amount) => (diff - amount as f64).abs() < epsilon,
-            FuzzyCondition::DecreasedBy(amount) => (diff + amount as f64).abs() < epsilon,
-            FuzzyCondition::IncreasedByRange(min, max) => diff >= min as f64 && diff <= max as f64,
-            FuzzyCondition::DecreasedByRange(min, max) => {
-                let neg_diff = -diff;
-                neg_diff >= min as f64 && neg_diff <= max as f64
-            },
-            FuzzyCondition::IncreasedByPercent(percent) => {
-                if old_val.abs() < epsilon {
-                    new_val > epsilon
-                } else {
-                    let threshold = old_val * (1.0 + percent as f64);
-                    new_val >= threshold
-                }
-            },
-            FuzzyCondition::DecreasedByPercent(percent) => {
-                if old_val.abs() < epsilon {
-                    new_val < -epsilon
-                } else {
-                    let threshold = old_val * (1.0 - percent as f64);
-                    new_val <= threshold
-                }
+    fn disk_items_mut(&mut self) -> &mut [FuzzySearchResultItem] {
+        match self.mmap {
+            Some(ref mut mmap) if self.disk_count > 0 => unsafe {
+                std::slice::from_raw_parts_mut(mmap.as_mut_ptr() as *mut FuzzySearchResultItem, self.disk_count)
             },
+            _ => &mut [],
         }
     }
 
-    /// 更新值（用于细化搜索后保存新值）
-    pub fn with_new_value(&self, new_bytes: &[u8]) -> Self {
-        FuzzySearchResultItem::from_bytes(self.address, new_bytes, self.value_type)
+    /// 将多个区域的原始字节按 stride 切分为结果项，按区域顺序依次插入，返回插入数量
+    /// stride 为 0 时使用值类型大小
+    pub fn add_regions(&mut self, regions: Vec<(u64, Vec<u8>)>, value_type: ValueType, stride: usize) -> Result<usize> {
+        let per_region: Vec<Vec<FuzzySearchResultItem>> = regions
+            .iter()
+            .map(|(base, bytes)| Self::region_items(*base, bytes, value_type, stride, self.byte_order()))
+            .collect();
+        self.insert_region_items(per_region)
     }
-}
 
-/// 模糊搜索结果管理器 - 内存 + 磁盘混合存储
-pub struct FuzzySearchResultManager {
-    memory_buffer: Vec<FuzzySearchResultItem>,
-    memory_buffer_capacity: usize,
-    cache_dir: PathBuf,
-    disk_file_path: Option<PathBuf>,
-    disk_file: Option<File>,
-    mmap: Option<MmapMut>,
-    disk_count: usize,
-    total_count: usize,
-}
+    /// `add_regions` 的并行版本：各区域的结果项并行构建，插入仍按区域顺序串行进行，
+    /// 因此结果与串行版本完全一致
+    pub fn add_regions_parallel(&mut self, regions: Vec<(u64, Vec<u8>)>, value_type: ValueType, stride: usize) -> Result<usize> {
+        use rayon::prelude::*;
 
-impl FuzzySearchResultManager {
-    const ITEM_SIZE: usize = size_of::<FuzzySearchResultItem>();
+        let per_region: Vec<Vec<FuzzySearchResultItem>> = regions
+            .par_iter()
+            .map(|(base, bytes)| Self::region_items(*base, bytes, value_type, stride, self.byte_order()))
+            .collect();
+        self.insert_region_items(per_region)
+    }
 
-    pub fn new(memory_buffer_size: usize, cache_dir: PathBuf) -> Self {
-        let capacity = if memory_buffer_size == 0 { 0 } else { memory_buffer_size / Self::ITEM_SIZE };
+    /// 根据新读取的值细化结果，返回满足条件的结果项（值已更新为新值）
+    ///
+    /// `new_values` 为 (地址, 新值) 对，必须按地址升序排列；找不到新值的结果项视为读取失败并丢弃
+    /// 比较方式见 [`set_compare_options`](Self::set_compare_options)
+    pub fn refine(&self, new_values: &[(u64, [u8; 8])], condition: FuzzyCondition) -> Vec<FuzzySearchResultItem> {
+        self.memory_buffer
+            .iter()
+            .chain(self.disk_items().iter())
+            .enumerate()
+            .filter_map(|(index, item)| Self::refine_item(item, new_values, condition, self.compare_options, self.history(index)))
+            .collect()
+    }
 
-        if memory_buffer_size == 0 {
-            info!(
-                "Initializing FuzzySearchResultManager: memory_buffer_capacity=0 (direct disk write mode), cache_dir={:?}",
-                cache_dir
-            );
-        } else {
-            info!(
-                "Initializing FuzzySearchResultManager: memory_buffer_capacity={} items ({} MB), cache_dir={:?}",
-                capacity,
-                memory_buffer_size / 1024 / 1024,
-                cache_dir
-            );
+    /// 用按当前结果顺序排列的新值原地细化：`fresh_values` 每项占 8 字节（不足 8 字节的值放在低位），
+    /// 满足条件的项以新值更新（开启历史跟踪时原值移入上一次的值）并保留，其余移除，返回剩余数量
+    ///
+    /// 与 [`refine`](Self::refine) 不同，调用方不需要按地址查找新值，也不需要自行维护索引
+    pub fn refine_in_place(&mut self, fresh_values: &[u8], condition: FuzzyCondition) -> Result<usize> {
+        if fresh_values.len() != self.total_count * 8 {
+            return Err(JniErrorCode::InvalidArgument.error(format!(
+                "Fresh values must hold 8 bytes per result: got {} bytes for {} results",
+                fresh_values.len(),
+                self.total_count
+            )));
         }
 
-        FuzzySearchResultManager {
-            memory_buffer: Vec::with_capacity(capacity),
-            memory_buffer_capacity: capacity,
-            cache_dir,
-            disk_file_path: None,
-            disk_file: None,
-            mmap: None,
-            disk_count: 0,
-            total_count: 0,
-        }
+        self.mark_disk_dirty()?;
+        let options = self.compare_options;
+        let mut history = self.history.take();
+        let mut keep_indices = Vec::new();
+        let mut refine = |index: usize, item: &mut FuzzySearchResultItem| {
+            // 超过 8 字节的变长值无法在此路径细化，直接淘汰
+            let Some(new_bytes) = fresh_values[index * 8..index * 8 + 8].get(..item.value_size()) else {
+                return;
+            };
+            let entry = history.as_mut().map(|history| &mut history[index]);
+            if item.matches_condition_history(new_bytes, condition, options, entry.as_deref()) {
+                let new_item = item.with_new_value_ordered(new_bytes, options.byte_order);
+                if let Some(entry) = entry {
+                    *entry = entry.advance(item, &new_item);
+                }
+                *item = new_item;
+                keep_indices.push(index);
+            }
+        };
+
+        let memory_len = self.memory_buffer.len();
+        self.memory_buffer.iter_mut().enumerate().for_each(|(index, item)| refine(index, item));
+        self.disk_items_mut().iter_mut().enumerate().for_each(|(index, item)| refine(memory_len + index, item));
+        self.history = history;
+
+        self.keep_only_results(keep_indices)?;
+        debug!("Refined fuzzy results in place with {:?}: {} remain", condition, self.total_count);
+        Ok(self.total_count)
     }
 
-    pub fn clear(&mut self) -> Result<()> {
-        self.memory_buffer.clear();
-        self.total_count = 0;
-        self.disk_count = 0;
-        debug!("Fuzzy search results cleared");
-        Ok(())
+    /// `refine` 的并行版本，使用 rayon 分块并行判断条件，结果顺序与串行版本一致
+    /// 细化期间只读访问内存缓冲区和 mmap，因此可以安全地并行
+    pub fn refine_parallel(&self, new_values: &[(u64, [u8; 8])], condition: FuzzyCondition) -> Vec<FuzzySearchResultItem> {
+        use rayon::prelude::*;
+
+        self.memory_buffer
+            .par_iter()
+            .chain(self.disk_items().par_iter())
+            .enumerate()
+            .filter_map(|(index, item)| Self::refine_item(item, new_values, condition, self.compare_options, self.history(index)))
+            .collect()
     }
 
-    pub fn clear_disk(&mut self) -> Result<()> {
-        drop(self.mmap.take());
-        drop(self.disk_file.take());
+    #[inline]
+    fn refine_item(
+        item: &FuzzySearchResultItem,
+        new_values: &[(u64, [u8; 8])],
+        condition: FuzzyCondition,
+        options: FuzzyCompareOptions,
+        history: Option<FuzzyItemHistory>,
+    ) -> Option<FuzzySearchResultItem> {
+        let address = item.address;
+        let index = new_values.binary_search_by_key(&address, |(addr, _)| *addr).ok()?;
+        // 快照只有 8 字节，超过 8 字节的变长值无法在此路径细化
+        let new_bytes = new_values[index].1.get(..item.value_size())?;
+        item.matches_condition_history(new_bytes, condition, options, history.as_ref())
+            .then(|| item.with_new_value_ordered(new_bytes, options.byte_order))
+    }
 
-        if let Some(ref path) = self.disk_file_path {
-            if path.exists() {
-                std::fs::remove_file(path)?;
-                debug!("Removed fuzzy disk file: {:?}", path);
-            }
+    fn region_items(base: u64, bytes: &[u8], value_type: ValueType, stride: usize, order: ByteOrder) -> Vec<FuzzySearchResultItem> {
+        let size = value_type.size();
+        let stride = if stride == 0 { size } else { stride };
+        if bytes.len() < size {
+            return Vec::new();
         }
 
-        self.disk_file_path = None;
-        self.disk_count = 0;
-        info!("Fuzzy disk resources cleared");
-        Ok(())
+        (0..=bytes.len() - size)
+            .step_by(stride)
+            .map(|offset| FuzzySearchResultItem::from_bytes_ordered(base + offset as u64, &bytes[offset..offset + size], value_type, order))
+            .collect()
     }
 
-    pub fn destroy(&mut self) -> Result<()> {
-        self.memory_buffer.clear();
-        self.total_count = 0;
-        self.disk_count = 0;
-
-        if let Some(ref path) = self.disk_file_path {
-            drop(self.mmap.take());
-            drop(self.disk_file.take());
-            if path.exists() {
-                std::fs::remove_file(path)?;
-                debug!("Removed fuzzy disk file: {:?}", path);
-            }
+    fn insert_region_items(&mut self, per_region: Vec<Vec<FuzzySearchResultItem>>) -> Result<usize> {
+        let added = per_region.iter().map(Vec::len).sum();
+        self.reserve(added)?;
+        for items in per_region {
+            self.add_results_bulk(&items)?;
         }
-
-        self.disk_file_path = None;
-        info!("FuzzySearchResultManager destroyed");
-        Ok(())
+        Ok(added)
     }
 
-    pub fn add_result(&mut self, item: FuzzySearchResultItem) -> Result<()> {
-        if self.memory_buffer_capacity == 0 {
-            self.write_to_disk(&item)?;
-        } else if self.memory_buffer.len() < self.memory_buffer_capacity {
-            self.memory_buffer.push(item);
-        } else {
-            self.write_to_disk(&item)?;
+    /// 以可移植格式导出所有结果，与内存中的 packed 布局无关，可在不同设备/位宽之间迁移
+    ///
+    /// 格式（全部小端）：
+    /// - 4 字节魔数 `MMFR`
+    /// - u16 版本号
+    /// - u8 值类型 id（`ValueType::to_id`），u8 保留
+    /// - u64 结果数量
+    /// - 每项：u64 地址 + 8 字节原始值
+    ///
+    /// 所有结果必须为同一值类型，返回写入的结果数量
+    pub fn export_portable<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        self.write_portable_header(writer, PORTABLE_VERSION)?;
+
+        for item in self.memory_buffer.iter().chain(self.disk_items().iter()) {
+            let address = item.address;
+            let value = item.value;
+            writer.write_all(&address.to_le_bytes())?;
+            writer.write_all(&value)?;
         }
+        writer.flush()?;
 
-        self.total_count += 1;
-        Ok(())
+        Ok(self.total_count)
     }
 
-    fn write_to_disk(&mut self, item: &FuzzySearchResultItem) -> Result<()> {
-        if self.disk_file.is_none() {
-            self.init_disk_file()?;
-        }
+    /// 以紧凑编码导出所有结果（可移植格式 v2），文件头与 [`Self::export_portable`] 相同，
+    /// 结果区按 [`super::packed`] 编码：等差地址段只记录起始间隔、步长与数量，值只保留值类型占用的字节
+    pub fn export_portable_packed<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let value_type = self.write_portable_header(writer, PORTABLE_VERSION_PACKED)?;
 
-        if let Some(ref mut mmap) = self.mmap {
-            let offset = self.disk_count * Self::ITEM_SIZE;
-            let mmap_size = mmap.len();
+        let mut packed = PackedWriter::new(writer, value_type);
+        for item in self.memory_buffer.iter().chain(self.disk_items().iter()) {
+            let value = item.value;
+            packed.push(item.address, &value)?;
+        }
+        packed.finish()?;
 
-            if offset + Self::ITEM_SIZE > mmap_size {
-                drop(self.mmap.take());
-                let new_size = mmap_size + 128 * 1024 * 1024;
-                if let Some(ref file) = self.disk_file {
-                    file.set_len(new_size as u64)?;
-                }
-                self.mmap = Some(unsafe { MmapMut::map_mut(self.disk_file.as_ref().unwrap())? });
-            }
+        Ok(self.total_count)
+    }
 
-            let mmap = self.mmap.as_mut().unwrap();
-            unsafe {
-                let ptr = mmap.as_mut_ptr().add(offset) as *mut FuzzySearchResultItem;
-                ptr.write(*item);
+    /// 所有结果共同的值类型，没有结果时为 None；类型不一致时返回最先出现的两种类型
+    fn common_value_type(&self) -> std::result::Result<Option<ValueType>, (ValueType, ValueType)> {
+        let mut value_type = None;
+        for item in self.memory_buffer.iter().chain(self.disk_items().iter()) {
+            let typ = item.value_type;
+            match value_type {
+                None => value_type = Some(typ),
+                Some(expected) if expected != typ => return Err((expected, typ)),
+                _ => {},
             }
-
-            self.disk_count += 1;
         }
-
-        Ok(())
+        Ok(value_type)
     }
 
-    fn init_disk_file(&mut self) -> Result<()> {
-        let file_path = self.cache_dir.join("mamu_fuzzy_results.bin");
-
-        debug!("Creating fuzzy disk file: {:?}", file_path);
+    /// 写入可移植格式文件头，返回所有结果共同的值类型（没有结果时为 Dword 占位）
+    fn write_portable_header<W: Write>(&self, writer: &mut W, version: u16) -> Result<ValueType> {
+        self.ensure_stored()?;
+        let value_type = self.common_value_type().map_err(|(expected, typ)| {
+            JniErrorCode::InvalidArgument.error(format!("Cannot export mixed value types: {} and {}", expected, typ))
+        })?;
+
+        let type_id = value_type.map_or(0, |typ| typ.to_id()) as u8;
+        writer.write_all(&PORTABLE_MAGIC)?;
+        writer.write_all(&version.to_le_bytes())?;
+        writer.write_all(&[type_id, 0])?;
+        writer.write_all(&(self.total_count as u64).to_le_bytes())?;
+        Ok(value_type.unwrap_or(ValueType::Dword))
+    }
 
-        let initial_size = 128 * 1024 * 1024;
-        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&file_path)?;
+    /// 从可移植格式（v1 或紧凑编码的 v2）导入结果，替换当前所有结果，返回导入的结果数量
+    pub fn import_portable<R: Read>(&mut self, reader: &mut R) -> Result<usize> {
+        let mut header = [0u8; 16];
+        reader.read_exact(&mut header)?;
 
-        file.set_len(initial_size as u64)?;
+        if header[0..4] != PORTABLE_MAGIC {
+            return Err(JniErrorCode::InvalidArgument.error("Invalid portable result file: bad magic"));
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != PORTABLE_VERSION && version != PORTABLE_VERSION_PACKED {
+            return Err(JniErrorCode::InvalidArgument.error(format!("Unsupported portable result version: {}", version)));
+        }
+        let value_type = ValueType::from_id(header[6] as i32)
+            .ok_or_else(|| JniErrorCode::InvalidArgument.error(format!("Invalid value type id: {}", header[6])))?;
+        let count = u64::from_le_bytes(header[8..16].try_into()?) as usize;
 
-        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        self.clear()?;
+        if version == PORTABLE_VERSION_PACKED {
+            read_packed(reader, value_type, count, |address, value| {
+                self.add_result(FuzzySearchResultItem::new(address, value, value_type))
+            })?;
+            info!("Imported {} packed portable fuzzy results ({})", count, value_type);
+            return Ok(count);
+        }
 
-        self.disk_file_path = Some(file_path);
-        self.disk_file = Some(file);
-        self.mmap = Some(mmap);
+        let mut record = [0u8; 16];
+        for _ in 0..count {
+            reader.read_exact(&mut record)?;
+            let address = u64::from_le_bytes(record[0..8].try_into()?);
+            let value: [u8; 8] = record[8..16].try_into()?;
+            self.add_result(FuzzySearchResultItem::new(address, value, value_type))?;
+        }
 
-        info!("Fuzzy disk file initialized with size {} MB", initial_size / 1024 / 1024);
-        Ok(())
+        info!("Imported {} portable fuzzy results ({})", count, value_type);
+        Ok(count)
     }
 
-    pub fn get_results(&self, start: usize, size: usize) -> Result<Vec<FuzzySearchResultItem>> {
-        let end = std::cmp::min(start + size, self.total_count);
-        if start >= self.total_count {
-            return Ok(Vec::new());
+    /// 保存当前会话（内存缓冲区 + 磁盘文件中的全部结果），用于进程被杀后恢复
+    ///
+    /// 格式（全部小端）：4 字节魔数 `MMFS`、u16 版本号、2 字节类型信息、u64 结果数量，之后为结果区。
+    ///
+    /// 每项历史信息为 8 字节上一次的值 + 8 字节最小值 + 8 字节最大值 + u32 变化次数 + u16 分组标签。
+    ///
+    /// 所有结果为同一值类型时写出紧凑编码（版本 2）：类型信息为 u8 值类型 id + u8 标记。开启历史跟踪时先写每项的历史信息，
+    /// 之后按 [`super::packed`] 紧凑编码地址与当前值。
+    ///
+    /// 包含多种值类型时写出逐项记录（版本 1）：类型信息保留为 0，每项为 u64 地址 + 8 字节当前值 + 历史信息 + u8 值类型 id；
+    /// 未开启历史跟踪时历史信息按当前值写出，变化次数为 0，未分组
+    pub fn save_session(&self, path: &Path) -> Result<usize> {
+        self.ensure_stored()?;
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&SESSION_MAGIC)?;
+        match self.common_value_type() {
+            Ok(value_type) => self.write_session_packed(&mut writer, value_type.unwrap_or(ValueType::Dword))?,
+            Err(_) => self.write_session_records(&mut writer)?,
         }
+        writer.flush()?;
 
-        let mut results = Vec::with_capacity(end - start);
+        info!("Saved fuzzy session: {} results -> {:?}", self.total_count, path);
+        Ok(self.total_count)
+    }
 
-        for i in start..end {
-            if i < self.memory_buffer.len() {
-                results.push(self.memory_buffer[i]);
-            } else {
-                let disk_index = i - self.memory_buffer.len();
-                if let Some(ref mmap) = self.mmap {
-                    let offset = disk_index * Self::ITEM_SIZE;
-                    unsafe {
-                        let ptr = mmap.as_ptr().add(offset) as *const FuzzySearchResultItem;
-                        results.push(*ptr);
-                    }
-                }
+    /// 紧凑编码会话文件的文件头（魔数之后）与结果区
+    fn write_session_packed<W: Write>(&self, writer: &mut W, value_type: ValueType) -> Result<()> {
+        let flags = if self.history.is_some() { SESSION_FLAG_HISTORY } else { 0 };
+        writer.write_all(&SESSION_VERSION_PACKED.to_le_bytes())?;
+        writer.write_all(&[value_type.to_id() as u8, flags])?;
+        writer.write_all(&(self.total_count as u64).to_le_bytes())?;
+
+        if self.history.is_some() {
+            for (index, item) in self.iter().enumerate() {
+                let entry = self.history(index).unwrap_or_else(|| FuzzyItemHistory::new(&item));
+                writer.write_all(&entry.to_session_bytes())?;
             }
         }
 
-        Ok(results)
+        let mut packed = PackedWriter::new(writer, value_type);
+        for item in self.iter() {
+            let value = item.value;
+            packed.push(item.address, &value)?;
+        }
+        packed.finish()
     }
 
-    pub fn get_all_results(&self) -> Result<Vec<FuzzySearchResultItem>> {
-        self.get_results(0, self.total_count)
+    /// 逐项记录会话文件的文件头（魔数之后）与结果区
+    fn write_session_records<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&SESSION_VERSION.to_le_bytes())?;
+        writer.write_all(&[0, 0])?;
+        writer.write_all(&(self.total_count as u64).to_le_bytes())?;
+
+        for (index, item) in self.iter().enumerate() {
+            let address = item.address;
+            let value = item.value;
+            let history = self.history(index).unwrap_or_else(|| FuzzyItemHistory::new(&item));
+            writer.write_all(&address.to_le_bytes())?;
+            writer.write_all(&value)?;
+            writer.write_all(&history.to_session_bytes())?;
+            writer.write_all(&[item.value_type.to_id() as u8])?;
+        }
+        Ok(())
     }
 
-    pub fn total_count(&self) -> usize {
-        self.total_count
+    /// 仅计数模式下没有可写出的结果，导出/保存会得到数量与内容不符的文件
+    fn ensure_stored(&self) -> Result<()> {
+        if self.discarding {
+            return Err(JniErrorCode::InvalidArgument.error("Count-only fuzzy results cannot be exported"));
+        }
+        Ok(())
     }
 
-    pub fn memory_count(&self) -> usize {
-        self.memory_buffer.len()
+    /// 从会话文件恢复结果，替换当前所有结果，返回恢复的结果数量
+    /// 超出内存缓冲区容量的部分照常写入磁盘文件，开启历史跟踪时同时恢复历史信息
+    pub fn load_session(&mut self, path: &Path) -> Result<usize> {
+        let reader = SessionReader::open(path)?;
+        let count = reader.remaining;
+
+        self.clear()?;
+        for entry in reader {
+            let (item, history) = entry?;
+            self.add_result_with_history(item, history)?;
+        }
+
+        info!("Loaded fuzzy session: {} results <- {:?}", count, path);
+        Ok(count)
     }
 
-    pub fn disk_count(&self) -> usize {
-        self.disk_count
+    /// 比较两个会话文件，不加载到管理器中，两个文件同时归并遍历一次
+    /// 会话中的结果须按地址升序排列（由有序的结果集保存而来），否则返回错误
+    pub fn diff_sessions(old_path: &Path, new_path: &Path) -> Result<SessionDiff> {
+        let ordered = |path: &Path| -> Result<_> {
+            let mut last = None;
+            let path = path.to_path_buf();
+            Ok(SessionReader::open(&path)?.map(move |entry| {
+                let (item, _) = entry?;
+                let address = item.address;
+                if last.is_some_and(|last| address < last) {
+                    return Err(JniErrorCode::InvalidArgument.error(format!("Session is not ordered by address: {:?}", path)));
+                }
+                last = Some(address);
+                Ok(item)
+            }))
+        };
+        let mut old = ordered(old_path)?;
+        let mut new = ordered(new_path)?;
+
+        let mut diff = SessionDiff::default();
+        let mut old_item = old.next().transpose()?;
+        let mut new_item = new.next().transpose()?;
+        loop {
+            let ordering = match (old_item, new_item) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(o), Some(n)) => { o.address }.cmp(&{ n.address }),
+            };
+            match ordering {
+                Ordering::Less => diff.disappeared.extend(old_item.map(|o| o.address)),
+                Ordering::Greater => diff.appeared.extend(new_item.map(|n| n.address)),
+                Ordering::Equal => {
+                    if let (Some(o), Some(n)) = (old_item, new_item) && (o.value_type != n.value_type || o.value != n.value) {
+                        diff.changed.push(o.address);
+                    }
+                },
+            }
+            if ordering != Ordering::Greater {
+                old_item = old.next().transpose()?;
+            }
+            if ordering != Ordering::Less {
+                new_item = new.next().transpose()?;
+            }
+        }
+
+        debug!(
+            "Session diff: {} appeared, {} disappeared, {} changed",
+            diff.appeared.len(),
+            diff.disappeared.len(),
+            diff.changed.len()
+        );
+        Ok(diff)
     }
 
-    /// 更新指定索引的结果项（用于细化搜索后更新值）
+    /// 更新指定索引的结果项（用于细化搜索后更新值），开启历史跟踪时原值移入上一次的值
     pub fn update_result(&mut self, index: usize, item: FuzzySearchResultItem) -> Result<()> {
         if index >= self.total_count {
-            return Err(anyhow!("Index out of bounds: {} >= {}", index, self.total_count));
+            return Err(JniErrorCode::OutOfBounds.error(format!("Index out of bounds: {} >= {}", index, self.total_count)));
+        }
+
+        let old = self.iter_range(index, index + 1).next();
+        if let Some(old) = old
+            && let Some(entry) = self.history.as_mut().and_then(|history| history.get_mut(index))
+        {
+            *entry = entry.advance(&old, &item);
         }
 
         if index < self.memory_buffer.len() {
             self.memory_buffer[index] = item;
         } else {
+            self.mark_disk_dirty()?;
             let disk_index = index - self.memory_buffer.len();
             if let Some(ref mut mmap) = self.mmap {
                 let offset = disk_index * Self::ITEM_SIZE;
@@ -403,18 +2217,78 @@ impl FuzzySearchResultManager {
         Ok(())
     }
 
+    /// 用预先读取的字节批量更新指定索引的值（开启历史跟踪时原值移入上一次的值），一次遍历内存缓冲区和 mmap
+    /// 越界索引以及超过 8 字节的变长值会被跳过
+    pub fn update_values_batch(&mut self, new_values: &[(usize, [u8; 8])]) -> Result<()> {
+        self.mark_disk_dirty()?;
+        let memory_len = self.memory_buffer.len();
+        let disk_items: &mut [FuzzySearchResultItem] = match self.mmap {
+            Some(ref mut mmap) if self.disk_count > 0 => unsafe {
+                std::slice::from_raw_parts_mut(mmap.as_mut_ptr() as *mut FuzzySearchResultItem, self.disk_count)
+            },
+            _ => &mut [],
+        };
+
+        let mut skipped = 0usize;
+        for (index, bytes) in new_values {
+            let slot = if *index < memory_len {
+                self.memory_buffer.get_mut(*index)
+            } else {
+                disk_items.get_mut(index - memory_len)
+            };
+            let Some(slot) = slot else {
+                skipped += 1;
+                continue;
+            };
+            let Some(new_bytes) = bytes.get(..slot.value_size()) else {
+                skipped += 1;
+                continue;
+            };
+            let new_item = slot.with_new_value_ordered(new_bytes, self.compare_options.byte_order);
+            if let Some(entry) = self.history.as_mut().and_then(|history| history.get_mut(*index)) {
+                *entry = entry.advance(slot, &new_item);
+            }
+            *slot = new_item;
+        }
+
+        if skipped > 0 {
+            debug!("update_values_batch skipped {} of {} entries", skipped, new_values.len());
+        }
+        Ok(())
+    }
+
     /// 批量替换所有结果（用于细化搜索后）
-    pub fn replace_all(&mut self, results: Vec<FuzzySearchResultItem>) -> Result<()> {
+    /// 开启历史跟踪时沿用 `results` 中的历史信息，没有历史信息时从当前值重新开始
+    pub fn replace_all(&mut self, results: FuzzyResultSet) -> Result<()> {
         self.clear()?;
-        for item in results {
-            self.add_result(item)?;
+        let tracking = self.history.take().is_some();
+        self.add_results_bulk(&results.items)?;
+
+        if tracking {
+            let history = if self.discarding {
+                Vec::new()
+            } else {
+                results
+                    .history
+                    .filter(|history| history.len() == results.items.len())
+                    .unwrap_or_else(|| results.items.iter().map(FuzzyItemHistory::new).collect())
+            };
+            self.history = Some(history);
         }
         Ok(())
     }
 
+    /// 当前全部结果及其历史信息
+    pub fn result_set(&self) -> FuzzyResultSet {
+        FuzzyResultSet { items: self.iter().collect(), history: self.history.clone() }
+    }
+
+    /// 删除指定索引的结果，其后的结果依次前移
+    /// 只移动同一存储区（内存缓冲区或磁盘）中位于其后的结果：删除末尾附近的结果几乎不移动数据，
+    /// 删除磁盘区靠前的结果则要移动其后全部磁盘结果，一次删除多项时应使用 remove_results_batch
     pub fn remove_result(&mut self, index: usize) -> Result<()> {
         if index >= self.total_count {
-            return Err(anyhow!("Index out of bounds: {} >= {}", index, self.total_count));
+            return Err(JniErrorCode::OutOfBounds.error(format!("Index out of bounds: {} >= {}", index, self.total_count)));
         }
 
         if index < self.memory_buffer.len() {
@@ -423,6 +2297,9 @@ impl FuzzySearchResultManager {
             let disk_index = index - self.memory_buffer.len();
             self.remove_disk_item(disk_index)?;
         }
+        if let Some(history) = self.history.as_mut() {
+            history.remove(index);
+        }
 
         self.total_count -= 1;
         debug!("Removed fuzzy result at index {}, total count: {}", index, self.total_count);
@@ -433,6 +2310,7 @@ impl FuzzySearchResultManager {
         if disk_index >= self.disk_count {
             return Err(anyhow!("Disk index out of bounds"));
         }
+        self.mark_disk_dirty()?;
 
         if let Some(ref mut mmap) = self.mmap {
             let src_offset = (disk_index + 1) * Self::ITEM_SIZE;
@@ -468,6 +2346,16 @@ impl FuzzySearchResultManager {
         let delete_count = indices.len();
         let memory_len = self.memory_buffer.len();
 
+        if let Some(history) = self.history.as_mut() {
+            let mut delete_iter = indices.iter().peekable();
+            let mut position = 0;
+            history.retain(|_| {
+                let deleted = delete_iter.next_if_eq(&&position).is_some();
+                position += 1;
+                !deleted
+            });
+        }
+
         let (memory_indices, disk_indices): (Vec<usize>, Vec<usize>) = indices.into_iter().partition(|&idx| idx < memory_len);
 
         if !memory_indices.is_empty() {
@@ -520,6 +2408,7 @@ impl FuzzySearchResultManager {
         if sorted_disk_indices.is_empty() || self.disk_count == 0 {
             return Ok(());
         }
+        self.mark_disk_dirty()?;
 
         let Some(ref mut mmap) = self.mmap else {
             return Ok(());
@@ -560,9 +2449,13 @@ impl FuzzySearchResultManager {
 
     pub fn keep_only_results(&mut self, mut keep_indices: Vec<usize>) -> Result<()> {
         if keep_indices.is_empty() {
+            self.mark_disk_dirty()?;
             self.memory_buffer.clear();
             self.disk_count = 0;
             self.total_count = 0;
+            if let Some(history) = self.history.as_mut() {
+                history.clear();
+            }
             debug!("Kept 0 fuzzy results, cleared all");
             return Ok(());
         }
@@ -582,12 +2475,15 @@ impl FuzzySearchResultManager {
             );
 
             keep_indices.sort_unstable();
+            keep_indices.dedup();
+            keep_indices.retain(|&idx| idx < self.total_count);
+            let kept_history = self
+                .history
+                .take()
+                .map(|history| keep_indices.iter().map(|&idx| history[idx]).collect::<Vec<_>>());
 
             let mut kept_items: Vec<FuzzySearchResultItem> = Vec::with_capacity(keep_count);
             for &idx in &keep_indices {
-                if idx >= self.total_count {
-                    continue;
-                }
                 if idx < self.memory_buffer.len() {
                     kept_items.push(self.memory_buffer[idx]);
                 } else {
@@ -609,6 +2505,7 @@ impl FuzzySearchResultManager {
             for item in kept_items {
                 self.add_result(item)?;
             }
+            self.history = kept_history;
 
             debug!("Rebuild complete: kept {} fuzzy results, removed {} results", self.total_count, remove_count);
         } else {
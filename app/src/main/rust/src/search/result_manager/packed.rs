@@ -0,0 +1,175 @@
+//! 紧凑结果编码，用于可移植导出格式 v2 和紧凑编码的会话文件
+//!
+//! 结果按地址切分为等差段，每段写入：
+//! - 变长整数：段起始地址与上一项地址之差（首段相对 0）
+//! - 变长整数：段内步长
+//! - 变长整数：段内数量（至少为 1）
+//! - 段内每项的值，只保留值类型实际占用的字节（变长类型保留完整 8 字节编码）
+//!
+//! 扫描得到的结果通常地址密集、步长固定，整段地址只需几个字节；
+//! 值类型由文件头统一记录，不再逐项保存。差值按 wrapping 计算，地址无序时也能还原
+
+use crate::ext::jni::JniErrorCode;
+use crate::search::types::ValueType;
+use anyhow::Result;
+use std::io::{Read, Write};
+
+/// 每项值实际写入的字节数
+fn value_width(value_type: ValueType) -> usize {
+    if value_type.is_variable_length() { 8 } else { value_type.size().min(8) }
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> Result<()> {
+    let mut buf = [0u8; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len])?;
+    Ok(())
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        value |= ((byte[0] & 0x7f) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(JniErrorCode::InvalidArgument.error("Invalid packed result data: varint too long"))
+}
+
+/// 逐项写入结果，同一等差段内的项缓存在内存中，段结束时一次写出
+pub(super) struct PackedWriter<'a, W: Write> {
+    writer: &'a mut W,
+    width: usize,
+    /// 上一段最后一项的地址
+    last: u64,
+    /// 当前段：起始地址、步长、数量
+    run: Option<(u64, u64, u64)>,
+    values: Vec<u8>,
+}
+
+impl<'a, W: Write> PackedWriter<'a, W> {
+    pub(super) fn new(writer: &'a mut W, value_type: ValueType) -> Self {
+        Self {
+            writer,
+            width: value_width(value_type),
+            last: 0,
+            run: None,
+            values: Vec::new(),
+        }
+    }
+
+    pub(super) fn push(&mut self, address: u64, value: &[u8; 8]) -> Result<()> {
+        match self.run {
+            Some((start, _, 1)) => {
+                self.run = Some((start, address.wrapping_sub(start), 2));
+            },
+            Some((start, stride, len)) if start.wrapping_add(stride.wrapping_mul(len)) == address => {
+                self.run = Some((start, stride, len + 1));
+            },
+            Some(_) => {
+                self.flush_run()?;
+                self.run = Some((address, 0, 1));
+            },
+            None => self.run = Some((address, 0, 1)),
+        }
+        self.values.extend_from_slice(&value[..self.width]);
+        Ok(())
+    }
+
+    fn flush_run(&mut self) -> Result<()> {
+        let Some((start, stride, len)) = self.run.take() else {
+            return Ok(());
+        };
+        write_varint(self.writer, start.wrapping_sub(self.last))?;
+        write_varint(self.writer, stride)?;
+        write_varint(self.writer, len)?;
+        self.writer.write_all(&self.values)?;
+        self.values.clear();
+        self.last = start.wrapping_add(stride.wrapping_mul(len - 1));
+        Ok(())
+    }
+
+    /// 写出最后一段
+    pub(super) fn finish(mut self) -> Result<()> {
+        self.flush_run()?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// 逐项读取 `count` 项紧凑编码的结果，每次只解码当前段的段头
+pub(super) struct PackedReader<R: Read> {
+    reader: R,
+    width: usize,
+    /// 上一项的地址
+    last: u64,
+    /// 尚未被已读段头覆盖的项数
+    unread: u64,
+    /// 当前段：下一项地址、步长、剩余数量
+    run: (u64, u64, u64),
+}
+
+impl<R: Read> PackedReader<R> {
+    pub(super) fn new(reader: R, value_type: ValueType, count: usize) -> Self {
+        Self {
+            reader,
+            width: value_width(value_type),
+            last: 0,
+            unread: count as u64,
+            run: (0, 0, 0),
+        }
+    }
+
+    /// 读取下一项的地址与值，超过 `count` 项时返回错误
+    pub(super) fn next_item(&mut self) -> Result<(u64, [u8; 8])> {
+        if self.run.2 == 0 {
+            if self.unread == 0 {
+                return Err(JniErrorCode::InvalidArgument.error("Invalid packed result data: no more items"));
+            }
+            let start = self.last.wrapping_add(read_varint(&mut self.reader)?);
+            let stride = read_varint(&mut self.reader)?;
+            let len = read_varint(&mut self.reader)?;
+            if len == 0 || len > self.unread {
+                return Err(JniErrorCode::InvalidArgument.error(format!("Invalid packed result run length: {}", len)));
+            }
+            self.unread -= len;
+            self.run = (start, stride, len);
+        }
+
+        let (address, stride, len) = self.run;
+        let mut value = [0u8; 8];
+        self.reader.read_exact(&mut value[..self.width])?;
+        self.last = address;
+        self.run = (address.wrapping_add(stride), stride, len - 1);
+        Ok((address, value))
+    }
+}
+
+/// 读取 `count` 项紧凑编码的结果，按原顺序逐项回调地址与值
+pub(super) fn read_packed<R: Read>(
+    reader: &mut R,
+    value_type: ValueType,
+    count: usize,
+    mut f: impl FnMut(u64, [u8; 8]) -> Result<()>,
+) -> Result<()> {
+    let mut packed = PackedReader::new(reader, value_type, count);
+    for _ in 0..count {
+        let (address, value) = packed.next_item()?;
+        f(address, value)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,418 @@
+//! Fuzzy condition tests
+
+#[cfg(test)]
+mod tests {
+    use crate::search::result_manager::{FuzzyItemHistory, FuzzySearchResultItem};
+    use crate::search::{ByteOrder, FuzzyCompareOptions, FuzzyCondition, ValueType};
+
+    fn dword(value: i32) -> FuzzySearchResultItem {
+        FuzzySearchResultItem::from_bytes(0x1000, &value.to_le_bytes(), ValueType::Dword)
+    }
+
+    fn float(value: f32) -> FuzzySearchResultItem {
+        FuzzySearchResultItem::from_bytes(0x1000, &value.to_le_bytes(), ValueType::Float)
+    }
+
+    fn opts(unsigned: bool, wrapping: bool) -> FuzzyCompareOptions {
+        FuzzyCompareOptions { unsigned, wrapping, ..Default::default() }
+    }
+
+    #[test]
+    fn test_between_int_inclusive_bounds() {
+        let old = dword(100);
+        let condition = FuzzyCondition::Between(50, 80);
+
+        assert!(old.matches_condition(&50i32.to_le_bytes(), condition));
+        assert!(old.matches_condition(&65i32.to_le_bytes(), condition));
+        assert!(old.matches_condition(&80i32.to_le_bytes(), condition));
+        assert!(!old.matches_condition(&49i32.to_le_bytes(), condition));
+        assert!(!old.matches_condition(&81i32.to_le_bytes(), condition));
+        // 与旧值无关：旧值本身不在范围内也不影响
+        assert!(!old.matches_condition(&100i32.to_le_bytes(), condition));
+        assert!(dword(60).matches_condition(&60i32.to_le_bytes(), condition));
+    }
+
+    #[test]
+    fn test_between_negative_range() {
+        let condition = FuzzyCondition::Between(-10, -1);
+        assert!(dword(0).matches_condition(&(-10i32).to_le_bytes(), condition));
+        assert!(dword(0).matches_condition(&(-1i32).to_le_bytes(), condition));
+        assert!(!dword(0).matches_condition(&0i32.to_le_bytes(), condition));
+    }
+
+    #[test]
+    fn test_between_float_values() {
+        let old = float(100.0);
+
+        // 整数边界作用于浮点值
+        let condition = FuzzyCondition::Between(50, 80);
+        assert!(old.matches_condition(&50.0f32.to_le_bytes(), condition));
+        assert!(old.matches_condition(&79.5f32.to_le_bytes(), condition));
+        assert!(!old.matches_condition(&80.25f32.to_le_bytes(), condition));
+
+        // 浮点边界
+        let condition = FuzzyCondition::BetweenFloat(0.25, 0.75);
+        assert!(old.matches_condition(&0.25f32.to_le_bytes(), condition));
+        assert!(old.matches_condition(&0.75f32.to_le_bytes(), condition));
+        assert!(!old.matches_condition(&0.2f32.to_le_bytes(), condition));
+        assert!(!old.matches_condition(&0.8f32.to_le_bytes(), condition));
+
+        // 浮点边界作用于整数值
+        assert!(dword(0).matches_condition(&3i32.to_le_bytes(), FuzzyCondition::BetweenFloat(2.5, 3.5)));
+        assert!(!dword(0).matches_condition(&4i32.to_le_bytes(), FuzzyCondition::BetweenFloat(2.5, 3.5)));
+    }
+
+    #[test]
+    fn test_increased_by_percent_negative_old_value() {
+        let old = dword(-100);
+        let condition = FuzzyCondition::IncreasedByPercent(0.1);
+
+        // -100 增加 10% => 至少 -90
+        assert!(old.matches_condition(&(-90i32).to_le_bytes(), condition));
+        assert!(old.matches_condition(&(-50i32).to_le_bytes(), condition));
+        assert!(!old.matches_condition(&(-95i32).to_le_bytes(), condition));
+        assert!(!old.matches_condition(&(-110i32).to_le_bytes(), condition));
+    }
+
+    #[test]
+    fn test_decreased_by_percent_negative_old_value() {
+        let old = dword(-100);
+        let condition = FuzzyCondition::DecreasedByPercent(0.1);
+
+        // -100 减少 10% => 至多 -110
+        assert!(old.matches_condition(&(-110i32).to_le_bytes(), condition));
+        assert!(!old.matches_condition(&(-105i32).to_le_bytes(), condition));
+        assert!(!old.matches_condition(&(-90i32).to_le_bytes(), condition));
+    }
+
+    #[test]
+    fn test_percent_positive_old_value_unchanged() {
+        let old = dword(100);
+        assert!(old.matches_condition(&110i32.to_le_bytes(), FuzzyCondition::IncreasedByPercent(0.1)));
+        assert!(!old.matches_condition(&109i32.to_le_bytes(), FuzzyCondition::IncreasedByPercent(0.1)));
+        assert!(old.matches_condition(&90i32.to_le_bytes(), FuzzyCondition::DecreasedByPercent(0.1)));
+        assert!(!old.matches_condition(&91i32.to_le_bytes(), FuzzyCondition::DecreasedByPercent(0.1)));
+    }
+
+    #[test]
+    fn test_percent_negative_old_float() {
+        let old = float(-100.0);
+        assert!(old.matches_condition(&(-90.0f32).to_le_bytes(), FuzzyCondition::IncreasedByPercent(0.1)));
+        assert!(!old.matches_condition(&(-95.0f32).to_le_bytes(), FuzzyCondition::IncreasedByPercent(0.1)));
+        assert!(old.matches_condition(&(-110.0f32).to_le_bytes(), FuzzyCondition::DecreasedByPercent(0.1)));
+        assert!(!old.matches_condition(&(-105.0f32).to_le_bytes(), FuzzyCondition::DecreasedByPercent(0.1)));
+    }
+
+    #[test]
+    fn test_between_from_id() {
+        assert_eq!(FuzzyCondition::from_id(11, 5, 10), Some(FuzzyCondition::Between(5, 10)));
+        assert_eq!(
+            FuzzyCondition::from_id(12, 1.5f64.to_bits() as i64, 2.5f64.to_bits() as i64),
+            Some(FuzzyCondition::BetweenFloat(1.5, 2.5))
+        );
+    }
+
+    #[test]
+    fn test_as_u64_zero_extends() {
+        assert_eq!(dword(-1).as_u64(), 0xFFFF_FFFF);
+        assert_eq!(dword(-1).as_i64(), -1);
+        let byte = FuzzySearchResultItem::from_bytes(0x1000, &[0x80], ValueType::Byte);
+        assert_eq!(byte.as_u64(), 0x80);
+        let word = FuzzySearchResultItem::from_bytes(0x1000, &0xFFFEu16.to_le_bytes(), ValueType::Word);
+        assert_eq!(word.as_u64(), 0xFFFE);
+        let qword = FuzzySearchResultItem::from_bytes(0x1000, &u64::MAX.to_le_bytes(), ValueType::Qword);
+        assert_eq!(qword.as_u64(), u64::MAX);
+    }
+
+    #[test]
+    fn test_percent_threshold_near_i64_limits() {
+        let qword = |value: i64| FuzzySearchResultItem::from_bytes(0x1000, &value.to_le_bytes(), ValueType::Qword);
+
+        // 阈值 i64::MAX / 2 * 2.5 超出 i64 范围，任何值都不满足
+        let old = qword(i64::MAX / 2);
+        assert!(!old.matches_condition(&i64::MAX.to_le_bytes(), FuzzyCondition::IncreasedByPercent(1.5)));
+        // 阈值约为 i64::MAX * 0.75
+        assert!(old.matches_condition(&i64::MAX.to_le_bytes(), FuzzyCondition::IncreasedByPercent(0.5)));
+        assert!(!old.matches_condition(&(i64::MAX / 2 + i64::MAX / 8).to_le_bytes(), FuzzyCondition::IncreasedByPercent(0.5)));
+
+        let old = qword(i64::MIN / 2);
+        assert!(!old.matches_condition(&i64::MIN.to_le_bytes(), FuzzyCondition::DecreasedByPercent(1.5)));
+        assert!(old.matches_condition(&i64::MIN.to_le_bytes(), FuzzyCondition::DecreasedByPercent(0.5)));
+    }
+
+    #[test]
+    fn test_changed_by_with_tolerance() {
+        let old = dword(100);
+        let increased = FuzzyCondition::IncreasedByTolerance(10, 2);
+        let decreased = FuzzyCondition::DecreasedByTolerance(10, 2);
+
+        assert!(old.matches_condition(&108i32.to_le_bytes(), increased));
+        assert!(old.matches_condition(&112i32.to_le_bytes(), increased));
+        assert!(!old.matches_condition(&113i32.to_le_bytes(), increased));
+        assert!(!old.matches_condition(&92i32.to_le_bytes(), increased));
+        assert!(old.matches_condition(&88i32.to_le_bytes(), decreased));
+        assert!(!old.matches_condition(&87i32.to_le_bytes(), decreased));
+        // 精确版本不受影响
+        assert!(!old.matches_condition(&108i32.to_le_bytes(), FuzzyCondition::IncreasedBy(10)));
+
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &0xFFFF_FFFFu32.to_le_bytes(), ValueType::Dword);
+        assert!(old.matches_condition_opts(&0xFFFF_FFF4u32.to_le_bytes(), decreased, opts(true, false)));
+
+        let old = float(1.0);
+        assert!(old.matches_condition(&11.5f32.to_le_bytes(), increased));
+        assert!(!old.matches_condition(&13.5f32.to_le_bytes(), increased));
+        assert!(!old.matches_condition(&f32::NAN.to_le_bytes(), increased));
+    }
+
+    #[test]
+    fn test_unsigned_increase_across_sign_boundary() {
+        let old = dword(i32::MAX);
+        let new_bytes = 0x8000_0000u32.to_le_bytes();
+
+        // 有符号比较时 0x80000000 是负数
+        assert!(!old.matches_condition(&new_bytes, FuzzyCondition::Increased));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::Increased, opts(true, false)));
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::Decreased, opts(true, false)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(1), opts(true, false)));
+    }
+
+    #[test]
+    fn test_unsigned_decrease_and_between() {
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &0xFFFF_FFFFu32.to_le_bytes(), ValueType::Dword);
+        let new_bytes = 0xFFFF_FFF0u32.to_le_bytes();
+
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::Decreased, opts(true, false)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::DecreasedBy(15), opts(true, false)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::DecreasedByRange(10, 20), opts(true, false)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::Between(0xFFFF_0000, 0xFFFF_FFFF), opts(true, false)));
+        assert!(!old.matches_condition(&new_bytes, FuzzyCondition::Between(0xFFFF_0000, 0xFFFF_FFFF)));
+    }
+
+    #[test]
+    fn test_unsigned_qword_full_range() {
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &0u64.to_le_bytes(), ValueType::Qword);
+        let new_bytes = u64::MAX.to_le_bytes();
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::Increased, opts(true, false)));
+        assert!(!old.matches_condition(&new_bytes, FuzzyCondition::Increased));
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::DecreasedByPercent(0.1), opts(true, false)));
+    }
+
+    #[test]
+    fn test_unsigned_ignored_for_float() {
+        let old = float(1.0);
+        assert!(old.matches_condition_opts(&(-1.0f32).to_le_bytes(), FuzzyCondition::Decreased, opts(true, false)));
+    }
+
+    #[test]
+    fn test_wrapping_byte_boundary() {
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &[0xFF], ValueType::Byte);
+        let new_bytes = [0x00];
+
+        // 默认按符号扩展后的 i64 计算：-1 -> 0 是 +1，但无符号时 255 -> 0 是 -255
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(1), opts(true, false)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(1), opts(true, true)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(1), opts(false, true)));
+
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &[0x7F], ValueType::Byte);
+        let new_bytes = [0x80];
+        assert!(!old.matches_condition(&new_bytes, FuzzyCondition::IncreasedBy(1)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(1), opts(false, true)));
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::DecreasedBy(255), opts(false, true)));
+
+        // 0 -> 254 回绕后为减少 2
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &[0x00], ValueType::Byte);
+        assert!(old.matches_condition_opts(&[0xFE], FuzzyCondition::DecreasedBy(2), opts(true, true)));
+        assert!(old.matches_condition_opts(&[0xFE], FuzzyCondition::DecreasedByRange(1, 3), opts(true, true)));
+    }
+
+    #[test]
+    fn test_wrapping_word_boundary() {
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &0xFFFFu16.to_le_bytes(), ValueType::Word);
+        let new_bytes = 0x0002u16.to_le_bytes();
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(3), opts(true, false)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(3), opts(true, true)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedByTolerance(2, 1), opts(true, true)));
+
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &i16::MIN.to_le_bytes(), ValueType::Word);
+        let new_bytes = i16::MAX.to_le_bytes();
+        assert!(!old.matches_condition(&new_bytes, FuzzyCondition::DecreasedBy(1)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::DecreasedBy(1), opts(false, true)));
+    }
+
+    #[test]
+    fn test_wrapping_dword_boundary() {
+        let old = dword(i32::MAX);
+        let new_bytes = i32::MIN.to_le_bytes();
+        assert!(!old.matches_condition(&new_bytes, FuzzyCondition::IncreasedBy(1)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(1), opts(false, true)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedByRange(1, 5), opts(false, true)));
+
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &u32::MAX.to_le_bytes(), ValueType::Dword);
+        let new_bytes = 9u32.to_le_bytes();
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(10), opts(true, false)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(10), opts(true, true)));
+
+        // 非差值类条件保持原有语义
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::Increased, opts(true, true)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::Increased, opts(false, true)));
+    }
+
+    #[test]
+    fn test_wrapping_qword_boundary() {
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &u64::MAX.to_le_bytes(), ValueType::Qword);
+        let new_bytes = 0u64.to_le_bytes();
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(1), opts(true, false)));
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(1), opts(true, true)));
+
+        let old = FuzzySearchResultItem::from_bytes(0x1000, &i64::MIN.to_le_bytes(), ValueType::Qword);
+        let new_bytes = i64::MAX.to_le_bytes();
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::DecreasedBy(1), opts(false, true)));
+        assert!(!old.matches_condition_opts(&new_bytes, FuzzyCondition::DecreasedBy(i64::MIN), opts(false, true)));
+    }
+
+    #[test]
+    fn test_wrapping_ignored_for_float() {
+        let old = float(1.0);
+        assert!(old.matches_condition_opts(&3.0f32.to_le_bytes(), FuzzyCondition::IncreasedBy(2), opts(false, true)));
+        assert!(!old.matches_condition_opts(&3.0f32.to_le_bytes(), FuzzyCondition::IncreasedBy(1), opts(false, true)));
+    }
+
+    /// 依次更新为 `values`，返回最终的结果项与历史信息
+    fn refine_through(mut item: FuzzySearchResultItem, values: &[i32]) -> (FuzzySearchResultItem, FuzzyItemHistory) {
+        let mut history = FuzzyItemHistory::new(&item);
+        for value in values {
+            let updated = item.with_new_value(&value.to_le_bytes());
+            history = history.advance(&item, &updated);
+            item = updated;
+        }
+        (item, history)
+    }
+
+    #[test]
+    fn test_change_count_tracks_updates() {
+        assert_eq!(FuzzyItemHistory::new(&dword(1)).change_count, 0);
+
+        let (_, history) = refine_through(dword(1), &[2, 2, 3]);
+        assert_eq!(history.change_count, 2);
+    }
+
+    #[test]
+    fn test_changed_at_least_uses_stored_counter() {
+        let (item, history) = refine_through(dword(1), &[2, 3]);
+        let matches = |value: i32, times: u32| {
+            item.matches_condition_history(&value.to_le_bytes(), FuzzyCondition::ChangedAtLeast(times), FuzzyCompareOptions::default(), Some(&history))
+        };
+
+        // 本次变化也计入
+        assert!(matches(4, 3));
+        assert!(!matches(3, 3));
+        assert!(matches(3, 2));
+        // 没有历史信息时只计入本次变化
+        assert!(!item.matches_condition(&4i32.to_le_bytes(), FuzzyCondition::ChangedAtLeast(2)));
+        assert!(item.matches_condition(&4i32.to_le_bytes(), FuzzyCondition::ChangedAtLeast(1)));
+
+        let old_float = float(1.0);
+        let float_item = old_float.with_new_value(&1.5f32.to_le_bytes());
+        let float_history = FuzzyItemHistory::new(&old_float).advance(&old_float, &float_item);
+        assert!(float_item.matches_condition_history(&2.0f32.to_le_bytes(), FuzzyCondition::ChangedAtLeast(2), opts(true, false), Some(&float_history)));
+        assert_eq!(FuzzyCondition::from_id(13, 5, 0), Some(FuzzyCondition::ChangedAtLeast(5)));
+    }
+
+    #[test]
+    fn test_float_nan_transitions() {
+        let nan = f32::NAN.to_le_bytes();
+
+        // 变为 NaN
+        let old = float(1.0);
+        assert!(old.matches_condition(&nan, FuzzyCondition::Changed));
+        assert!(!old.matches_condition(&nan, FuzzyCondition::Unchanged));
+        assert!(!old.matches_condition(&nan, FuzzyCondition::Increased));
+        assert!(!old.matches_condition(&nan, FuzzyCondition::Decreased));
+        assert!(!old.matches_condition(&nan, FuzzyCondition::Between(-100, 100)));
+
+        // 从 NaN 恢复
+        let old = float(f32::NAN);
+        assert!(old.matches_condition(&1.0f32.to_le_bytes(), FuzzyCondition::Changed));
+        assert!(!old.matches_condition(&1.0f32.to_le_bytes(), FuzzyCondition::Unchanged));
+        assert!(!old.matches_condition(&1.0f32.to_le_bytes(), FuzzyCondition::IncreasedBy(1)));
+
+        // 保持 NaN
+        assert!(old.matches_condition(&nan, FuzzyCondition::Unchanged));
+        assert!(!old.matches_condition(&nan, FuzzyCondition::Changed));
+    }
+
+    #[test]
+    fn test_float_infinity_unchanged() {
+        let inf = f32::INFINITY.to_le_bytes();
+        assert!(float(f32::INFINITY).matches_condition(&inf, FuzzyCondition::Unchanged));
+        assert!(!float(f32::INFINITY).matches_condition(&inf, FuzzyCondition::Changed));
+        assert!(float(1.0).matches_condition(&inf, FuzzyCondition::Increased));
+    }
+
+    #[test]
+    fn test_decode_same_bytes_both_orders() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let decode = |value_type, order| FuzzySearchResultItem::from_bytes_ordered(0x1000, &bytes, value_type, order);
+
+        assert_eq!(decode(ValueType::Qword, ByteOrder::Little).as_i64(), 0x0807060504030201);
+        assert_eq!(decode(ValueType::Qword, ByteOrder::Big).as_i64(), 0x0102030405060708);
+        assert_eq!(decode(ValueType::Dword, ByteOrder::Little).as_i64(), 0x04030201);
+        assert_eq!(decode(ValueType::Dword, ByteOrder::Big).as_i64(), 0x01020304);
+        assert_eq!(decode(ValueType::Word, ByteOrder::Big).as_i64(), 0x0102);
+        assert_eq!(decode(ValueType::Byte, ByteOrder::Big).as_i64(), 0x01);
+
+        let double = 1.5f64;
+        let be = FuzzySearchResultItem::from_bytes_ordered(0x1000, &double.to_be_bytes(), ValueType::Double, ByteOrder::Big);
+        assert_eq!(be.as_f64(), 1.5);
+        assert_ne!(FuzzySearchResultItem::from_bytes(0x1000, &double.to_be_bytes(), ValueType::Double).as_f64(), 1.5);
+        let float = FuzzySearchResultItem::from_bytes_ordered(0x1000, &2.5f32.to_be_bytes(), ValueType::Float, ByteOrder::Big);
+        assert_eq!(float.as_f64(), 2.5);
+    }
+
+    #[test]
+    fn test_big_endian_refine() {
+        let old = FuzzySearchResultItem::from_bytes_ordered(0x1000, &100i32.to_be_bytes(), ValueType::Dword, ByteOrder::Big);
+        let new_bytes = 105i32.to_be_bytes();
+
+        assert!(old.matches_condition_opts(&new_bytes, FuzzyCondition::IncreasedBy(5), FuzzyCompareOptions { byte_order: ByteOrder::Big, ..Default::default() }));
+        assert!(!old.matches_condition(&new_bytes, FuzzyCondition::IncreasedBy(5)));
+        let updated = old.with_new_value_ordered(&new_bytes, ByteOrder::Big);
+        assert_eq!(updated.as_i64(), 105);
+        assert_eq!(FuzzyItemHistory::new(&old).advance(&old, &updated).prev_as_i64(ValueType::Dword), 100);
+    }
+
+    #[test]
+    fn test_reinterpret_scanned_bytes_as_big_endian() {
+        // 扫描路径按原始字节构造结果项，入库前再按目标字节序解码
+        let raw = FuzzySearchResultItem::from_bytes(0x1000, &0x1234_5678u32.to_be_bytes(), ValueType::Dword);
+        assert_eq!(raw.reinterpret_order(ByteOrder::Big).as_i64(), 0x1234_5678);
+        assert_eq!(raw.reinterpret_order(ByteOrder::Little).as_i64(), raw.as_i64());
+
+        let raw = FuzzySearchResultItem::from_bytes(0x1000, &1.5f64.to_be_bytes(), ValueType::Double);
+        assert_eq!(raw.reinterpret_order(ByteOrder::Big).as_f64(), 1.5);
+        let raw = FuzzySearchResultItem::from_bytes(0x1000, &[0xAB], ValueType::Byte);
+        assert_eq!(raw.reinterpret_order(ByteOrder::Big).as_i64(), raw.as_i64());
+    }
+
+    #[test]
+    fn test_equals_now() {
+        // 与旧值无关，只比较新读取的值
+        let old = dword(120);
+        assert!(old.matches_condition(&95i32.to_le_bytes(), FuzzyCondition::EqualsNow(95)));
+        assert!(!old.matches_condition(&96i32.to_le_bytes(), FuzzyCondition::EqualsNow(95)));
+        assert!(dword(95).matches_condition(&95i32.to_le_bytes(), FuzzyCondition::EqualsNow(95)));
+        assert!(old.matches_condition_opts(&0xFFFF_FFF0u32.to_le_bytes(), FuzzyCondition::EqualsNow(0xFFFF_FFF0), opts(true, false)));
+
+        let old = float(1.0);
+        assert!(old.matches_condition(&0.1f32.to_le_bytes(), FuzzyCondition::EqualsNowFloat(0.1)));
+        assert!(!old.matches_condition(&0.1001f32.to_le_bytes(), FuzzyCondition::EqualsNowFloat(0.1)));
+        assert!(old.matches_condition(&95.0f32.to_le_bytes(), FuzzyCondition::EqualsNow(95)));
+        assert!(!old.matches_condition(&f32::NAN.to_le_bytes(), FuzzyCondition::EqualsNowFloat(0.1)));
+
+        assert_eq!(FuzzyCondition::from_id(16, 95, 0), Some(FuzzyCondition::EqualsNow(95)));
+        assert_eq!(
+            FuzzyCondition::from_id(17, 0.5f64.to_bits() as i64, 0),
+            Some(FuzzyCondition::EqualsNowFloat(0.5))
+        );
+    }
+}
@@ -4,4 +4,6 @@ pub mod mock_memory;
 pub mod single_search_tests;
 pub mod group_search_tests;
 pub mod refine_search_tests;
-pub mod deep_search_tests;
\ No newline at end of file
+pub mod deep_search_tests;
+pub mod result_manager_tests;
+pub mod fuzzy_condition_tests;
@@ -0,0 +1,1656 @@
+//! Result manager tests
+
+#[cfg(test)]
+mod tests {
+    use crate::search::{ByteOrder, FuzzyCompareOptions, FuzzyCondition, SearchResultItem, ValueType};
+    use crate::search::result_manager::{
+        ExactSearchResultItem, FuzzyItemHistory, FuzzySearchResultItem, FuzzySearchResultManager, SearchResultManager, SearchResultManagerConfig,
+        SearchResultMode,
+    };
+    use std::mem::size_of;
+    use std::path::PathBuf;
+
+    /// 为每个测试创建独立的缓存目录，避免磁盘文件互相覆盖
+    fn test_cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mamu_result_manager_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// 创建内存缓冲区只能容纳 `memory_items` 项的模糊结果管理器，其余结果写入磁盘
+    fn fuzzy_manager(name: &str, memory_items: usize) -> FuzzySearchResultManager {
+        FuzzySearchResultManager::new(memory_items * size_of::<FuzzySearchResultItem>(), test_cache_dir(name))
+    }
+
+    fn dword_item(address: u64, value: i32) -> FuzzySearchResultItem {
+        FuzzySearchResultItem::from_bytes(address, &value.to_le_bytes(), ValueType::Dword)
+    }
+
+    /// 磁盘文件头中记录的结果区偏移
+    fn disk_data_offset(path: &std::path::Path) -> usize {
+        use std::os::unix::fs::FileExt;
+
+        let mut offset = [0u8; 8];
+        std::fs::File::open(path).unwrap().read_exact_at(&mut offset, 24).unwrap();
+        u64::from_le_bytes(offset) as usize
+    }
+
+    #[test]
+    fn test_fuzzy_count_where_over_threshold() {
+        let mut manager = fuzzy_manager("count_where", 4);
+        for i in 0..10 {
+            manager.add_result(dword_item(0x1000 + i * 4, i as i32 * 10)).unwrap();
+        }
+        assert_eq!(manager.memory_count(), 4);
+        assert_eq!(manager.disk_count(), 6);
+
+        // 值 > 35 的项：40, 50, 60, 70, 80, 90（全部位于磁盘）
+        assert_eq!(manager.count_where(|item| item.as_i64() > 35), 6);
+        // 值 > 15 的项跨越内存与磁盘：20, 30 + 40..90
+        assert_eq!(manager.count_where(|item| item.as_i64() > 15), 8);
+        assert_eq!(manager.count_where(|_| true), manager.total_count());
+        assert_eq!(manager.count_where(|item| item.as_i64() > 1000), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_reinterpret_value_type() {
+        let mut manager = fuzzy_manager("reinterpret", 2);
+        let bits = 1.5f32.to_bits() as i32;
+        for i in 0..5 {
+            manager.add_result(dword_item(0x1000 + i * 4, bits)).unwrap();
+        }
+        assert_eq!(manager.disk_count(), 3);
+
+        manager.reinterpret_value_type(ValueType::Float).unwrap();
+        assert_eq!(manager.total_count(), 5);
+        for item in manager.iter() {
+            assert_eq!({ item.value_type }, ValueType::Float);
+            assert_eq!(item.as_f64(), 1.5);
+        }
+        assert!(manager.get_results(4, 1).unwrap()[0].matches_condition(&1.5f32.to_le_bytes(), FuzzyCondition::Unchanged));
+
+        // 改为更窄的类型读取低位字节
+        manager.reinterpret_value_type(ValueType::Word).unwrap();
+        assert_eq!(manager.get_results(0, 1).unwrap()[0].as_u64(), (bits as u32 & 0xFFFF) as u64);
+
+        assert!(manager.reinterpret_value_type(ValueType::String).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_refine_in_place() {
+        let mut manager = fuzzy_manager("refine_in_place", 2);
+        manager.set_history_tracking(true);
+        for i in 0..5 {
+            manager.add_result(dword_item(0x1000 + i * 4, 100)).unwrap();
+        }
+
+        // 索引 1 和 3（后者在磁盘上）增加
+        let fresh: Vec<u8> = [100i64, 110, 90, 120, 100].iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(manager.refine_in_place(&fresh, FuzzyCondition::Increased).unwrap(), 2);
+
+        let items = manager.get_all_results().unwrap();
+        assert_eq!(items.iter().map(|item| item.address).collect::<Vec<_>>(), vec![0x1004, 0x100c]);
+        assert_eq!(items[1].as_i64(), 120);
+        let history = manager.history(1).unwrap();
+        assert_eq!(history.prev_as_i64(ValueType::Dword), 100);
+        assert_eq!(history.change_count, 1);
+
+        assert!(manager.refine_in_place(&fresh, FuzzyCondition::Increased).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_refine_in_place_matches_refine_options() {
+        let old_values = [0x7FFF_FFFFu32, 0xFFFF_FFFF, 5, 0x8000_0000, 10];
+        let new_values = [0x8000_0000u32, 0, 6, 0x7FFF_FFFF, 10];
+        let conditions = [FuzzyCondition::Increased, FuzzyCondition::IncreasedBy(1), FuzzyCondition::DecreasedBy(1), FuzzyCondition::Unchanged];
+
+        for (unsigned, wrapping) in [(false, false), (true, false), (false, true), (true, true)] {
+            let options = FuzzyCompareOptions { unsigned, wrapping, ..Default::default() };
+            for condition in conditions {
+                let mut manager = fuzzy_manager("refine_in_place_options", 2);
+                manager.set_compare_options(options);
+                for (i, value) in old_values.iter().enumerate() {
+                    let item = FuzzySearchResultItem::from_bytes(0x1000 + i as u64 * 4, &value.to_le_bytes(), ValueType::Dword);
+                    manager.add_result(item).unwrap();
+                }
+
+                let snapshot: Vec<(u64, [u8; 8])> = new_values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| (0x1000 + i as u64 * 4, (*value as u64).to_le_bytes()))
+                    .collect();
+                let expected: Vec<(u64, i64)> = manager.refine(&snapshot, condition).iter().map(|item| (item.address, item.as_i64())).collect();
+
+                let fresh: Vec<u8> = snapshot.iter().flat_map(|(_, bytes)| *bytes).collect();
+                manager.refine_in_place(&fresh, condition).unwrap();
+                let actual: Vec<(u64, i64)> = manager.get_all_results().unwrap().iter().map(|item| (item.address, item.as_i64())).collect();
+                assert_eq!(actual, expected, "{:?} with {:?}", condition, options);
+            }
+        }
+
+        // 选项确实生效：无符号时 0x7FFFFFFF -> 0x80000000 视为增加
+        let mut manager = fuzzy_manager("refine_in_place_unsigned", 2);
+        manager.set_compare_options(FuzzyCompareOptions { unsigned: true, ..Default::default() });
+        manager.add_result(FuzzySearchResultItem::from_bytes(0x1000, &0x7FFF_FFFFu32.to_le_bytes(), ValueType::Dword)).unwrap();
+        assert_eq!(manager.refine_in_place(&0x8000_0000u64.to_le_bytes(), FuzzyCondition::Increased).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_fuzzy_contiguous_runs() {
+        let mut manager = fuzzy_manager("contiguous_runs", 2);
+        for address in [0x1000, 0x1004, 0x1008, 0x1010, 0x1018, 0x101c] {
+            manager.add_result(dword_item(address, 0)).unwrap();
+        }
+
+        assert_eq!(manager.contiguous_runs(0).unwrap(), vec![(0x1000, 3), (0x1010, 1), (0x1018, 2)]);
+        assert_eq!(manager.contiguous_runs(8).unwrap(), vec![(0x1000, 1), (0x1004, 1), (0x1008, 3), (0x101c, 1)]);
+        assert!(fuzzy_manager("contiguous_runs_empty", 2).contiguous_runs(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_value_histogram() {
+        let mut manager = fuzzy_manager("value_histogram", 4);
+        assert!(manager.value_histogram(4).unwrap().is_empty());
+        assert!(manager.value_histogram(0).is_err());
+
+        for i in 0..10 {
+            manager.add_result(dword_item(0x1000 + i * 4, i as i32 * 10)).unwrap();
+        }
+
+        // 0..=90 分为 3 桶：[0, 30) [30, 60) [60, 90]
+        let histogram = manager.value_histogram(3).unwrap();
+        assert_eq!(histogram, vec![(0.0, 30.0, 3), (30.0, 60.0, 3), (60.0, 90.0, 4)]);
+    }
+
+    #[test]
+    fn test_fuzzy_value_histogram_all_equal() {
+        let mut manager = fuzzy_manager("value_histogram_equal", 2);
+        for i in 0..5 {
+            manager.add_result(dword_item(0x1000 + i * 4, 42)).unwrap();
+        }
+
+        assert_eq!(manager.value_histogram(8).unwrap(), vec![(42.0, 42.0, 5)]);
+    }
+
+    #[test]
+    fn test_fuzzy_item_previous_value() {
+        let item = dword_item(0x1000, 100);
+        let history = FuzzyItemHistory::new(&item);
+        assert_eq!(history.prev_as_i64(ValueType::Dword), 100);
+
+        let refined = item.with_new_value(&95i32.to_le_bytes());
+        let history = history.advance(&item, &refined);
+        assert_eq!(refined.as_i64(), 95);
+        assert_eq!(history.prev_as_i64(ValueType::Dword), 100);
+
+        let refined_again = refined.with_new_value(&90i32.to_le_bytes());
+        assert_eq!(history.advance(&refined, &refined_again).prev_as_i64(ValueType::Dword), 95);
+
+        let float = FuzzySearchResultItem::from_bytes(0x2000, &1.5f32.to_le_bytes(), ValueType::Float);
+        let refined_float = float.with_new_value(&2.5f32.to_le_bytes());
+        assert_eq!(refined_float.as_f64(), 2.5);
+        assert_eq!(FuzzyItemHistory::new(&float).advance(&float, &refined_float).prev_as_f64(ValueType::Float), 1.5);
+    }
+
+    #[test]
+    fn test_fuzzy_history_is_optional() {
+        assert_eq!(size_of::<FuzzySearchResultItem>(), 17);
+
+        let mut manager = fuzzy_manager("history_optional", 2);
+        for i in 0..3 {
+            manager.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        assert!(manager.history(0).is_none());
+        assert_eq!(manager.memory_bytes(), 2 * size_of::<FuzzySearchResultItem>());
+
+        // 开启后以当前值初始化，更新与删除保持与结果索引对齐
+        manager.set_history_tracking(true);
+        assert_eq!(manager.history(2).unwrap().prev_as_i64(ValueType::Dword), 2);
+        manager.update_result(2, dword_item(0x1008, 7)).unwrap();
+        manager.remove_result(0).unwrap();
+        let history = manager.history(1).unwrap();
+        assert_eq!((history.prev_as_i64(ValueType::Dword), history.change_count), (2, 1));
+        assert!(manager.history(2).is_none());
+
+        manager.set_history_tracking(false);
+        assert!(manager.history(1).is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_iter_and_iter_range() {
+        let mut manager = fuzzy_manager("iter", 3);
+        for i in 0..8u64 {
+            manager.add_result(dword_item(0x6000 + i * 4, i as i32)).unwrap();
+        }
+
+        let all: Vec<i64> = manager.iter().map(|item| item.as_i64()).collect();
+        assert_eq!(all, (0..8).collect::<Vec<i64>>());
+        let expected: Vec<i64> = manager.get_all_results().unwrap().iter().map(|item| item.as_i64()).collect();
+        assert_eq!(all, expected);
+
+        // 跨越内存与磁盘边界的窗口
+        let window: Vec<i64> = manager.iter_range(2, 5).map(|item| item.as_i64()).collect();
+        assert_eq!(window, vec![2, 3, 4]);
+        // 仅磁盘部分
+        let disk: Vec<i64> = manager.iter_range(6, 100).map(|item| item.as_i64()).collect();
+        assert_eq!(disk, vec![6, 7]);
+        assert_eq!(manager.iter_range(5, 2).count(), 0);
+        assert_eq!(manager.iter_range(100, 200).count(), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_find_byte_pattern_in_disk_file() {
+        let mut manager = fuzzy_manager("find_byte_pattern", 0);
+        manager.add_result(dword_item(0x1000, 0x11223344)).unwrap();
+        manager.add_result(dword_item(0x1004, 0x55667788)).unwrap();
+
+        let item_size = size_of::<FuzzySearchResultItem>();
+        // 值字段紧跟 8 字节地址之后
+        assert_eq!(manager.find_byte_pattern(&0x55667788u32.to_le_bytes()), vec![item_size + 8]);
+        assert_eq!(manager.find_byte_pattern(&0x1004u64.to_le_bytes()), vec![item_size]);
+        assert!(manager.find_byte_pattern(&[0xde, 0xad, 0xbe, 0xef]).is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_portable_round_trip() {
+        let mut source = fuzzy_manager("portable_src", 2);
+        for i in 0..5 {
+            source.add_result(dword_item(0x7000_0000_0000 + i * 4, -(i as i32))).unwrap();
+        }
+
+        let mut buf = Vec::new();
+        assert_eq!(source.export_portable(&mut buf).unwrap(), 5);
+        // 16 字节头 + 每项 16 字节，与内存中 17 字节的 packed 布局无关
+        assert_eq!(buf.len(), 16 + 5 * 16);
+        assert_eq!(&buf[0..4], b"MMFR");
+        assert_eq!(&buf[16..24], &0x7000_0000_0000u64.to_le_bytes());
+
+        // 目标端使用不同的内存/磁盘划分
+        let mut target = fuzzy_manager("portable_dst", 0);
+        assert_eq!(target.import_portable(&mut buf.as_slice()).unwrap(), 5);
+        let imported: Vec<(u64, i64, ValueType)> =
+            target.get_all_results().unwrap().iter().map(|item| (item.address, item.as_i64(), item.value_type)).collect();
+        let expected: Vec<(u64, i64, ValueType)> = (0..5).map(|i| (0x7000_0000_0000 + i * 4, -(i as i64), ValueType::Dword)).collect();
+        assert_eq!(imported, expected);
+    }
+
+    #[test]
+    fn test_fuzzy_portable_packed_round_trip() {
+        const COUNT: usize = 1_000_000;
+        // 每 1000 项为一段步长 4 的连续地址，段之间留有间隔；值以 7 为周期重复
+        let address = |i: usize| 0x7000_0000_0000 + (i * 4 + (i / 1000) * 0x100) as u64;
+        let mut source = fuzzy_manager("packed_src", 4096);
+        for i in 0..COUNT {
+            source.add_result(dword_item(address(i), (i % 7) as i32 - 3)).unwrap();
+        }
+
+        let mut plain = Vec::new();
+        source.export_portable(&mut plain).unwrap();
+        let mut packed = Vec::new();
+        assert_eq!(source.export_portable_packed(&mut packed).unwrap(), COUNT);
+        assert_eq!(u16::from_le_bytes([packed[4], packed[5]]), 2);
+        // 地址段几乎不占空间，每项只剩 4 字节的值
+        assert!(packed.len() < 16 + COUNT * 4 + 1000 * 8);
+        assert!(packed.len() * 3 < plain.len());
+
+        let mut target = fuzzy_manager("packed_dst", 1024);
+        assert_eq!(target.import_portable(&mut packed.as_slice()).unwrap(), COUNT);
+        assert_eq!(target.total_count(), COUNT);
+        for (i, item) in target.iter().enumerate() {
+            assert_eq!({ item.address }, address(i));
+            assert_eq!(item.as_i64(), (i % 7) as i64 - 3);
+            assert_eq!(item.value_type, ValueType::Dword);
+        }
+
+        // 非等差地址与空结果同样可以还原
+        let mut sparse = fuzzy_manager("packed_sparse", 2);
+        for addr in [0x10u64, 0x18, 0x1000, 0x1001, 0x1010, u64::MAX - 8] {
+            sparse.add_result(FuzzySearchResultItem::from_bytes(addr, &(addr as i64).to_le_bytes(), ValueType::Qword)).unwrap();
+        }
+        let mut buf = Vec::new();
+        sparse.export_portable_packed(&mut buf).unwrap();
+        target.import_portable(&mut buf.as_slice()).unwrap();
+        assert_eq!(target.get_all_results().unwrap(), sparse.get_all_results().unwrap());
+        let values: Vec<i64> = target.iter().map(|item| item.as_i64()).collect();
+        assert_eq!(values, sparse.iter().map(|item| item.as_i64()).collect::<Vec<_>>());
+
+        let mut buf = Vec::new();
+        fuzzy_manager("packed_empty", 2).export_portable_packed(&mut buf).unwrap();
+        assert_eq!(target.import_portable(&mut buf.as_slice()).unwrap(), 0);
+
+        // 段长度超过声明的数量
+        let mut corrupt = packed[..16].to_vec();
+        corrupt[8..16].copy_from_slice(&1u64.to_le_bytes());
+        corrupt.extend_from_slice(&[0x00, 0x04, 0x02]);
+        assert!(target.import_portable(&mut corrupt.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_portable_rejects_invalid_input() {
+        let mut manager = fuzzy_manager("portable_invalid", 4);
+        assert!(manager.import_portable(&mut &b"XXXX\x01\x00\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00"[..]).is_err());
+
+        // 头部声明 1 项但数据被截断
+        let mut truncated = b"MMFR".to_vec();
+        truncated.extend_from_slice(&1u16.to_le_bytes());
+        truncated.extend_from_slice(&[2, 0]);
+        truncated.extend_from_slice(&1u64.to_le_bytes());
+        truncated.extend_from_slice(&[0u8; 8]);
+        assert!(manager.import_portable(&mut truncated.as_slice()).is_err());
+
+        // 混合值类型无法导出
+        manager.clear().unwrap();
+        manager.add_result(dword_item(0x1000, 1)).unwrap();
+        manager.add_result(FuzzySearchResultItem::from_bytes(0x1004, &1.0f32.to_le_bytes(), ValueType::Float)).unwrap();
+        assert!(manager.export_portable(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_big_endian_regions_and_refine() {
+        let mut manager = fuzzy_manager("big_endian", 4).with_byte_order(ByteOrder::Big);
+        assert_eq!(manager.byte_order(), ByteOrder::Big);
+
+        let bytes: Vec<u8> = [10i32, 20, 30].iter().flat_map(|v| v.to_be_bytes()).collect();
+        assert_eq!(manager.add_regions(vec![(0x1000, bytes)], ValueType::Dword, 4).unwrap(), 3);
+        let values: Vec<i64> = manager.iter().map(|item| item.as_i64()).collect();
+        assert_eq!(values, vec![10, 20, 30]);
+
+        let mut new_value = [0u8; 8];
+        new_value[..4].copy_from_slice(&25i32.to_be_bytes());
+        let refined = manager.refine(&[(0x1004, new_value)], FuzzyCondition::IncreasedBy(5));
+        assert_eq!(refined.len(), 1);
+        assert_eq!(refined[0].as_i64(), 25);
+    }
+
+    #[test]
+    fn test_fuzzy_add_regions_parallel_matches_serial() {
+        let regions: Vec<(u64, Vec<u8>)> = (0..16u64)
+            .map(|r| (0x10_0000 + r * 0x1000, (0..256u32).map(|i| (i as u8).wrapping_mul(r as u8 + 1)).collect()))
+            .collect();
+
+        let mut serial = fuzzy_manager("regions_serial", 64);
+        let mut parallel = fuzzy_manager("regions_parallel", 64);
+        let serial_count = serial.add_regions(regions.clone(), ValueType::Dword, 4).unwrap();
+        let parallel_count = parallel.add_regions_parallel(regions, ValueType::Dword, 4).unwrap();
+
+        assert_eq!(serial_count, 16 * 64);
+        assert_eq!(serial_count, parallel_count);
+
+        let to_tuples = |manager: &FuzzySearchResultManager| -> Vec<(u64, i64)> {
+            manager.get_all_results().unwrap().iter().map(|item| (item.address, item.as_i64())).collect()
+        };
+        let serial_items = to_tuples(&serial);
+        assert_eq!(serial_items, to_tuples(&parallel));
+        assert_eq!(serial_items[0].0, 0x10_0000);
+        assert_eq!(serial_items[64].0, 0x10_1000);
+    }
+
+    #[test]
+    fn test_fuzzy_bulk_insert_matches_per_item() {
+        use crate::search::result_manager::DiskSpillConfig;
+
+        // 小步长扩展，逐项写入时会频繁重新映射
+        let config = DiskSpillConfig::geometric(1, 64 * 1024);
+        let items: Vec<FuzzySearchResultItem> = (0..8000u64).map(|i| dword_item(0x10_0000 + i * 4, i as i32)).collect();
+
+        let mut per_item = fuzzy_manager("bulk_per_item", 1000).with_disk_config(config);
+        for item in &items {
+            per_item.add_result(*item).unwrap();
+        }
+
+        let mut bulk = fuzzy_manager("bulk", 1000).with_disk_config(config);
+        // 分两批写入，第一批跨越内存与磁盘的边界
+        bulk.add_results_bulk(&items[..1500]).unwrap();
+        bulk.add_results_bulk(&items[1500..]).unwrap();
+
+        assert_eq!(bulk.memory_count(), 1000);
+        assert_eq!(bulk.total_count(), items.len());
+        let to_tuples = |manager: &FuzzySearchResultManager| -> Vec<(u64, i64)> {
+            manager.iter().map(|item| (item.address, item.as_i64())).collect()
+        };
+        assert_eq!(to_tuples(&bulk), to_tuples(&per_item));
+
+        // 预留后逐项写入不再扩展磁盘文件
+        let mut reserved = fuzzy_manager("bulk_reserve", 10).with_disk_config(DiskSpillConfig::geometric(1, 4096));
+        reserved.reserve(5000).unwrap();
+        let mapped = reserved.mapped_disk_size();
+        assert!(mapped >= 4990 * size_of::<FuzzySearchResultItem>());
+        for item in &items[..5000] {
+            reserved.add_result(*item).unwrap();
+        }
+        assert_eq!(reserved.mapped_disk_size(), mapped);
+
+        // 磁盘文件无法创建时整批失败，历史信息不会与结果错位
+        let missing_dir = test_cache_dir("bulk_failed").join("missing");
+        let mut failed = FuzzySearchResultManager::new(2 * size_of::<FuzzySearchResultItem>(), missing_dir);
+        failed.set_history_tracking(true);
+        assert!(failed.add_results_bulk(&items[..5]).is_err());
+        assert_eq!(failed.total_count(), 0);
+        assert!(failed.history(0).is_none());
+        failed.add_results_bulk(&items[7..9]).unwrap();
+        assert_eq!(failed.history(0).unwrap().prev_as_i64(ValueType::Dword), 7);
+        assert!(failed.history(2).is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_discarding_counts_only() {
+        let mut manager = FuzzySearchResultManager::new(0, test_cache_dir("discarding")).with_discarding(true);
+        assert!(manager.is_discarding());
+        for i in 0..1000 {
+            manager.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+
+        assert_eq!(manager.total_count(), 1000);
+        assert_eq!(manager.memory_count(), 0);
+        assert_eq!(manager.disk_count(), 0);
+        assert_eq!(manager.mapped_disk_size(), 0);
+        assert!(manager.get_results(0, 10).unwrap().is_empty());
+
+        let session = test_cache_dir("discarding").join("count_only.session");
+        assert!(manager.save_session(&session).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_session_round_trip() {
+        let mut source = fuzzy_manager("session_src", 3);
+        for i in 0..8u64 {
+            source.add_result(dword_item(0x4000 + i * 4, i as i32 * 3)).unwrap();
+        }
+        source.set_history_tracking(true);
+        let double = FuzzySearchResultItem::from_bytes(0x5000, &2.5f64.to_le_bytes(), ValueType::Double);
+        let refined = double.with_new_value(&3.5f64.to_le_bytes());
+        source.add_result_with_history(refined, FuzzyItemHistory::new(&double).advance(&double, &refined).with_tag(5)).unwrap();
+        assert!(source.disk_count() > 0);
+
+        let session = test_cache_dir("session_file").join("fuzzy.session");
+        assert_eq!(source.save_session(&session).unwrap(), 9);
+
+        // 模拟进程重启：原管理器销毁后由新的管理器恢复
+        source.destroy().unwrap();
+        let mut restored = fuzzy_manager("session_dst", 4);
+        restored.set_history_tracking(true);
+        assert_eq!(restored.load_session(&session).unwrap(), 9);
+        assert_eq!(restored.total_count(), 9);
+        assert_eq!(restored.memory_count(), 4);
+
+        let items = restored.get_all_results().unwrap();
+        for (i, item) in items.iter().take(8).enumerate() {
+            let address = item.address;
+            assert_eq!(address, 0x4000 + i as u64 * 4);
+            assert_eq!(item.as_i64(), i as i64 * 3);
+        }
+        assert_eq!(items[8].value_type, ValueType::Double);
+        assert_eq!(items[8].as_f64(), 3.5);
+        let history = restored.history(8).unwrap();
+        assert_eq!(history.prev_as_f64(ValueType::Double), 2.5);
+        assert_eq!(history.min_as_f64(ValueType::Double), 2.5);
+        assert_eq!(history.max_as_f64(ValueType::Double), 3.5);
+        assert_eq!(history.change_count, 1);
+        assert_eq!(restored.history(0).unwrap().change_count, 0);
+        assert_eq!(history.tag, 5);
+    }
+
+    #[test]
+    fn test_fuzzy_session_packed_when_single_type() {
+        const COUNT: u64 = 2000;
+        let mut source = fuzzy_manager("session_packed_src", 500);
+        source.set_history_tracking(true);
+        for i in 0..COUNT {
+            source.add_result(dword_item(0x8000 + i * 4, i as i32)).unwrap();
+        }
+        let mut value = [0u8; 8];
+        value[..4].copy_from_slice(&70i32.to_le_bytes());
+        source.update_values_batch(&[(7, value)]).unwrap();
+        assert!(source.disk_count() > 0);
+
+        let dir = test_cache_dir("session_packed");
+        let session = dir.join("packed.session");
+        assert_eq!(source.save_session(&session).unwrap(), COUNT as usize);
+        let bytes = std::fs::read(&session).unwrap();
+        assert_eq!(u16::from_le_bytes([bytes[4], bytes[5]]), 2);
+        // 历史信息每项 30 字节，地址与值远小于逐项记录的每项 47 字节
+        assert!(bytes.len() < 16 + COUNT as usize * (30 + 4) + 64);
+
+        let mut restored = fuzzy_manager("session_packed_dst", 500);
+        restored.set_history_tracking(true);
+        assert_eq!(restored.load_session(&session).unwrap(), COUNT as usize);
+        assert_eq!(restored.get_all_results().unwrap(), source.get_all_results().unwrap());
+        let history = restored.history(7).unwrap();
+        assert_eq!(history.prev_as_i64(ValueType::Dword), 7);
+        assert_eq!(history.change_count, 1);
+
+        // 未开启历史跟踪时只写出紧凑编码的结果区
+        source.set_history_tracking(false);
+        let compact = dir.join("compact.session");
+        source.save_session(&compact).unwrap();
+        assert!(std::fs::metadata(&compact).unwrap().len() < 16 + COUNT * 4 + 64);
+        let mut untracked = fuzzy_manager("session_packed_untracked", 500);
+        assert_eq!(untracked.load_session(&compact).unwrap(), COUNT as usize);
+        assert_eq!(untracked.get_all_results().unwrap(), source.get_all_results().unwrap());
+    }
+
+    #[test]
+    fn test_fuzzy_diff_sessions() {
+        let dir = test_cache_dir("session_diff");
+        let mut old = fuzzy_manager("session_diff_old", 2);
+        for i in 0..6u64 {
+            old.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        let old_path = dir.join("old.session");
+        old.save_session(&old_path).unwrap();
+
+        // 0x1000 消失，0x1008 值变化，0x100c 类型变化，0x1018 和 0x1020 新出现
+        let mut new = fuzzy_manager("session_diff_new", 2);
+        new.add_result(dword_item(0x1004, 1)).unwrap();
+        new.add_result(dword_item(0x1008, 20)).unwrap();
+        new.add_result(FuzzySearchResultItem::from_bytes(0x100c, &3i16.to_le_bytes(), ValueType::Word)).unwrap();
+        new.add_result(dword_item(0x1010, 4)).unwrap();
+        new.add_result(dword_item(0x1014, 5)).unwrap();
+        new.add_result(dword_item(0x1018, 6)).unwrap();
+        new.add_result(dword_item(0x1020, 8)).unwrap();
+        let new_path = dir.join("new.session");
+        new.save_session(&new_path).unwrap();
+
+        let diff = FuzzySearchResultManager::diff_sessions(&old_path, &new_path).unwrap();
+        assert_eq!(diff.appeared, vec![0x1018, 0x1020]);
+        assert_eq!(diff.disappeared, vec![0x1000]);
+        assert_eq!(diff.changed, vec![0x1008, 0x100c]);
+
+        // 反向比较时出现与消失互换
+        let reverse = FuzzySearchResultManager::diff_sessions(&new_path, &old_path).unwrap();
+        assert_eq!(reverse.appeared, diff.disappeared);
+        assert_eq!(reverse.disappeared, diff.appeared);
+        assert!(FuzzySearchResultManager::diff_sessions(&old_path, &old_path).unwrap().changed.is_empty());
+
+        // 地址无序的会话无法归并比较
+        let mut unordered = fuzzy_manager("session_diff_unordered", 4);
+        unordered.add_result(dword_item(0x2000, 0)).unwrap();
+        unordered.add_result(dword_item(0x1000, 0)).unwrap();
+        let unordered_path = dir.join("unordered.session");
+        unordered.save_session(&unordered_path).unwrap();
+        assert!(FuzzySearchResultManager::diff_sessions(&old_path, &unordered_path).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_session_rejects_unknown_version() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"MMFS");
+        bytes.extend_from_slice(&3u16.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+
+        let path = test_cache_dir("session_version").join("unknown.session");
+        std::fs::write(&path, bytes).unwrap();
+        let mut manager = fuzzy_manager("session_version_mgr", 4);
+        assert!(manager.load_session(&path).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_session_rejects_bad_magic() {
+        let path = test_cache_dir("session_bad").join("bad.session");
+        std::fs::write(&path, [0u8; 32]).unwrap();
+        let mut manager = fuzzy_manager("session_bad_mgr", 4);
+        assert!(manager.load_session(&path).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_disk_file_reopen_round_trip() {
+        // 前 4 项在内存缓冲区中，落盘时一并写入磁盘文件
+        let mut source = fuzzy_manager("disk_reopen", 4);
+        for i in 0..10 {
+            source.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        source.flush_disk_file().unwrap();
+        assert_eq!(source.disk_count(), 10);
+        assert_eq!(source.get_all_results().unwrap()[0].as_i64(), 0);
+
+        let mut restored = fuzzy_manager("disk_reopen", 4);
+        assert_eq!(restored.reopen_disk_file(source.disk_file_path().unwrap()).unwrap(), 10);
+        assert_eq!(restored.disk_count(), 10);
+        let items = restored.get_all_results().unwrap();
+        assert_eq!({ items[0].address }, 0x1000);
+        assert_eq!({ items[9].address }, 0x1000 + 9 * 4);
+        assert_eq!(items[9].as_i64(), 9);
+
+        // 重新打开后继续追加的结果排在已有结果之后
+        restored.add_result(dword_item(0x2000, 99)).unwrap();
+        assert_eq!(restored.total_count(), 11);
+        assert_eq!({ restored.get_all_results().unwrap()[10].address }, 0x2000);
+    }
+
+    #[test]
+    fn test_fuzzy_disk_file_reopen_uses_stored_data_offset() {
+        use std::os::unix::fs::FileExt;
+
+        let mut source = fuzzy_manager("disk_offset", 0);
+        for i in 0..10 {
+            source.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        source.flush_disk_file().unwrap();
+
+        // 模拟另一种页大小的设备写入的文件：结果区移到更大的偏移，只改文件头记录的偏移
+        let path = source.disk_file_path().unwrap().to_path_buf();
+        let offset = disk_data_offset(&path);
+        assert_eq!(offset % *crate::search::PAGE_SIZE, 0);
+        let item_size = size_of::<FuzzySearchResultItem>();
+        let file = std::fs::OpenOptions::new().read(true).write(true).open(&path).unwrap();
+        let mut items = vec![0u8; 10 * item_size];
+        file.read_exact_at(&mut items, offset as u64).unwrap();
+        file.write_all_at(&items, (offset * 2) as u64).unwrap();
+        file.write_all_at(&((offset * 2) as u64).to_le_bytes(), 24).unwrap();
+
+        let mut restored = fuzzy_manager("disk_offset", 0);
+        assert_eq!(restored.reopen_disk_file(&path).unwrap(), 10);
+        assert_eq!(restored.get_all_results().unwrap()[9].as_i64(), 9);
+
+        // 与当前页大小不对齐的偏移无法映射
+        file.write_all_at(&((offset + 8) as u64).to_le_bytes(), 24).unwrap();
+        let err = fuzzy_manager("disk_offset", 0).reopen_disk_file(&path).unwrap_err();
+        assert!(err.to_string().contains("data offset"), "{}", err);
+    }
+
+    #[test]
+    fn test_fuzzy_disk_file_reopen_rejects_corruption() {
+        use std::os::unix::fs::FileExt;
+
+        let mut source = fuzzy_manager("disk_corrupt", 0);
+        for i in 0..10 {
+            source.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        source.flush_disk_file().unwrap();
+
+        let path = source.disk_file_path().unwrap().to_path_buf();
+        let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.write_all_at(&[0xFF], (disk_data_offset(&path) + 3) as u64).unwrap();
+
+        let mut restored = fuzzy_manager("disk_corrupt", 4);
+        assert!(restored.reopen_disk_file(&path).is_err());
+        assert_eq!(restored.total_count(), 0);
+
+        // 截断到文件头以内
+        file.set_len(16).unwrap();
+        assert!(restored.reopen_disk_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_disk_file_reopen_rejects_bad_value_type() {
+        use std::os::unix::fs::FileExt;
+
+        let mut source = fuzzy_manager("disk_bad_type", 0);
+        for i in 0..10 {
+            source.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        source.flush_disk_file().unwrap();
+
+        // 把第 3 项的值类型字节改成无效判别值，并重新计算 CRC，使其只能被类型校验发现
+        let item_size = size_of::<FuzzySearchResultItem>();
+        let path = source.disk_file_path().unwrap().to_path_buf();
+        let header_size = disk_data_offset(&path);
+        let file = std::fs::OpenOptions::new().read(true).write(true).open(&path).unwrap();
+        file.write_all_at(&[0xEE], (header_size + 3 * item_size + item_size - 1) as u64).unwrap();
+        let mut items = vec![0u8; 10 * item_size];
+        file.read_exact_at(&mut items, header_size as u64).unwrap();
+        file.write_all_at(&crc32fast::hash(&items).to_le_bytes(), 16).unwrap();
+
+        assert_eq!(ValueType::from_repr(ValueType::Bytes as u8), Some(ValueType::Bytes));
+        assert_eq!(ValueType::from_repr(0xEE), None);
+
+        let mut restored = fuzzy_manager("disk_bad_type", 4);
+        let err = restored.reopen_disk_file(&path).unwrap_err();
+        assert!(err.to_string().contains("invalid value type"), "{}", err);
+        assert_eq!(restored.total_count(), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_disk_files_are_per_manager() {
+        let mut first = fuzzy_manager("disk_per_manager", 0);
+        let mut second = fuzzy_manager("disk_per_manager", 0);
+        first.add_result(dword_item(0x1000, 1)).unwrap();
+        second.add_result(dword_item(0x2000, 2)).unwrap();
+
+        let first_path = first.disk_file_path().unwrap().to_path_buf();
+        let second_path = second.disk_file_path().unwrap().to_path_buf();
+        assert_ne!(first_path, second_path);
+        assert_eq!({ first.get_all_results().unwrap()[0].address }, 0x1000);
+        assert_eq!({ second.get_all_results().unwrap()[0].address }, 0x2000);
+
+        first.clear_disk().unwrap();
+        assert!(!first_path.exists());
+        assert!(second_path.exists());
+        second.destroy().unwrap();
+        assert!(!second_path.exists());
+
+        // 已退出进程遗留的未落盘文件和旧版本的固定文件名在启动清理时删除，
+        // 落盘的文件和本进程的文件保留
+        let cache_dir = test_cache_dir("disk_per_manager");
+        let stale = cache_dir.join(format!("mamu_fuzzy_results_{}_0.bin", u32::MAX));
+        std::fs::write(&stale, [0u8; 4]).unwrap();
+        let legacy = cache_dir.join("mamu_fuzzy_results.bin");
+        std::fs::write(&legacy, [0u8; 4]).unwrap();
+        let mut persisted = fuzzy_manager("disk_per_manager", 0);
+        persisted.add_result(dword_item(0x4000, 4)).unwrap();
+        persisted.flush_disk_file().unwrap();
+        let flushed = cache_dir.join(format!("mamu_fuzzy_results_{}_1.bin", u32::MAX));
+        std::fs::copy(persisted.disk_file_path().unwrap(), &flushed).unwrap();
+        let mut live = fuzzy_manager("disk_per_manager", 0);
+        live.add_result(dword_item(0x3000, 3)).unwrap();
+        assert_eq!(FuzzySearchResultManager::remove_stale_disk_files(&cache_dir).unwrap(), 2);
+        assert!(!stale.exists());
+        assert!(!legacy.exists());
+        assert!(flushed.exists());
+        assert!(live.disk_file_path().unwrap().exists());
+
+        let mut restored = fuzzy_manager("disk_per_manager", 0);
+        assert_eq!(restored.reopen_disk_file(&flushed).unwrap(), 1);
+        restored.destroy().unwrap();
+        assert!(!flushed.exists());
+    }
+
+    #[test]
+    fn test_fuzzy_disk_file_reopen_requires_flush() {
+        let mut source = fuzzy_manager("disk_unflushed", 0);
+        for i in 0..4 {
+            source.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        let path = source.disk_file_path().unwrap().to_path_buf();
+
+        // 未调用 flush_disk_file 的文件没有落盘标记
+        let mut restored = fuzzy_manager("disk_unflushed", 4);
+        let err = restored.reopen_disk_file(&path).unwrap_err();
+        assert!(err.to_string().contains("not flushed"), "{}", err);
+
+        // 落盘之后再修改结果会清除落盘标记
+        source.flush_disk_file().unwrap();
+        assert_eq!(fuzzy_manager("disk_unflushed", 0).reopen_disk_file(&path).unwrap(), 4);
+        source.remove_result(0).unwrap();
+        assert!(fuzzy_manager("disk_unflushed", 0).reopen_disk_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_refine_parallel_matches_sequential() {
+        use crate::search::FuzzyCondition;
+
+        let mut manager = fuzzy_manager("refine_parallel", 500);
+        for i in 0..2000u64 {
+            manager.add_result(dword_item(0x10000 + i * 4, i as i32)).unwrap();
+        }
+
+        // 每 3 项增大、每 5 项减小，每 7 项读取失败（缺失）
+        let new_values: Vec<(u64, [u8; 8])> = (0..2000u64)
+            .filter(|i| i % 7 != 0)
+            .map(|i| {
+                let value = if i % 3 == 0 { i as i32 + 10 } else if i % 5 == 0 { i as i32 - 1 } else { i as i32 };
+                let mut bytes = [0u8; 8];
+                bytes[..4].copy_from_slice(&value.to_le_bytes());
+                (0x10000 + i * 4, bytes)
+            })
+            .collect();
+
+        for condition in [FuzzyCondition::Increased, FuzzyCondition::Decreased, FuzzyCondition::Unchanged, FuzzyCondition::IncreasedBy(10)] {
+            let sequential = manager.refine(&new_values, condition);
+            let parallel = manager.refine_parallel(&new_values, condition);
+            let to_tuples = |items: &[FuzzySearchResultItem]| -> Vec<(u64, i64)> {
+                items.iter().map(|item| (item.address, item.as_i64())).collect()
+            };
+            assert!(!sequential.is_empty());
+            assert_eq!(to_tuples(&sequential), to_tuples(&parallel));
+        }
+
+        let increased = manager.refine_parallel(&new_values, FuzzyCondition::Increased);
+        let expected = (0..2000u64).filter(|i| i % 7 != 0 && i % 3 == 0).count();
+        assert_eq!(increased.len(), expected);
+        assert_eq!(increased[0].as_i64(), 13);
+    }
+
+    #[test]
+    fn test_fuzzy_compact_disk_file() {
+        // 没有磁盘文件时 compact 为空操作
+        let mut empty = fuzzy_manager("compact_empty", 4);
+        empty.compact().unwrap();
+
+        let mut manager = fuzzy_manager("compact", 2);
+        for i in 0..1000u64 {
+            manager.add_result(dword_item(0x8000 + i * 4, i as i32)).unwrap();
+        }
+        let file_path = manager.disk_file_path().unwrap().to_path_buf();
+        let grown_size = std::fs::metadata(&file_path).unwrap().len();
+
+        manager.keep_only_results(vec![0, 1, 500, 999]).unwrap();
+        manager.compact().unwrap();
+
+        let compacted_size = std::fs::metadata(&file_path).unwrap().len();
+        assert!(compacted_size < grown_size);
+        assert!(compacted_size >= (manager.disk_count() * size_of::<FuzzySearchResultItem>()) as u64);
+
+        let values: Vec<i64> = manager.iter().map(|item| item.as_i64()).collect();
+        assert_eq!(values, vec![0, 1, 500, 999]);
+
+        // 收缩后仍可继续写入磁盘
+        for i in 0..500u64 {
+            manager.add_result(dword_item(0x10000 + i * 4, -1)).unwrap();
+        }
+        assert_eq!(manager.total_count(), 504);
+        assert_eq!(manager.iter().nth(3).unwrap().as_i64(), 999);
+    }
+
+    #[test]
+    fn test_fuzzy_keep_unique_values() {
+        let mut manager = fuzzy_manager("keep_unique", 3);
+        let values = [1, 2, 2, 3, 4, 4, 4, 5];
+        for (i, value) in values.iter().enumerate() {
+            manager.add_result(dword_item(0x2000 + i as u64 * 4, *value)).unwrap();
+        }
+
+        assert_eq!(manager.keep_unique_values().unwrap(), 3);
+        assert_eq!(manager.total_count(), 3);
+
+        let kept: Vec<(u64, i64)> = manager.get_all_results().unwrap().iter().map(|item| (item.address, item.as_i64())).collect();
+        assert_eq!(kept, vec![(0x2000, 1), (0x200C, 3), (0x201C, 5)]);
+    }
+
+    #[test]
+    fn test_fuzzy_keep_unique_values_float_epsilon() {
+        let mut manager = fuzzy_manager("keep_unique_float", 16);
+        let values = [1.5f32, 1.5000001, 2.25, -0.0, 0.0, 7.0];
+        for (i, value) in values.iter().enumerate() {
+            let item = FuzzySearchResultItem::from_bytes(0x3000 + i as u64 * 4, &value.to_le_bytes(), ValueType::Float);
+            manager.add_result(item).unwrap();
+        }
+
+        // 1.5 与 1.5000001 落入同一分桶，-0.0 与 0.0 视为相同
+        assert_eq!(manager.keep_unique_values().unwrap(), 2);
+        let kept: Vec<f64> = manager.get_all_results().unwrap().iter().map(|item| item.as_f64()).collect();
+        assert_eq!(kept, vec![2.25, 7.0]);
+    }
+
+    #[test]
+    fn test_fuzzy_usage_bytes_count_live_items() {
+        let mut manager = fuzzy_manager("usage_bytes", 4);
+        assert_eq!(manager.memory_bytes(), 0);
+        assert_eq!(manager.disk_bytes(), 0);
+
+        for i in 0..10 {
+            manager.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        let item_size = size_of::<FuzzySearchResultItem>();
+        assert_eq!(manager.memory_bytes(), 4 * item_size);
+        // 只统计磁盘上 6 个存活结果，而不是预分配的 mmap 大小
+        assert_eq!(manager.disk_bytes(), 6 * item_size);
+
+        manager.remove_result(9).unwrap();
+        assert_eq!(manager.disk_bytes(), 5 * item_size);
+    }
+
+    #[test]
+    fn test_result_manager_usage_stats() {
+        let mut manager = SearchResultManager::new(
+            4 * size_of::<FuzzySearchResultItem>(),
+            test_cache_dir("usage_stats"),
+        );
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        for i in 0..10 {
+            manager.add_fuzzy_result(dword_item(0x2000 + i * 4, i as i32)).unwrap();
+        }
+
+        let stats = manager.usage_stats();
+        let item_size = size_of::<FuzzySearchResultItem>();
+        assert_eq!(stats.mode, SearchResultMode::Fuzzy);
+        assert_eq!(stats.total_count, 10);
+        assert_eq!((stats.memory_count, stats.disk_count), (4, 6));
+        assert_eq!(stats.memory_bytes, 4 * item_size);
+        assert_eq!(stats.disk_bytes, 6 * item_size);
+        assert_eq!(stats.total_bytes(), 10 * item_size);
+
+        manager.clear().unwrap();
+        assert_eq!(manager.usage_stats().total_bytes(), 0);
+    }
+
+    #[test]
+    fn test_result_manager_from_config() {
+        let config = SearchResultManagerConfig {
+            exact_memory_budget: 0,
+            fuzzy_memory_budget: 4 * size_of::<FuzzySearchResultItem>(),
+            disk_growth_increment: 64 * 1024,
+            cache_dir: test_cache_dir("from_config"),
+        };
+        let mut manager = SearchResultManager::from_config(config.clone());
+
+        // 精确结果直接写入磁盘
+        for i in 0..3 {
+            manager.add_result(SearchResultItem::new_exact(0x1000 + i * 4, ValueType::Dword)).unwrap();
+        }
+        let stats = manager.usage_stats();
+        assert_eq!((stats.memory_count, stats.disk_count), (0, 3));
+
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        for i in 0..6 {
+            manager.add_fuzzy_result(dword_item(0x2000 + i * 4, i as i32)).unwrap();
+        }
+        let stats = manager.usage_stats();
+        assert_eq!((stats.memory_count, stats.disk_count), (4, 2));
+
+        let direct = SearchResultManagerConfig::new(1024 * 1024, config.cache_dir).direct_disk();
+        assert_eq!((direct.exact_memory_budget, direct.fuzzy_memory_budget), (0, 0));
+    }
+
+    #[test]
+    fn test_update_fuzzy_value() {
+        let mut manager = SearchResultManager::new(2 * size_of::<FuzzySearchResultItem>(), test_cache_dir("update_value"));
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        manager.set_history_tracking(true);
+        for i in 0..4 {
+            manager.add_fuzzy_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+
+        // 磁盘上的项同样可以更新
+        manager.update_fuzzy_value(3, &99i32.to_le_bytes()).unwrap();
+        let item = manager.get_all_fuzzy_results().unwrap()[3];
+        assert_eq!(item.as_i64(), 99);
+        assert_eq!(manager.fuzzy_history(3).unwrap().prev_as_i64(ValueType::Dword), 3);
+
+        assert!(manager.update_fuzzy_value(4, &1i32.to_le_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_tags_keep_and_remove() {
+        let mut manager = SearchResultManager::new(4 * size_of::<FuzzySearchResultItem>(), test_cache_dir("tags"));
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        for (tag, base) in [(1u16, 0x1000u64), (2, 0x2000), (1, 0x3000)] {
+            manager.set_fuzzy_tag(tag);
+            let items = (0..3).map(|i| dword_item(base + i * 4, i as i32)).collect();
+            manager.add_fuzzy_results_batch(items).unwrap();
+        }
+        assert_eq!(manager.total_count(), 9);
+
+        // 更新值后标签保留
+        manager.update_fuzzy_value(3, &7i32.to_le_bytes()).unwrap();
+        assert_eq!(manager.fuzzy_history(3).unwrap().tag, 2);
+
+        assert_eq!(manager.remove_tag(2).unwrap(), 3);
+        assert_eq!(manager.total_count(), 6);
+        assert_eq!(manager.keep_only_tag(3).unwrap(), 0);
+        assert_eq!(manager.total_count(), 0);
+
+        manager.set_mode(SearchResultMode::Exact).unwrap();
+        assert!(manager.keep_only_tag(1).is_err());
+    }
+
+    #[test]
+    fn test_export_csv_fuzzy() {
+        let mut manager = SearchResultManager::new(
+            2 * size_of::<FuzzySearchResultItem>(),
+            test_cache_dir("export_csv"),
+        );
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        manager.add_fuzzy_result(dword_item(0x1000, -1)).unwrap();
+        manager.add_fuzzy_result(dword_item(0x1004, 100)).unwrap();
+        manager
+            .add_fuzzy_result(FuzzySearchResultItem::from_bytes(0x1008, &1.5f32.to_le_bytes(), ValueType::Float))
+            .unwrap();
+
+        let mut out = Vec::new();
+        manager.export_csv(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "address,type,raw,int,float");
+        assert_eq!(lines[1], "0x1000,Dword,ffffffff,-1,-1");
+        assert_eq!(lines[2], "0x1004,Dword,64000000,100,100");
+        assert!(lines[3].starts_with("0x1008,Float,0000c03f,"));
+        assert!(lines[3].ends_with(",1.5"));
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_export_json_exact_and_fuzzy() {
+        let mut manager = SearchResultManager::new(
+            2 * size_of::<FuzzySearchResultItem>(),
+            test_cache_dir("export_json"),
+        );
+        manager.add_result(SearchResultItem::new_exact(0xdead, ValueType::Qword)).unwrap();
+
+        let mut out = Vec::new();
+        manager.export_json(&mut out).unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed, serde_json::json!([{ "address": "0xdead", "type": "Qword" }]));
+
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        for i in 0..4 {
+            manager.add_fuzzy_result(dword_item(0x2000 + i * 4, i as i32)).unwrap();
+        }
+        let mut out = Vec::new();
+        manager.export_json(&mut out).unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let entries = parsed.as_array().unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[3]["address"], "0x200c");
+        assert_eq!(entries[3]["raw"], "03000000");
+        assert_eq!(entries[3]["int"], 3);
+        assert_eq!(entries[3]["float"], 3.0);
+
+        manager.clear().unwrap();
+        let mut out = Vec::new();
+        manager.export_json(&mut out).unwrap();
+        assert_eq!(out, b"[]");
+    }
+
+    #[test]
+    fn test_variable_value_type_ids() {
+        assert_eq!(ValueType::from_id(100), Some(ValueType::String));
+        assert_eq!(ValueType::from_id(102), Some(ValueType::Bytes));
+        assert_eq!(ValueType::String.to_id(), 100);
+        assert_eq!(ValueType::Bytes.to_id(), 102);
+        assert!(ValueType::String.is_variable_length());
+        assert!(!ValueType::Dword.is_variable_length());
+    }
+
+    #[test]
+    fn test_fuzzy_string_inline_round_trip() {
+        let item = FuzzySearchResultItem::from_bytes(0x1000, b"Gold", ValueType::String);
+        assert_eq!(item.value_size(), 4);
+        assert_eq!(item.variable_bytes().as_deref(), Some(&b"Gold"[..]));
+
+        let max_inline = FuzzySearchResultItem::from_bytes(0x1000, b"abcdef", ValueType::Bytes);
+        assert_eq!(max_inline.value_size(), 6);
+        assert_eq!(max_inline.variable_bytes().as_deref(), Some(&b"abcdef"[..]));
+
+        // 长文本只保存长度与哈希，内容需要按地址重新读取
+        let long = FuzzySearchResultItem::from_bytes(0x2000, b"PlayerName", ValueType::String);
+        assert_eq!(long.value_size(), 10);
+        assert_eq!(long.variable_bytes(), None);
+    }
+
+    #[test]
+    fn test_fuzzy_string_changed_detection() {
+        use crate::search::FuzzyCondition;
+
+        let item = FuzzySearchResultItem::from_bytes(0x1000, b"PlayerName", ValueType::String);
+        assert!(item.matches_condition(b"PlayerName", FuzzyCondition::Unchanged));
+        assert!(!item.matches_condition(b"PlayerName", FuzzyCondition::Changed));
+        assert!(item.matches_condition(b"PlayerNamf", FuzzyCondition::Changed));
+
+        let short = FuzzySearchResultItem::from_bytes(0x1000, b"abc", ValueType::String);
+        assert!(short.matches_condition(b"abd", FuzzyCondition::Changed));
+
+        let updated = short.with_new_value(b"xyz");
+        let history = FuzzyItemHistory::new(&short).advance(&short, &updated);
+        assert_eq!(updated.variable_bytes().as_deref(), Some(&b"xyz"[..]));
+        assert_eq!(history.variable_bytes_prev(ValueType::String).as_deref(), Some(&b"abc"[..]));
+    }
+
+    #[test]
+    fn test_fuzzy_string_results_survive_disk() {
+        let mut manager = fuzzy_manager("string_disk", 1);
+        let words: [&[u8]; 3] = [b"hp", b"mana", b"stamina"];
+        for (i, word) in words.iter().enumerate() {
+            manager
+                .add_result(FuzzySearchResultItem::from_bytes(0x3000 + i as u64 * 16, word, ValueType::String))
+                .unwrap();
+        }
+        assert_eq!(manager.disk_count(), 2);
+
+        let items = manager.get_all_results().unwrap();
+        assert_eq!(items[0].variable_bytes().as_deref(), Some(&b"hp"[..]));
+        assert_eq!(items[1].variable_bytes().as_deref(), Some(&b"mana"[..]));
+        assert_eq!(items[2].value_size(), 7);
+        assert_eq!(items[2].value_type, ValueType::String);
+    }
+
+    #[test]
+    fn test_fuzzy_filter_address_range() {
+        let mut manager = fuzzy_manager("filter_range", 3);
+        for i in 0..10 {
+            manager.add_result(dword_item(0x1000 + i * 0x10, i as i32)).unwrap();
+        }
+
+        // 范围跨越内存与磁盘，两端均包含
+        manager.filter_address_range(0x1020, 0x1060).unwrap();
+        assert_eq!(manager.total_count(), 5);
+        let addresses: Vec<u64> = manager.iter().map(|item| item.address).collect();
+        assert_eq!(addresses, vec![0x1020, 0x1030, 0x1040, 0x1050, 0x1060]);
+
+        manager.filter_address_range(0x2000, 0x3000).unwrap();
+        assert_eq!(manager.total_count(), 0);
+        assert!(manager.filter_address_range(0x3000, 0x2000).is_err());
+    }
+
+    #[test]
+    fn test_get_page_clamps_to_last_page() {
+        let mut manager = SearchResultManager::new(4 * 9, test_cache_dir("get_page"));
+        assert_eq!(manager.get_page(3, 4).unwrap().1, 0);
+        assert!(manager.get_page(0, 0).is_err());
+
+        for i in 0..10u64 {
+            manager.add_result(SearchResultItem::new_exact(0x4000 + i * 4, ValueType::Dword)).unwrap();
+        }
+        let address = |item: &SearchResultItem| match item {
+            SearchResultItem::Exact(exact) => exact.address,
+            SearchResultItem::Fuzzy(fuzzy) => fuzzy.address,
+        };
+
+        let (items, total_pages) = manager.get_page(1, 4).unwrap();
+        assert_eq!(total_pages, 3);
+        assert_eq!(items.len(), 4);
+        assert_eq!(address(&items[0]), 0x4010);
+
+        // 超出范围的页码取最后一页
+        let (items, total_pages) = manager.get_page(9, 4).unwrap();
+        assert_eq!(total_pages, 3);
+        assert_eq!(items.iter().map(address).collect::<Vec<_>>(), vec![0x4020, 0x4024]);
+    }
+
+    #[test]
+    fn test_exact_filter_address_range() {
+        let mut manager = SearchResultManager::new(4 * 9, test_cache_dir("exact_filter_range"));
+        for i in 0..10u64 {
+            manager.add_result(SearchResultItem::new_exact(0x4000 + i * 4, ValueType::Dword)).unwrap();
+        }
+
+        manager.filter_address_range(0x4008, 0x4010).unwrap();
+        assert_eq!(manager.total_count(), 3);
+        let addresses: Vec<u64> = manager.get_all_exact_results().unwrap().iter().map(|item| item.address).collect();
+        assert_eq!(addresses, vec![0x4008, 0x400c, 0x4010]);
+    }
+
+    #[test]
+    fn test_intersect_with() {
+        let mut manager = fuzzy_manager("intersect", 3);
+        for i in 0..10 {
+            manager.add_result(dword_item(0x1000 + i * 0x10, i as i32)).unwrap();
+        }
+
+        // 交集跨越内存与磁盘，其它地址中的重复项与多余地址不影响结果
+        manager.intersect_with(&[0x0ff0, 0x1010, 0x1010, 0x1028, 0x1040, 0x1090, 0x2000]).unwrap();
+        let addresses: Vec<u64> = manager.iter().map(|item| item.address).collect();
+        assert_eq!(addresses, vec![0x1010, 0x1040, 0x1090]);
+        assert!(manager.intersect_with(&[0x1090, 0x1010]).is_err());
+        manager.intersect_with(&[]).unwrap();
+        assert_eq!(manager.total_count(), 0);
+
+        let mut manager = SearchResultManager::new(4 * 9, test_cache_dir("exact_intersect"));
+        for i in 0..10u64 {
+            manager.add_result(SearchResultItem::new_exact(0x4000 + i * 4, ValueType::Dword)).unwrap();
+        }
+        manager.intersect_with(&[0x4004, 0x4018, 0x4024]).unwrap();
+        let addresses: Vec<u64> = manager.get_all_exact_results().unwrap().iter().map(|item| item.address).collect();
+        assert_eq!(addresses, vec![0x4004, 0x4018, 0x4024]);
+    }
+
+    #[test]
+    fn test_required_alignment() {
+        let mut manager = SearchResultManager::new(4 * 9, test_cache_dir("required_alignment"));
+        assert!(manager.set_required_alignment(Some(3)).is_err());
+        manager.set_required_alignment(Some(8)).unwrap();
+
+        // Dword 需 4 字节对齐，Byte 不受限制，Qword 需 8 字节对齐
+        let items = vec![
+            SearchResultItem::new_exact(0x4000, ValueType::Dword),
+            SearchResultItem::new_exact(0x4002, ValueType::Dword),
+            SearchResultItem::new_exact(0x4003, ValueType::Byte),
+            SearchResultItem::new_exact(0x4004, ValueType::Dword),
+            SearchResultItem::new_exact(0x4004, ValueType::Qword),
+            SearchResultItem::new_exact(0x4008, ValueType::Qword),
+        ];
+        manager.add_results_batch(items).unwrap();
+        let kept: Vec<(u64, ValueType)> = manager.get_all_exact_results().unwrap().iter().map(|item| (item.address, item.typ)).collect();
+        assert_eq!(
+            kept,
+            vec![(0x4000, ValueType::Dword), (0x4003, ValueType::Byte), (0x4004, ValueType::Dword), (0x4008, ValueType::Qword)]
+        );
+
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        manager.set_required_alignment(Some(2)).unwrap();
+        manager.add_fuzzy_results_batch(vec![dword_item(0x1000, 1), dword_item(0x1001, 2), dword_item(0x1002, 3)]).unwrap();
+        assert_eq!(manager.total_count(), 2);
+
+        manager.set_required_alignment(None).unwrap();
+        manager.add_fuzzy_result(dword_item(0x1003, 4)).unwrap();
+        assert_eq!(manager.total_count(), 3);
+    }
+
+    #[test]
+    fn test_result_cursor() {
+        let mut engine = crate::search::SearchEngineManager::new();
+        assert!(engine.open_result_cursor().is_err());
+
+        let cache_dir = test_cache_dir("result_cursor");
+        engine.init(4 * size_of::<ExactSearchResultItem>(), cache_dir.to_string_lossy().into_owned(), 0).unwrap();
+        let items = (0..10u64).map(|i| SearchResultItem::new_exact(0x4000 + i * 4, ValueType::Dword)).collect();
+        engine.add_results_batch(items).unwrap();
+
+        // 两个游标互不影响，批次跨越内存与磁盘
+        let first = engine.open_result_cursor().unwrap();
+        let second = engine.open_result_cursor().unwrap();
+        assert_ne!(first, second);
+        let mut positions = Vec::new();
+        loop {
+            let batch = engine.cursor_next(first, 3).unwrap();
+            if batch.is_empty() {
+                break;
+            }
+            assert!(batch.len() <= 3);
+            positions.extend(batch.into_iter().map(|(position, _)| position));
+        }
+        assert_eq!(positions, (0..10).collect::<Vec<_>>());
+        assert_eq!(engine.cursor_next(second, 100).unwrap().len(), 10);
+
+        assert!(engine.close_result_cursor(first));
+        assert!(!engine.close_result_cursor(first));
+        assert!(engine.cursor_next(first, 3).is_err());
+    }
+
+    #[test]
+    fn test_result_addresses() {
+        let mut manager = SearchResultManager::new(4 * 3, test_cache_dir("result_addresses"));
+        for i in 0..5u64 {
+            manager.add_result(SearchResultItem::new_exact(0x4000 + i * 4, ValueType::Dword)).unwrap();
+        }
+        assert_eq!(manager.addresses().unwrap(), vec![0x4000, 0x4004, 0x4008, 0x400c, 0x4010]);
+
+        let mut fuzzy = fuzzy_manager("result_addresses_fuzzy", 2);
+        for i in 0..4 {
+            fuzzy.add_result(dword_item(0x1000 + i * 8, i as i32)).unwrap();
+        }
+        assert_eq!(fuzzy.disk_count(), 2);
+        assert_eq!(fuzzy.addresses().unwrap(), vec![0x1000, 0x1008, 0x1010, 0x1018]);
+    }
+
+    #[test]
+    fn test_fuzzy_sorted_by_value() {
+        let mut fuzzy = fuzzy_manager("sorted_by_value", 2);
+        for (i, value) in [30, -5, 30, 7].into_iter().enumerate() {
+            fuzzy.add_result(dword_item(0x1000 + i as u64 * 4, value)).unwrap();
+        }
+        assert_eq!(fuzzy.disk_count(), 2);
+
+        let ascending: Vec<(u64, i64)> = fuzzy.sorted_by_value(false).unwrap().iter().map(|item| ({ item.address }, item.as_i64())).collect();
+        assert_eq!(ascending, vec![(0x1004, -5), (0x100c, 7), (0x1000, 30), (0x1008, 30)]);
+
+        let descending: Vec<usize> = fuzzy.sorted_by_value_indexed(true).unwrap().iter().map(|(index, _)| *index).collect();
+        assert_eq!(descending, vec![0, 2, 3, 1]);
+
+        // 存储顺序不受影响
+        assert_eq!(fuzzy.addresses().unwrap(), vec![0x1000, 0x1004, 0x1008, 0x100c]);
+    }
+
+    #[test]
+    fn test_snapshot_restore_after_refine() {
+        let mut manager = SearchResultManager::new(2 * size_of::<FuzzySearchResultItem>(), test_cache_dir("snapshot_restore"));
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        for i in 0..5 {
+            manager.add_fuzzy_result(dword_item(0x2000 + i * 4, i as i32)).unwrap();
+        }
+
+        let snapshot = manager.snapshot().unwrap();
+        assert_eq!(snapshot.len(), 5);
+
+        manager.keep_only_results(vec![1]).unwrap();
+        assert_eq!(manager.total_count(), 1);
+
+        manager.restore(snapshot.clone()).unwrap();
+        assert_eq!(manager.total_count(), 5);
+        assert_eq!(manager.addresses().unwrap(), vec![0x2000, 0x2004, 0x2008, 0x200c, 0x2010]);
+
+        // 快照所属模式不同时切换回该模式
+        manager.set_mode(SearchResultMode::Exact).unwrap();
+        manager.add_result(SearchResultItem::new_exact(0x9000, ValueType::Dword)).unwrap();
+        manager.restore(snapshot).unwrap();
+        assert_eq!(manager.get_mode(), SearchResultMode::Fuzzy);
+        assert_eq!(manager.total_count(), 5);
+    }
+
+    #[test]
+    fn test_remove_tail_one_at_a_time() {
+        // 逐个删除末尾附近的结果，跨越内存缓冲区（前 4 项）与磁盘的边界
+        let mut exact = SearchResultManager::new(4 * 9, test_cache_dir("remove_tail_exact"));
+        for i in 0..10u64 {
+            exact.add_result(SearchResultItem::new_exact(0x4000 + i * 4, ValueType::Dword)).unwrap();
+        }
+        let mut fuzzy = fuzzy_manager("remove_tail_fuzzy", 4);
+        for i in 0..10u64 {
+            fuzzy.add_result(dword_item(0x4000 + i * 4, i as i32)).unwrap();
+        }
+
+        let mut expected: Vec<u64> = (0..10u64).map(|i| 0x4000 + i * 4).collect();
+        for _ in 0..4 {
+            let last = expected.len() - 1;
+            expected.remove(last);
+            exact.remove_result(last).unwrap();
+            fuzzy.remove_result(last).unwrap();
+        }
+        // 倒数第二项
+        let index = expected.len() - 2;
+        expected.remove(index);
+        exact.remove_result(index).unwrap();
+        fuzzy.remove_result(index).unwrap();
+        for _ in 0..3 {
+            let last = expected.len() - 1;
+            expected.remove(last);
+            exact.remove_result(last).unwrap();
+            fuzzy.remove_result(last).unwrap();
+        }
+
+        let exact_addresses: Vec<u64> = exact.get_all_exact_results().unwrap().iter().map(|item| item.address).collect();
+        assert_eq!(exact_addresses, expected);
+        assert_eq!(fuzzy.addresses().unwrap(), expected);
+        assert_eq!(fuzzy.memory_count(), 2);
+        assert_eq!(fuzzy.disk_count(), 0);
+        assert!(exact.remove_result(expected.len()).is_err());
+    }
+
+    #[test]
+    fn test_exact_shrink_to_fit() {
+        use crate::search::PAGE_SIZE;
+
+        let cache_dir = test_cache_dir("exact_shrink");
+        let disk_path = cache_dir.join("mamu_search_results.bin");
+        let mut manager = SearchResultManager::new(4 * 9, cache_dir);
+        for i in 0..1000u64 {
+            manager.add_result(SearchResultItem::new_exact(0x4000 + i * 4, ValueType::Dword)).unwrap();
+        }
+        let initial_size = std::fs::metadata(&disk_path).unwrap().len();
+
+        // 保留 10 项：4 项在内存，6 项溢出到磁盘
+        manager.keep_only_results((0..10).map(|i| i * 100).collect()).unwrap();
+        manager.shrink_to_fit().unwrap();
+        assert_eq!(std::fs::metadata(&disk_path).unwrap().len(), *PAGE_SIZE as u64);
+        assert!(std::fs::metadata(&disk_path).unwrap().len() < initial_size);
+
+        let addresses: Vec<u64> = manager.get_all_exact_results().unwrap().iter().map(|item| item.address).collect();
+        assert_eq!(addresses, (0..10u64).map(|i| 0x4000 + i * 400).collect::<Vec<_>>());
+
+        // 收缩后仍可继续追加
+        manager.add_result(SearchResultItem::new_exact(0x9000, ValueType::Dword)).unwrap();
+        assert_eq!(manager.total_count(), 11);
+
+        // 磁盘上没有结果时删除磁盘文件
+        manager.keep_only_results(vec![0, 1]).unwrap();
+        manager.shrink_to_fit().unwrap();
+        assert!(!disk_path.exists());
+        assert_eq!(manager.total_count(), 2);
+    }
+
+    #[test]
+    fn test_fuzzy_disk_failure_keeps_counts_consistent() {
+        let cache_dir = std::env::temp_dir().join(format!("mamu_result_manager_missing_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        let mut manager = FuzzySearchResultManager::new(2 * size_of::<FuzzySearchResultItem>(), cache_dir.clone());
+
+        manager.add_result(dword_item(0x1000, 1)).unwrap();
+        manager.add_result(dword_item(0x1004, 2)).unwrap();
+        // 缓存目录不存在，溢出到磁盘失败时应返回错误且不计入结果
+        assert!(manager.add_result(dword_item(0x1008, 3)).is_err());
+        assert_eq!(manager.total_count(), 2);
+        assert_eq!(manager.disk_count(), 0);
+        assert_eq!(manager.get_all_results().unwrap().len(), 2);
+
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        manager.add_result(dword_item(0x1008, 3)).unwrap();
+        assert_eq!(manager.total_count(), 3);
+        assert_eq!(manager.disk_count(), 1);
+        let _ = std::fs::remove_dir_all(&cache_dir);
+    }
+
+    #[test]
+    fn test_fuzzy_disk_spill_config() {
+        use crate::search::PAGE_SIZE;
+        use crate::search::result_manager::DiskSpillConfig;
+
+        let page = *PAGE_SIZE;
+        let mut manager = fuzzy_manager("disk_spill_config", 0).with_disk_config(DiskSpillConfig::geometric(1, 4 * page));
+        let item_size = size_of::<FuzzySearchResultItem>();
+
+        manager.add_result(dword_item(0x1000, 0)).unwrap();
+        // 初始大小按页向上取整
+        assert_eq!(manager.mapped_disk_size(), page);
+
+        let mut sizes = vec![manager.mapped_disk_size()];
+        let target = (15 * page) / item_size;
+        for i in 1..target {
+            manager.add_result(dword_item(0x1000 + i as u64 * 4, i as i32)).unwrap();
+            if manager.mapped_disk_size() != *sizes.last().unwrap() {
+                sizes.push(manager.mapped_disk_size());
+            }
+        }
+        // 1 -> 2 -> 4 -> 8 页按倍数增长，之后每次最多扩展 4 页
+        assert_eq!(sizes, vec![page, 2 * page, 4 * page, 8 * page, 12 * page, 16 * page]);
+        assert_eq!(manager.total_count(), target);
+        assert_eq!(manager.get_all_results().unwrap().last().unwrap().as_i64(), target as i64 - 1);
+
+        let default_config = DiskSpillConfig::default();
+        assert_eq!(default_config.initial_size, 128 * 1024 * 1024);
+        assert_eq!(default_config.growth_increment, 128 * 1024 * 1024);
+        assert!(!default_config.geometric);
+    }
+
+    #[test]
+    fn test_result_manager_merge() {
+        let item_size = size_of::<FuzzySearchResultItem>();
+        let mut first = SearchResultManager::new(2 * item_size, test_cache_dir("merge_first"));
+        let mut second = SearchResultManager::new(2 * item_size, test_cache_dir("merge_second"));
+        first.set_mode(SearchResultMode::Fuzzy).unwrap();
+        second.set_mode(SearchResultMode::Fuzzy).unwrap();
+
+        for i in [0u64, 2, 4] {
+            first.add_fuzzy_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+        // 与 first 交错，0x1008 两边都有
+        for i in [1u64, 2, 3, 5] {
+            second
+                .add_fuzzy_result(FuzzySearchResultItem::from_bytes(0x1000 + i * 4, &(i as f32).to_le_bytes(), ValueType::Float))
+                .unwrap();
+        }
+
+        first.merge(second).unwrap();
+        // 重叠地址两边的结果都保留
+        assert_eq!(first.total_count(), 3 + 4);
+        let merged = first.get_all_fuzzy_results().unwrap();
+        let addresses: Vec<u64> = merged.iter().map(|item| item.address).collect();
+        assert_eq!(addresses, vec![0x1000, 0x1008, 0x1010, 0x1004, 0x1008, 0x100c, 0x1014]);
+        assert_eq!(merged[1].value_type, ValueType::Dword);
+        assert_eq!(merged[4].value_type, ValueType::Float);
+        assert_eq!(merged[6].as_f64(), 5.0);
+
+        let mut exact_first = SearchResultManager::new(2 * item_size, test_cache_dir("merge_exact_first"));
+        let mut exact_second = SearchResultManager::new(2 * item_size, test_cache_dir("merge_exact_second"));
+        for i in 0..3u64 {
+            exact_first.add_result(SearchResultItem::new_exact(0x2000 + i * 4, ValueType::Dword)).unwrap();
+            exact_second.add_result(SearchResultItem::new_exact(0x2000 + i * 4, ValueType::Float)).unwrap();
+        }
+        exact_first.merge(exact_second).unwrap();
+        assert_eq!(exact_first.total_count(), 6);
+
+        let exact = SearchResultManager::new(2 * item_size, test_cache_dir("merge_exact"));
+        assert!(first.merge(exact).is_err());
+        assert_eq!(first.total_count(), 7);
+    }
+
+    #[test]
+    fn test_result_manager_find_near() {
+        let mut manager = SearchResultManager::new(3 * size_of::<FuzzySearchResultItem>(), test_cache_dir("find_near"));
+        manager.set_mode(SearchResultMode::Fuzzy).unwrap();
+        for i in 0..10 {
+            manager.add_fuzzy_result(dword_item(0x1000 + i * 0x10, i as i32)).unwrap();
+        }
+
+        // 窗口跨越内存缓冲区（前 3 项）与磁盘
+        let near = manager.find_near(0x1025, 0x15).unwrap();
+        let found: Vec<(usize, u64)> = near
+            .iter()
+            .map(|(index, item)| match item {
+                SearchResultItem::Fuzzy(item) => (*index, item.address),
+                SearchResultItem::Exact(_) => unreachable!(),
+            })
+            .collect();
+        assert_eq!(found, vec![(1, 0x1010), (2, 0x1020), (3, 0x1030)]);
+
+        assert_eq!(manager.find_near(0x1000, 0).unwrap().len(), 1);
+        assert_eq!(manager.find_near(0, 0x10).unwrap().len(), 0);
+        assert_eq!(manager.find_near(u64::MAX, u64::MAX).unwrap().len(), 10);
+
+        let mut exact = SearchResultManager::new(2 * 9, test_cache_dir("find_near_exact"));
+        for i in 0..6u64 {
+            exact.add_result(SearchResultItem::new_exact(0x2000 + i * 8, ValueType::Qword)).unwrap();
+        }
+        let indices: Vec<usize> = exact.find_near(0x2010, 8).unwrap().into_iter().map(|(index, _)| index).collect();
+        assert_eq!(indices, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_fuzzy_find_by_address() {
+        let mut manager = fuzzy_manager("find_by_address", 3);
+        for i in 0..10 {
+            manager.add_result(dword_item(0x1000 + i * 0x10, i as i32)).unwrap();
+        }
+
+        // 内存缓冲区与磁盘区都能找到，索引与 get_results 一致
+        let (index, item) = manager.find_by_address(0x1010).unwrap();
+        assert_eq!((index, item.as_i64()), (1, 1));
+        let (index, item) = manager.find_by_address(0x1070).unwrap();
+        assert_eq!((index, item.as_i64()), (7, 7));
+        assert_eq!({ manager.get_results(index, 1).unwrap()[0].address }, 0x1070);
+        assert!(manager.find_by_address(0x1008).is_none());
+        assert!(manager.find_by_address(0x2000).is_none());
+
+        manager.remove_result(index).unwrap();
+        assert!(manager.find_by_address(0x1070).is_none());
+        assert_eq!(manager.find_by_address(0x1080).unwrap().0, 7);
+    }
+
+    #[test]
+    fn test_fuzzy_update_values_batch() {
+        let mut manager = fuzzy_manager("update_values_batch", 2);
+        manager.set_history_tracking(true);
+        for i in 0..5 {
+            manager.add_result(dword_item(0x1000 + i * 4, i as i32)).unwrap();
+        }
+
+        let bytes = |value: i32| {
+            let mut buf = [0u8; 8];
+            buf[..4].copy_from_slice(&value.to_le_bytes());
+            buf
+        };
+        // 同时覆盖内存缓冲区（0、1）和磁盘（3、4），越界索引被跳过
+        manager
+            .update_values_batch(&[(0, bytes(100)), (3, bytes(-3)), (4, bytes(400)), (99, bytes(7))])
+            .unwrap();
+
+        let items = manager.get_all_results().unwrap();
+        let values: Vec<i64> = items.iter().map(|item| item.as_i64()).collect();
+        assert_eq!(values, vec![100, 1, 2, -3, 400]);
+        let previous: Vec<i64> = (0..5).map(|i| manager.history(i).unwrap().prev_as_i64(ValueType::Dword)).collect();
+        assert_eq!(previous, vec![0, 1, 2, 3, 4]);
+        assert_eq!(manager.total_count(), 5);
+
+        // 大端管理器按大端解释新值，磁盘上的结果同样如此
+        let mut big = fuzzy_manager("update_values_batch_big", 1).with_byte_order(ByteOrder::Big);
+        let region: Vec<u8> = [10i32, 20, 30].iter().flat_map(|v| v.to_be_bytes()).collect();
+        big.add_regions(vec![(0x1000, region)], ValueType::Dword, 4).unwrap();
+        let be_bytes = |value: i32| {
+            let mut buf = [0u8; 8];
+            buf[..4].copy_from_slice(&value.to_be_bytes());
+            buf
+        };
+        big.update_values_batch(&[(0, be_bytes(-5)), (2, be_bytes(300))]).unwrap();
+        let values: Vec<i64> = big.iter().map(|item| item.as_i64()).collect();
+        assert_eq!(values, vec![-5, 20, 300]);
+    }
+
+    #[test]
+    fn test_fuzzy_item_min_max_tracking() {
+        fn track(mut item: FuzzySearchResultItem, values: &[[u8; 4]]) -> (FuzzySearchResultItem, FuzzyItemHistory) {
+            let mut history = FuzzyItemHistory::new(&item);
+            for value in values {
+                let updated = item.with_new_value(value);
+                history = history.advance(&item, &updated);
+                item = updated;
+            }
+            (item, history)
+        }
+
+        // 血量来回波动，分数只增不减
+        let (hp, history) = track(dword_item(0x1000, 100), &[80i32, 95, 60, 100].map(i32::to_le_bytes));
+        assert_eq!(history.min_as_i64(ValueType::Dword), 60);
+        assert_eq!(history.max_as_i64(ValueType::Dword), 100);
+        assert_eq!(hp.as_i64(), 100);
+
+        let (_, history) = track(dword_item(0x2000, 10), &[20i32, 35, 50].map(i32::to_le_bytes));
+        assert_eq!(history.min_as_i64(ValueType::Dword), 10);
+        assert_eq!(history.max_as_i64(ValueType::Dword), 50);
+
+        let speed = FuzzySearchResultItem::from_bytes(0x3000, &1.5f32.to_le_bytes(), ValueType::Float);
+        let (_, history) = track(speed, &[-2.0f32, 0.5, 3.25].map(f32::to_le_bytes));
+        assert_eq!(history.min_as_f64(ValueType::Float), -2.0);
+        assert_eq!(history.max_as_f64(ValueType::Float), 3.25);
+    }
+
+    #[test]
+    fn test_fuzzy_format_value_integers() {
+        let byte = FuzzySearchResultItem::from_bytes(0x1000, &[0xFF], ValueType::Byte);
+        assert_eq!(byte.format_value(), "255");
+        let word = FuzzySearchResultItem::from_bytes(0x1000, &(-300i16).to_le_bytes(), ValueType::Word);
+        assert_eq!(word.format_value(), "65236");
+        assert_eq!(dword_item(0x1000, -123456).format_value(), "4294843840");
+        assert_eq!(dword_item(0x1000, 123456).format_value(), "123456");
+        let auto = FuzzySearchResultItem::from_bytes(0x1000, &77i32.to_le_bytes(), ValueType::Auto);
+        assert_eq!(auto.format_value(), "77");
+        let qword = FuzzySearchResultItem::from_bytes(0x1000, &i64::MIN.to_le_bytes(), ValueType::Qword);
+        assert_eq!(qword.format_value(), "9223372036854775808");
+        let qword = FuzzySearchResultItem::from_bytes(0x1000, &u64::MAX.to_le_bytes(), ValueType::Qword);
+        assert_eq!(qword.format_value(), "18446744073709551615");
+        let xor = FuzzySearchResultItem::from_bytes(0x1000, &0xDEADBEEFu32.to_le_bytes(), ValueType::Xor);
+        assert_eq!(xor.format_value(), "0xDEADBEEF");
+    }
+
+    #[test]
+    fn test_fuzzy_format_value_floats() {
+        let float = FuzzySearchResultItem::from_bytes(0x1000, &0.1f32.to_le_bytes(), ValueType::Float);
+        assert_eq!(float.format_value(), "0.1");
+        let negative = FuzzySearchResultItem::from_bytes(0x1000, &(-2.5f32).to_le_bytes(), ValueType::Float);
+        assert_eq!(negative.format_value(), "-2.5");
+        let nan = FuzzySearchResultItem::from_bytes(0x1000, &f32::NAN.to_le_bytes(), ValueType::Float);
+        assert_eq!(nan.format_value(), "NaN");
+
+        let double = FuzzySearchResultItem::from_bytes(0x1000, &(-1234.5678f64).to_le_bytes(), ValueType::Double);
+        assert_eq!(double.format_value(), "-1234.5678");
+        let nan = FuzzySearchResultItem::from_bytes(0x1000, &f64::NAN.to_le_bytes(), ValueType::Double);
+        assert_eq!(nan.format_value(), "NaN");
+        let inf = FuzzySearchResultItem::from_bytes(0x1000, &f64::NEG_INFINITY.to_le_bytes(), ValueType::Double);
+        assert_eq!(inf.format_value(), "-inf");
+        let huge = FuzzySearchResultItem::from_bytes(0x1000, &1e300f64.to_le_bytes(), ValueType::Double);
+        assert_eq!(huge.format_value(), "1e300");
+    }
+
+    #[test]
+    fn test_fuzzy_format_value_variable_length() {
+        let text = FuzzySearchResultItem::from_bytes(0x1000, b"hp", ValueType::String);
+        assert_eq!(text.format_value(), "hp");
+        let bytes = FuzzySearchResultItem::from_bytes(0x1000, &[0xDE, 0xAD, 0x01], ValueType::Bytes);
+        assert_eq!(bytes.format_value(), "DE AD 01");
+        // 超过内联长度只保存哈希
+        let long = FuzzySearchResultItem::from_bytes(0x1000, b"player_name", ValueType::String);
+        assert_eq!(long.format_value(), "11 bytes");
+    }
+
+    #[test]
+    fn test_fuzzy_format_prev_value() {
+        let old = dword_item(0x1000, 100);
+        let item = old.with_new_value(&95i32.to_le_bytes());
+        assert_eq!(item.format_value(), "95");
+        assert_eq!(FuzzyItemHistory::new(&old).advance(&old, &item).format_prev_value(ValueType::Dword), "100");
+    }
+
+    #[test]
+    fn test_exact_format_value() {
+        let dword = ExactSearchResultItem::new(0x1000, ValueType::Dword);
+        assert_eq!(dword.format_value(&(-5i32).to_le_bytes()), "4294967291");
+        let float = ExactSearchResultItem::new(0x1000, ValueType::Float);
+        assert_eq!(float.format_value(&f32::NAN.to_le_bytes()), "NaN");
+        let xor = ExactSearchResultItem::new(0x1000, ValueType::Xor);
+        assert_eq!(xor.format_value(&0x10u32.to_le_bytes()), "0x00000010");
+        let text = ExactSearchResultItem::new(0x1000, ValueType::String);
+        assert_eq!(text.format_value(b"gold"), "gold");
+        // 读取的字节不足时
+        let qword = ExactSearchResultItem::new(0x1000, ValueType::Qword);
+        assert_eq!(qword.format_value(&[1, 2, 3]), "N/A");
+    }
+}
@@ -1,6 +1,28 @@
 use anyhow::anyhow;
 use std::fmt;
 
+/// 目标进程中数值的字节序，默认小端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ByteOrder {
+    #[default]
+    Little,
+    /// 少数嵌入式/模拟器目标使用大端
+    Big,
+}
+
+/// 模糊细化时整数与字节序的解释方式，默认按有符号、小端比较
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FuzzyCompareOptions {
+    /// 整数类型按无符号比较，用于计时器、货币等无符号计数器在符号边界附近的增减判断
+    pub unsigned: bool,
+    /// 差值类条件（增加/减少指定量、容差、范围）按值类型的原生宽度回绕计算
+    pub wrapping: bool,
+    /// 新读取字节的字节序
+    pub byte_order: ByteOrder,
+}
+
+/// 内存表示固定为 u8，结果文件中直接存放该字节，见 [`ValueType::from_repr`]
+#[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ValueType {
     Byte,
@@ -11,9 +33,34 @@ pub enum ValueType {
     Double,
     Auto,
     Xor,
+    /// UTF-8 文本（变长）
+    String,
+    /// 任意字节序列（变长）
+    Bytes,
 }
 
 impl ValueType {
+    const ALL: [ValueType; 10] = [
+        Self::Byte,
+        Self::Word,
+        Self::Dword,
+        Self::Qword,
+        Self::Float,
+        Self::Double,
+        Self::Auto,
+        Self::Xor,
+        Self::String,
+        Self::Bytes,
+    ];
+
+    /// 由内存中的判别值字节还原类型（与 [`ValueType::from_id`] 的 id 不同），无效字节返回 None
+    ///
+    /// 用于校验从磁盘映射回来的原始结果，避免把无效判别值当作 `ValueType` 读取
+    #[inline]
+    pub fn from_repr(byte: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|value_type| *value_type as u8 == byte)
+    }
+
     #[inline]
     pub fn from_id(id: i32) -> Option<Self> {
         match id {
@@ -25,6 +72,8 @@ impl ValueType {
             5 => Self::Double.into(),
             6 => Self::Auto.into(),
             7 => Self::Xor.into(),
+            100 => Self::String.into(),
+            102 => Self::Bytes.into(),
             _ => None,
         }
     }
@@ -40,6 +89,8 @@ impl ValueType {
             ValueType::Double => 5,
             ValueType::Auto => 6,
             ValueType::Xor => 7,
+            ValueType::String => 100,
+            ValueType::Bytes => 102,
         }
     }
 
@@ -58,6 +109,7 @@ impl ValueType {
         }
     }
 
+    /// 单个元素的固定大小；变长类型返回 1（按字节对齐扫描），实际长度见结果项
     #[inline]
     pub fn size(&self) -> usize {
         match self {
@@ -69,13 +121,61 @@ impl ValueType {
             ValueType::Double => 8,
             ValueType::Auto => 4,
             ValueType::Xor => 4,
+            ValueType::String | ValueType::Bytes => 1,
         }
     }
 
+    /// 自然对齐字节数，用于对齐扫描；变长类型按字节对齐
+    #[inline]
+    pub fn alignment(&self) -> usize {
+        if self.is_variable_length() { 1 } else { self.size() }
+    }
+
+    #[inline]
+    pub fn is_variable_length(&self) -> bool {
+        matches!(self, ValueType::String | ValueType::Bytes)
+    }
+
     #[inline]
     pub fn is_float_type(&self) -> bool {
         matches!(self, ValueType::Float | ValueType::Double)
     }
+
+    /// 按类型把小端字节格式化为显示文本，字节不足时返回 "N/A"
+    /// 整数为无符号十进制（与结果列表一直以来的显示一致），Xor 为十六进制，浮点数使用最短的可还原表示（过大或过小时用科学计数法），
+    /// String 按 UTF-8 有损解码，Bytes 为空格分隔的十六进制
+    pub fn format_bytes(&self, bytes: &[u8]) -> String {
+        if bytes.len() < self.size() {
+            return "N/A".to_string();
+        }
+        match self {
+            ValueType::Byte => format!("{}", bytes[0]),
+            ValueType::Word => format!("{}", u16::from_le_bytes([bytes[0], bytes[1]])),
+            ValueType::Dword | ValueType::Auto => format!("{}", u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            ValueType::Qword => format!("{}", u64::from_le_bytes(bytes[..8].try_into().unwrap())),
+            ValueType::Float => {
+                let value = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                format_float(value, value as f64)
+            },
+            ValueType::Double => {
+                let value = f64::from_le_bytes(bytes[..8].try_into().unwrap());
+                format_float(value, value)
+            },
+            ValueType::Xor => format!("0x{:08X}", u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            ValueType::String => String::from_utf8_lossy(bytes).into_owned(),
+            ValueType::Bytes => bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" "),
+        }
+    }
+}
+
+/// 浮点数显示：常规范围用最短可还原的十进制，绝对值过大或过小时改用科学计数法，避免几百位的数字
+fn format_float<T: fmt::Display + fmt::LowerExp>(value: T, magnitude: f64) -> String {
+    let magnitude = magnitude.abs();
+    if magnitude.is_finite() && magnitude != 0.0 && !(1e-6..1e16).contains(&magnitude) {
+        format!("{:e}", value)
+    } else {
+        format!("{}", value)
+    }
 }
 
 impl fmt::Display for ValueType {
@@ -89,6 +189,8 @@ impl fmt::Display for ValueType {
             ValueType::Double => write!(f, "Double"),
             ValueType::Auto => write!(f, "Auto"),
             ValueType::Xor => write!(f, "Xor"),
+            ValueType::String => write!(f, "String"),
+            ValueType::Bytes => write!(f, "Bytes"),
         }
     }
 }
@@ -117,6 +219,12 @@ pub enum SearchValue {
         value_type: ValueType,
         exclude: bool,
     },
+    /// 掩码搜索，只比较掩码中为 1 的位：(mem & mask) == (value & mask)，用于只知道部分字节的值
+    Masked {
+        value: [u8; 16],
+        mask: [u8; 16],
+        value_type: ValueType,
+    },
 }
 
 impl SearchValue {
@@ -153,6 +261,16 @@ impl SearchValue {
         }
     }
 
+    /// `value` 为值的原始位（浮点数传 to_bits 的结果），`mask` 按相同字节序应用
+    #[inline]
+    pub fn masked(value: i128, mask: i128, value_type: ValueType) -> Self {
+        SearchValue::Masked {
+            value: i128::to_le_bytes(value),
+            mask: i128::to_le_bytes(mask),
+            value_type,
+        }
+    }
+
     #[inline]
     pub fn value_type(&self) -> ValueType {
         match self {
@@ -160,6 +278,7 @@ impl SearchValue {
             SearchValue::RangeInt { value_type, .. } => *value_type,
             SearchValue::FixedFloat { value_type, .. } => *value_type,
             SearchValue::RangeFloat { value_type, .. } => *value_type,
+            SearchValue::Masked { value_type, .. } => *value_type,
         }
     }
 
@@ -280,6 +399,17 @@ impl SearchValue {
                     Ok(other_value >= *start && other_value <= *end)
                 }
             },
+            SearchValue::Masked { value, mask, value_type } => {
+                let size = value_type.size();
+                if other.len() < size {
+                    return Err(anyhow!("Input slice too small: expected at least {} bytes, got {}", size, other.len()));
+                }
+                Ok(value[..size]
+                    .iter()
+                    .zip(&mask[..size])
+                    .zip(&other[..size])
+                    .all(|((v, m), o)| v & m == o & m))
+            },
         }
     }
 }
@@ -315,6 +445,22 @@ pub enum FuzzyCondition {
     IncreasedByPercent(f32),
     /// 值小于旧值指定百分比
     DecreasedByPercent(f32),
+    /// 当前值位于 [min, max] 闭区间内（两端都包含），与旧值无关
+    /// 浮点类型按 f64 比较整数边界
+    Between(i64, i64),
+    /// 当前值位于 [min, max] 闭区间内（两端都包含），边界为浮点数，用于小数范围
+    BetweenFloat(f64, f64),
+    /// 值在历次细化中（含本次）至少变化了指定次数，基于已存储结果项的变化计数判断
+    ChangedAtLeast(u32),
+    /// 值增加了指定数量，允许偏差 (数量, 容差)：|差值 - 数量| <= 容差，用于带小幅波动的变化量（如伤害）
+    IncreasedByTolerance(i64, i64),
+    /// 值减少了指定数量，允许偏差 (数量, 容差)
+    DecreasedByTolerance(i64, i64),
+    /// 当前值等于指定常量，与旧值无关，用于已知当前值（如界面显示的血量）时快速收敛
+    /// 浮点类型按 f64 比较
+    EqualsNow(i64),
+    /// 当前值等于指定浮点常量，与旧值无关；Float 类型按 f32 精度放宽比较
+    EqualsNowFloat(f64),
 }
 
 impl FuzzyCondition {
@@ -332,6 +478,14 @@ impl FuzzyCondition {
             8 => Some(FuzzyCondition::DecreasedByRange(param1, param2)),
             9 => Some(FuzzyCondition::IncreasedByPercent(param1 as f32 / 100.0)),
             10 => Some(FuzzyCondition::DecreasedByPercent(param1 as f32 / 100.0)),
+            11 => Some(FuzzyCondition::Between(param1, param2)),
+            // 浮点边界以 f64 的原始位传递 (Double.toRawBits)
+            12 => Some(FuzzyCondition::BetweenFloat(f64::from_bits(param1 as u64), f64::from_bits(param2 as u64))),
+            13 => Some(FuzzyCondition::ChangedAtLeast(param1.clamp(0, u32::MAX as i64) as u32)),
+            14 => Some(FuzzyCondition::IncreasedByTolerance(param1, param2)),
+            15 => Some(FuzzyCondition::DecreasedByTolerance(param1, param2)),
+            16 => Some(FuzzyCondition::EqualsNow(param1)),
+            17 => Some(FuzzyCondition::EqualsNowFloat(f64::from_bits(param1 as u64))),
             _ => None,
         }
     }